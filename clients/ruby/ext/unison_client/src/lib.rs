@@ -8,6 +8,8 @@
 //! - `Unison::Client`  — connection lifecycle, wraps `ProtocolClient`
 //! - `Unison::Channel` — request/response + event push, wraps `UnisonChannel`
 //! - `Unison::Error`   — base class for every failure this binding raises
+//! - `Unison::RemoteError` — a structured error returned by the server's
+//!   channel handler (`code` / `details` / `category`)
 //!
 //! Channel payloads cross the boundary as native Ruby values: `serde_magnus`
 //! converts Ruby `Hash`/`Array`/… ⇄ `serde_json::Value`, which the channel's
@@ -22,7 +24,7 @@ use std::sync::OnceLock;
 use magnus::{Error, ExceptionClass, Ruby, Value, function, method, prelude::*};
 use serde_json::Value as JsonValue;
 use tokio::runtime::Runtime;
-use unison::{NetworkError, ProtocolClient, UnisonChannel};
+use unison::{NetworkError, ProtocolClient, ProtocolError, UnisonChannel};
 
 /// Process-wide multi-thread tokio runtime backing every blocking bridge.
 ///
@@ -109,8 +111,28 @@ fn unison_error(msg: impl Into<String>) -> Error {
 }
 
 /// Turns a `NetworkError` into a `Unison::Error`.
+///
+/// `NetworkError::Remote` becomes a `Unison::RemoteError` (defined in
+/// `lib/unison.rb`) carrying the handler's `code` and `details`, mirroring the
+/// Rust `ProtocolError` shape.
 fn net_err(e: NetworkError) -> Error {
-    unison_error(e.to_string())
+    match e {
+        NetworkError::Remote(err) => remote_error(err),
+        e => unison_error(e.to_string()),
+    }
+}
+
+/// Builds a `Unison::RemoteError` from a handler's `ProtocolError`.
+fn remote_error(err: ProtocolError) -> Error {
+    let ruby = ruby();
+    let build = || -> Result<Error, Error> {
+        let class: ExceptionClass = ruby.define_module("Unison")?.const_get("RemoteError")?;
+        let details: Value = serde_magnus::serialize(&ruby, &err.details)?;
+        let exc = class.new_instance((err.message.clone(), err.code, details))?;
+        Ok(exc.into())
+    };
+    // Fall back to a plain Unison::Error if the class is unavailable.
+    build().unwrap_or_else(|_| unison_error(NetworkError::Remote(err).to_string()))
 }
 
 /// The Unison protocol generation this client is built against.
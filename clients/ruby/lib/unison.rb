@@ -18,4 +18,23 @@ require "unison_client/unison_client"
 # `Unison::Channel` wraps `UnisonChannel` (`request` / `send_event` / `recv` /
 # `close`). Channel payloads are native Ruby values.
 module Unison
+  # A structured error returned by the server's channel handler.
+  #
+  # Raised by `Unison::Channel#request` when the handler replies with
+  # `send_error` — the Ruby face of the Rust `NetworkError::Remote(ProtocolError)`.
+  # `code` is application-defined; `details` is any payload (or nil).
+  class RemoteError < Error
+    attr_reader :code, :details
+
+    def initialize(message, code, details = nil)
+      super(message)
+      @code = code
+      @details = details
+    end
+
+    # Error category, matching the Rust / TypeScript `ErrorCategory` value.
+    def category
+      :application
+    end
+  end
 end
@@ -29,4 +29,13 @@ class UnisonTest < Minitest::Test
   def test_error_is_a_standard_error_subclass
     assert_operator Unison::Error, :<, StandardError
   end
+
+  def test_remote_error_carries_the_protocol_error_shape
+    err = Unison::RemoteError.new("memory not found", 404, { "id" => "m-1" })
+    assert_operator Unison::RemoteError, :<, Unison::Error
+    assert_equal "memory not found", err.message
+    assert_equal 404, err.code
+    assert_equal({ "id" => "m-1" }, err.details)
+    assert_equal :application, err.category
+  end
 end
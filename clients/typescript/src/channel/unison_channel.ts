@@ -4,6 +4,7 @@
  * QUIC / WebTransport bidi stream 上の request/response + server-pushed event。
  * 内部で 1 本の recv loop を持ち、 受信 `ProtocolMessage` を `msgType` で振り分ける:
  * - `response` / `error` → `id` 対応の pending request を resolve/reject
+ *   (= `{code, message, details}` shape の error は `UnisonRemoteError` で reject)
  * - `event` / `request` → events() の AsyncIterable queue に流す
  *
  * Rust `network/channel.rs` の `UnisonChannel` に対応する TS port。 wire は
@@ -21,6 +22,8 @@
  */
 
 import type { Codec } from "../codec/codec.js";
+import { JsonCodec } from "../codec/json_codec.js";
+import { UnisonRemoteError } from "../error/remote.js";
 import type { BidiStream } from "../transport/types.js";
 import { defaultCodec } from "./default_codec.js";
 import { AsyncQueue } from "./async_queue.js";
@@ -199,7 +202,7 @@ export class UnisonChannelImpl<M extends ChannelMeta>
       if (pending === undefined) return;
       this.#pending.delete(message.id);
       if (message.msgType === MSG_TYPE_ERROR) {
-        pending.reject(new Error(this.#errorText(message.payload)));
+        pending.reject(this.#errorFor(message.payload));
       } else {
         this.#tryResolve(pending, message.payload);
       }
@@ -225,6 +228,21 @@ export class UnisonChannelImpl<M extends ChannelMeta>
     }
   }
 
+  /**
+   * Error frame の payload から reject 用 error を作る。 Rust の `send_error` 由来
+   * (= 常に JSON の `ProtocolError`) なら `UnisonRemoteError`、 それ以外は従来の
+   * 文字列 Error。
+   */
+  #errorFor(payload: Uint8Array): Error {
+    try {
+      const remote = UnisonRemoteError.fromPayload(JsonCodec.shared.decode(payload));
+      if (remote !== undefined) return remote;
+    } catch {
+      // JSON でない payload — 文字列 Error にフォールバック
+    }
+    return new Error(this.#errorText(payload));
+  }
+
   #errorText(payload: Uint8Array): string {
     try {
       return `channel "${this.name}" request error: ${JSON.stringify(this.#codec.decode(payload))}`;
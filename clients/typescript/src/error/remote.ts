@@ -0,0 +1,45 @@
+/**
+ * handler が返した構造化エラー (v1.0 で追加)。
+ *
+ * Rust 側 `UnisonChannel::send_error(request_id, ProtocolError { code, message, details })`
+ * が送る `Error` frame の payload (= 常に JSON) を表す。 Rust client の
+ * `NetworkError::Remote(ProtocolError)` に対応し、 category は常に `application`。
+ */
+
+import type { ErrorCategory } from "./category.js";
+
+/** Rust `unison::ProtocolError` と同じ JSON shape */
+export interface ProtocolError {
+  code: number;
+  message: string;
+  details?: unknown;
+}
+
+/** peer の handler が返した構造化エラー */
+export class UnisonRemoteError extends Error {
+  /** エラー分類 (Rust `NetworkError::Remote` と同じく `application`) */
+  readonly category: ErrorCategory = "application";
+  /** アプリケーション定義のエラーコード */
+  readonly code: number;
+  /** 任意の追加情報 (= 省略時 undefined) */
+  readonly details: unknown;
+
+  constructor(error: ProtocolError, options?: ErrorOptions) {
+    super(error.message, options);
+    this.name = new.target.name;
+    this.code = error.code;
+    this.details = error.details;
+  }
+
+  /**
+   * decode 済み payload が `ProtocolError` shape なら `UnisonRemoteError` を返す。
+   * それ以外 (= 内部生成の `{"error": ...}` 等) は `undefined`。
+   */
+  static fromPayload(value: unknown): UnisonRemoteError | undefined {
+    if (typeof value !== "object" || value === null) return undefined;
+    const { code, message, details } = value as Record<string, unknown>;
+    if (typeof code !== "number" || !Number.isInteger(code)) return undefined;
+    if (typeof message !== "string") return undefined;
+    return new UnisonRemoteError({ code, message, details: details ?? undefined });
+  }
+}
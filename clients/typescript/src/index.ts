@@ -13,6 +13,9 @@
 // === Phase 5: error category framework (UNS-15) ===
 export type { ErrorCategory } from "./error/category.js";
 export { ERROR_CATEGORIES } from "./error/category.js";
+// v1.0: handler の構造化エラー (= Rust `NetworkError::Remote`)
+export type { ProtocolError } from "./error/remote.js";
+export { UnisonRemoteError } from "./error/remote.js";
 
 // === Phase 2b: transport === (= 実装中)
 export type {
@@ -0,0 +1,31 @@
+import { describe, expect, it } from "vitest";
+import { UnisonRemoteError } from "../../src/error/remote.js";
+
+describe("UnisonRemoteError", () => {
+  it("is built from a ProtocolError-shaped payload", () => {
+    const err = UnisonRemoteError.fromPayload({ code: 404, message: "not found" });
+    expect(err).toBeInstanceOf(UnisonRemoteError);
+    expect(err?.code).toBe(404);
+    expect(err?.message).toBe("not found");
+    expect(err?.details).toBeUndefined();
+    expect(err?.category).toBe("application");
+    expect(err?.name).toBe("UnisonRemoteError");
+  });
+
+  it("keeps details when present", () => {
+    const err = UnisonRemoteError.fromPayload({
+      code: 1,
+      message: "x",
+      details: { id: "m1" },
+    });
+    expect(err?.details).toEqual({ id: "m1" });
+  });
+
+  it("rejects payloads that are not ProtocolError-shaped", () => {
+    expect(UnisonRemoteError.fromPayload({ error: "connection closed" })).toBeUndefined();
+    expect(UnisonRemoteError.fromPayload({ code: "404", message: "x" })).toBeUndefined();
+    expect(UnisonRemoteError.fromPayload({ code: 1.5, message: "x" })).toBeUndefined();
+    expect(UnisonRemoteError.fromPayload(null)).toBeUndefined();
+    expect(UnisonRemoteError.fromPayload("oops")).toBeUndefined();
+  });
+});
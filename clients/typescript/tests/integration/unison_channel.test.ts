@@ -9,6 +9,7 @@ import { describe, expect, it, vi } from "vitest";
 import { UnisonChannelImpl } from "../../src/channel/unison_channel.js";
 import type { ChannelMeta } from "../../src/channel/types.js";
 import { JsonCodec } from "../../src/codec/json_codec.js";
+import { UnisonRemoteError } from "../../src/error/remote.js";
 import type { ChannelPayload } from "../../src/channel/types.js";
 import { MockConnection } from "./mock_transport.js";
 import { StreamServerStub } from "./server_stub.js";
@@ -64,6 +65,25 @@ describe("UnisonChannel E2E over mock connection", () => {
     await stub.close();
   });
 
+  it("request() rejects with UnisonRemoteError on a structured error frame", async () => {
+    const { channel, stub } = await openChannelPair();
+    const reqPromise = channel.request("SubscribeMetric", { names: [] });
+    await stub.pushError(1, "error", {
+      code: 403,
+      message: "metric access denied",
+      details: { metric: "cpu" },
+    });
+    const err = await reqPromise.catch((e: unknown) => e);
+    expect(err).toBeInstanceOf(UnisonRemoteError);
+    const remote = err as UnisonRemoteError;
+    expect(remote.code).toBe(403);
+    expect(remote.message).toBe("metric access denied");
+    expect(remote.details).toEqual({ metric: "cpu" });
+    expect(remote.category).toBe("application");
+    await channel.close();
+    await stub.close();
+  });
+
   it("events() receives server-pushed events as an AsyncIterable", async () => {
     const { channel, stub } = await openChannelPair();
     const received: ChannelPayload[] = [];
@@ -13,7 +13,7 @@ use clap::Args;
 use unison::ProtocolClient;
 use unison::network::quic::QuicClient;
 
-use crate::TrustMode;
+use crate::TrustArgs;
 
 #[derive(Args)]
 pub struct CallArgs {
@@ -32,9 +32,8 @@ pub struct CallArgs {
     #[arg(short, long, default_value = "{}")]
     pub payload: String,
 
-    /// trust anchor mode (default: skip)
-    #[arg(long, value_enum, default_value = "skip")]
-    pub trust: TrustMode,
+    #[command(flatten)]
+    pub trust: TrustArgs,
 
     /// response 待ちタイムアウト (ミリ秒、省略時は channel 既定値)
     #[arg(long)]
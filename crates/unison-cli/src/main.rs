@@ -11,7 +11,9 @@
 //! - `mock --schema F`   — KDL schema から stub server を起動
 //! - `schema-lint F`     — KDL schema を parse + invariant 検証
 
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
 
 mod call;
 mod mock;
@@ -62,13 +64,47 @@ async fn main() -> anyhow::Result<()> {
     }
 }
 
-/// `--trust` フラグ共通定義 (= ping / call / sniff で共有)
+/// `--trust` / `--ca-file` フラグ共通定義 (= ping / call / sniff で共有)
+#[derive(Args, Debug)]
+pub struct TrustArgs {
+    /// trust anchor mode (default: skip — dev self-signed 用)
+    #[arg(long, value_enum, default_value = "skip")]
+    pub trust: TrustMode,
+
+    /// この CA (PEM ファイル、 またはその入ったディレクトリ) を信頼する (= 社内 CA 向け)
+    #[arg(long, value_name = "PATH", conflicts_with = "trust")]
+    pub ca_file: Option<PathBuf>,
+}
+
+impl TrustArgs {
+    /// `unison::network::TrustAnchors` へ変換 (= `--ca-file` 優先)
+    pub fn to_anchors(&self) -> unison::network::TrustAnchors {
+        match &self.ca_file {
+            Some(path) if path.is_dir() => unison::network::TrustAnchors::FromDir(path.clone()),
+            Some(path) => unison::network::TrustAnchors::FromPemFile(path.clone()),
+            None => self.trust.to_anchors(),
+        }
+    }
+}
+
+impl std::fmt::Display for TrustArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.ca_file {
+            Some(path) => write!(f, "ca-file={}", path.display()),
+            None => write!(f, "{:?}", self.trust),
+        }
+    }
+}
+
+/// `--trust` の選択肢
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 pub enum TrustMode {
     /// cert 検証を skip (dev 用、self-signed server 向け)
     Skip,
-    /// OS / webpki-roots の trust store を使う (public server 向け)
+    /// webpki-roots 同梱の Mozilla root を使う (public server 向け)
     System,
+    /// OS の trust store を使う (= 端末に入れた社内 CA も信頼される)
+    Native,
 }
 
 impl TrustMode {
@@ -77,6 +113,7 @@ impl TrustMode {
         match self {
             Self::Skip => unison::network::TrustAnchors::SkipVerification,
             Self::System => unison::network::TrustAnchors::System,
+            Self::Native => unison::network::TrustAnchors::OsNative,
         }
     }
 }
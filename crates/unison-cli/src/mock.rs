@@ -6,6 +6,7 @@
 //!
 //! stub 応答は field 型から決定的に生成する (string→"", int→0, bool→false,
 //! json/object→{}, float→0.0)。`returns` を持たない request には空 object を返す。
+//! `returns ... stream=#true` の request には stub を 1 item 流してから `end_stream` する。
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -14,7 +15,7 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use clap::Args;
 use unison::network::quic::UnisonStream;
-use unison::network::{MessageType, UnisonChannel};
+use unison::network::{MessageType, NetworkError, UnisonChannel};
 use unison::parser::{ChannelBackend, Field, FieldType, SchemaParser};
 use unison::{ProtocolServer, UnisonProtocol};
 
@@ -29,8 +30,16 @@ pub struct MockArgs {
     pub addr: String,
 }
 
-/// 1 request の stub 仕様: method 名 → 返す payload。
-type StubTable = HashMap<String, serde_json::Value>;
+/// 1 request の stub 応答。
+struct Stub {
+    /// 返す payload
+    payload: serde_json::Value,
+    /// Server-streaming request か (= `returns ... stream=#true`)
+    stream: bool,
+}
+
+/// 1 request の stub 仕様: method 名 → stub 応答。
+type StubTable = HashMap<String, Stub>;
 
 pub async fn run(args: MockArgs) -> Result<()> {
     let src = std::fs::read_to_string(&args.schema)
@@ -76,7 +85,13 @@ pub async fn run(args: MockArgs) -> Result<()> {
                 .as_ref()
                 .map(|r| stub_object(&r.fields))
                 .unwrap_or_else(|| serde_json::json!({}));
-            stubs.insert(req.name.clone(), payload);
+            stubs.insert(
+                req.name.clone(),
+                Stub {
+                    payload,
+                    stream: req.is_streaming(),
+                },
+            );
         }
         let stubs = Arc::new(stubs);
         let chan_name = channel.name.clone();
@@ -111,17 +126,25 @@ async fn handle_channel(
     chan_name: String,
     stubs: Arc<StubTable>,
     stream: UnisonStream,
-) -> Result<(), unison::network::NetworkError> {
+) -> Result<(), NetworkError> {
     let channel: UnisonChannel = UnisonChannel::new(stream);
     loop {
         match channel.recv().await {
             Ok(msg) if msg.msg_type == MessageType::Request => {
-                let reply = stubs
-                    .get(&msg.method)
-                    .cloned()
+                let stub = stubs.get(&msg.method);
+                let reply = stub
+                    .map(|s| s.payload.clone())
                     .unwrap_or_else(|| serde_json::json!({}));
                 tracing::info!(channel = %chan_name, method = %msg.method, "mock stub reply");
-                channel.send_response(msg.id, &msg.method, &reply).await?;
+                if stub.is_some_and(|s| s.stream) {
+                    match channel.send_stream_item(msg.id, &msg.method, &reply).await {
+                        Ok(()) => channel.end_stream(msg.id).await?,
+                        Err(NetworkError::Cancelled { .. }) => {}
+                        Err(e) => return Err(e),
+                    }
+                } else {
+                    channel.send_response(msg.id, &msg.method, &reply).await?;
+                }
             }
             Ok(msg) => {
                 // Event 等 — mock は受け流すだけ
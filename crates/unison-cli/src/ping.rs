@@ -10,16 +10,15 @@ use clap::Args;
 use unison::ProtocolClient;
 use unison::network::quic::QuicClient;
 
-use crate::TrustMode;
+use crate::TrustArgs;
 
 #[derive(Args)]
 pub struct PingArgs {
     /// Unison サーバの URL (例: `quic://[::1]:7878`)
     pub url: String,
 
-    /// trust anchor mode (default: skip — dev self-signed 用)
-    #[arg(long, value_enum, default_value = "skip")]
-    pub trust: TrustMode,
+    #[command(flatten)]
+    pub trust: TrustArgs,
 
     /// 計測回数
     #[arg(short, long, default_value_t = 4)]
@@ -27,7 +26,7 @@ pub struct PingArgs {
 }
 
 pub async fn run(args: PingArgs) -> Result<()> {
-    println!("PING {} (trust={:?})", args.url, args.trust);
+    println!("PING {} (trust={})", args.url, args.trust);
 
     let mut samples: Vec<Duration> = Vec::with_capacity(args.count as usize);
     let mut identity_shown = false;
@@ -8,13 +8,15 @@
 //! - channel 名の重複
 //! - `request`/`event` 名の channel 内重複
 //! - backend が datagram なのに event を 1 つも持たない (= 無意味な channel)
+//! - `require-role` / `allow-from` を open handshake の無い channel に付けている
+//!   (= datagram channel、 `from="server"` の channel ではサーバーが判定できない)
 
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use clap::Args;
 use unison::UnisonProtocol;
-use unison::parser::{ChannelBackend, ParsedSchema, SchemaParser};
+use unison::parser::{ChannelBackend, ChannelFrom, ParsedSchema, SchemaParser};
 
 #[derive(Args)]
 pub struct SchemaLintArgs {
@@ -86,10 +88,10 @@ fn lint_invariants(schema: &ParsedSchema) -> Vec<String> {
     // datagram channel_id 衝突
     let mut seen_ids: std::collections::HashMap<u64, Vec<&str>> = Default::default();
     for ch in &protocol.channels {
-        if ch.backend() == ChannelBackend::Datagram {
-            if let Some(id) = ch.channel_id {
-                seen_ids.entry(id).or_default().push(ch.name.as_str());
-            }
+        if ch.backend() == ChannelBackend::Datagram
+            && let Some(id) = ch.channel_id
+        {
+            seen_ids.entry(id).or_default().push(ch.name.as_str());
         }
     }
     for (id, owners) in &seen_ids {
@@ -121,6 +123,23 @@ fn lint_invariants(schema: &ParsedSchema) -> Vec<String> {
                 ));
             }
         }
+        // アクセスポリシーはサーバーが受ける `__channel:` open でしか判定されない
+        // (= 書式は Channel::validate で検査済み)
+        if ch.require_role.is_some() || ch.allow_from.is_some() {
+            if ch.backend() == ChannelBackend::Datagram {
+                errs.push(format!(
+                    "channel \"{}\": require-role/allow-from on backend=\"datagram\" \
+                     is never enforced (= datagram channels have no open handshake)",
+                    ch.name
+                ));
+            } else if ch.from == ChannelFrom::Server {
+                errs.push(format!(
+                    "channel \"{}\": require-role/allow-from on from=\"server\" \
+                     is never enforced (= the server opens this channel itself)",
+                    ch.name
+                ));
+            }
+        }
         if ch.backend() == ChannelBackend::Datagram && ch.events.is_empty() {
             errs.push(format!(
                 "channel \"{}\": backend=\"datagram\" but declares no event \
@@ -149,11 +168,18 @@ fn report_summary(schema: &ParsedSchema) {
             .channel_id
             .map(|i| format!(" channel_id={i}"))
             .unwrap_or_default();
+        let policy = ch
+            .policy()
+            .ok()
+            .flatten()
+            .map(|p| format!(" {p}"))
+            .unwrap_or_default();
         println!(
-            "    - {} [backend={:?}{}] {} request(s), {} event(s)",
+            "    - {} [backend={:?}{}{}] {} request(s), {} event(s)",
             ch.name,
             ch.backend(),
             id,
+            policy,
             ch.requests.len(),
             ch.events.len(),
         );
@@ -14,7 +14,7 @@ use clap::Args;
 use unison::ProtocolClient;
 use unison::network::quic::QuicClient;
 
-use crate::TrustMode;
+use crate::TrustArgs;
 
 #[derive(Args)]
 pub struct SniffArgs {
@@ -25,9 +25,8 @@ pub struct SniffArgs {
     #[arg(short, long)]
     pub channel: String,
 
-    /// trust anchor mode (default: skip)
-    #[arg(long, value_enum, default_value = "skip")]
-    pub trust: TrustMode,
+    #[command(flatten)]
+    pub trust: TrustArgs,
 
     /// この件数を観測したら終了 (0 = 無制限、Ctrl-C で停止)
     #[arg(short = 'n', long, default_value_t = 0)]
@@ -5,6 +5,8 @@
 //! - `unison_call` — 任意 channel を open して payload を送信、response を返す
 //! - `unison_channel_list` — **TODO**: サーバ側登録済み channel を列挙 (要 Unison 側 API 追加)
 
+use std::path::PathBuf;
+
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
@@ -44,18 +46,19 @@ impl Default for UnisonProbe {
 /// Trust mode selector for the probe (matches `unison::TrustAnchors`).
 ///
 /// - `"skip"`: skip cert verification (dev only, against self-signed servers)
-/// - `"system"`: use OS/webpki-roots trust store (for public servers)
-#[derive(Debug, Deserialize, JsonSchema)]
+/// - `"system"`: use the bundled webpki-roots trust store (for public servers)
+/// - `"native"`: use the OS trust store (includes locally installed CAs)
+/// - `{"ca_file": "/path/ca.pem"}`: trust this PEM bundle, or every PEM file
+///   in this directory (for an internal CA)
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TrustMode {
+    #[default]
     Skip,
     System,
-}
-
-impl Default for TrustMode {
-    fn default() -> Self {
-        Self::Skip
-    }
+    Native,
+    #[serde(rename = "ca_file")]
+    CaFile(PathBuf),
 }
 
 impl TrustMode {
@@ -63,6 +66,11 @@ impl TrustMode {
         match self {
             Self::Skip => unison::network::TrustAnchors::SkipVerification,
             Self::System => unison::network::TrustAnchors::System,
+            Self::Native => unison::network::TrustAnchors::OsNative,
+            Self::CaFile(path) if path.is_dir() => {
+                unison::network::TrustAnchors::FromDir(path.clone())
+            }
+            Self::CaFile(path) => unison::network::TrustAnchors::FromPemFile(path.clone()),
         }
     }
 }
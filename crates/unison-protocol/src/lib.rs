@@ -79,7 +79,7 @@ use parser::{ParseError as UnisonParseError, ParsedSchema, SchemaParser};
 // よく使用されるトレイトとクライアント/サーバーの再エクスポート
 pub use network::{
     ConnectionEvent, ConnectionEventReceiver, ErrorCategory, NetworkError, ProtocolClient,
-    ProtocolServer, ServerHandle, UnisonChannel,
+    ProtocolError, ProtocolServer, ServerHandle, UnisonChannel,
 };
 
 /// Unison Protocolのメインエントリポイント
@@ -0,0 +1,526 @@
+//! 接続単位の認証 handshake (v1.0 で追加)
+//!
+//! Identity handshake はサーバー → クライアントの一方向で、 クライアントが誰かを示す
+//! 手段が無かった。 `ProtocolServer::set_authenticator()` を設定したサーバーは、
+//! 予約 method `__auth` のストリームで認証が済むまで `__channel:` の open を
+//! `unauthenticated` で nack する。
+//!
+//! ```rust,ignore
+//! // server
+//! server
+//!     .set_authenticator(
+//!         TokenAuthenticator::new().with_token("s3cret", Principal::new("svc-a").with_roles(["admin"])),
+//!     )
+//!     .await;
+//!
+//! // client
+//! let client = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("s3cret"));
+//! client.connect(url).await?; // 認証失敗は NetworkError::Unauthenticated
+//!
+//! // channel handler
+//! let who = ctx.principal().map(|p| p.id.as_str());
+//! ```
+//!
+//! # wire
+//!
+//! クライアントが新しい双方向ストリームを開き、 `__auth` の Request を送る:
+//!
+//! - bearer: payload `{"scheme":"bearer","token":"..."}`
+//! - HMAC: payload `{"scheme":"hmac","key_id":"..."}`。 サーバーは同じ id の Response
+//!   `{"challenge":"<hex>"}` (32 byte の乱数) を返し、 クライアントは
+//!   `HMAC-SHA256(secret, challenge)` を同じストリームに Request `{"signature":"<hex>"}`
+//!   として送る
+//!
+//! 成功するとサーバーは Response `{"principal":{...}}` を返し、 失敗すると
+//! [`ProtocolError::UNAUTHENTICATED`] の Error を返す。 試行は接続ごとに 1 回だけで、
+//! 2 回目以降の `__auth` は結果に関わらず拒否する (= 同じ接続で資格情報を総当たり
+//! させない)。 認証されないまま [`AUTH_TIMEOUT`] が過ぎた接続はサーバーが閉じる。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::conn::{BoxUnisonRecv, BoxUnisonSend, UnisonConn};
+use super::context::ConnectionContext;
+use super::frame::{AUTH_METHOD, FRAME_TYPE_PROTOCOL, read_typed_frame, write_typed_frame};
+use super::{MessageType, NetworkError, ProtocolError, ProtocolFrame, ProtocolMessage};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// 認証を待つ時間 (= 過ぎても未認証の接続はサーバーが閉じる)
+pub const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HMAC challenge の長さ (byte)
+const CHALLENGE_LEN: usize = 32;
+
+/// 認証された相手
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Principal {
+    /// 相手の識別子 (= token / key に紐づけた名前)
+    pub id: String,
+    /// 認可判断用のロール
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl Principal {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            roles: Vec::new(),
+        }
+    }
+
+    /// ロールを設定（ビルダーパターン）
+    pub fn with_roles<I, S>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// `role` を持つか
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+/// クライアントが提示する資格情報
+///
+/// `Debug` は秘密を表示しない。
+#[derive(Clone)]
+pub enum Credentials {
+    /// bearer token をそのまま送る
+    Bearer(String),
+    /// サーバーの challenge に `secret` で HMAC-SHA256 署名する (= secret は送らない)
+    Hmac { key_id: String, secret: Vec<u8> },
+}
+
+impl Credentials {
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::Bearer(token.into())
+    }
+
+    pub fn hmac(key_id: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self::Hmac {
+            key_id: key_id.into(),
+            secret: secret.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bearer(_) => f.write_str("Credentials::Bearer(..)"),
+            Self::Hmac { key_id, .. } => f
+                .debug_struct("Credentials::Hmac")
+                .field("key_id", key_id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// サーバーが [`Authenticator`] に渡す認証要求
+#[derive(Clone)]
+pub enum AuthRequest {
+    Bearer {
+        token: String,
+    },
+    /// `signature` はクライアントが `challenge` に付けた HMAC-SHA256
+    /// (= [`verify_hmac`] で検証する)
+    Hmac {
+        key_id: String,
+        challenge: Vec<u8>,
+        signature: Vec<u8>,
+    },
+}
+
+impl std::fmt::Debug for AuthRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bearer { .. } => f.write_str("AuthRequest::Bearer { .. }"),
+            Self::Hmac { key_id, .. } => f
+                .debug_struct("AuthRequest::Hmac")
+                .field("key_id", key_id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// 接続の認証を行う
+///
+/// `Err(reason)` はそのままクライアントの [`NetworkError::Unauthenticated`] になる。
+pub trait Authenticator: Send + Sync + 'static {
+    fn authenticate<'a>(
+        &'a self,
+        request: &'a AuthRequest,
+    ) -> BoxFuture<'a, Result<Principal, String>>;
+}
+
+/// `challenge` への HMAC-SHA256 署名
+pub fn sign_hmac(secret: &[u8], challenge: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::sign(&key, challenge).as_ref().to_vec()
+}
+
+/// HMAC-SHA256 署名を定数時間で検証する
+pub fn verify_hmac(secret: &[u8], challenge: &[u8], signature: &[u8]) -> bool {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::verify(&key, challenge, signature).is_ok()
+}
+
+/// 固定の bearer token 表で認証する
+#[derive(Default)]
+pub struct TokenAuthenticator {
+    tokens: HashMap<String, Principal>,
+}
+
+impl TokenAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// token と対応する principal を登録（ビルダーパターン）
+    pub fn with_token(mut self, token: impl Into<String>, principal: Principal) -> Self {
+        self.tokens.insert(token.into(), principal);
+        self
+    }
+}
+
+impl Authenticator for TokenAuthenticator {
+    fn authenticate<'a>(
+        &'a self,
+        request: &'a AuthRequest,
+    ) -> BoxFuture<'a, Result<Principal, String>> {
+        let result = match request {
+            AuthRequest::Bearer { token } => self
+                .tokens
+                .get(token)
+                .cloned()
+                .ok_or_else(|| "invalid token".to_string()),
+            AuthRequest::Hmac { .. } => Err("unsupported scheme: hmac".to_string()),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+/// key id ごとの共有鍵で HMAC challenge を検証する
+#[derive(Default)]
+pub struct HmacAuthenticator {
+    keys: HashMap<String, (Vec<u8>, Principal)>,
+}
+
+impl HmacAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// key id・共有鍵・principal を登録（ビルダーパターン）
+    pub fn with_key(
+        mut self,
+        key_id: impl Into<String>,
+        secret: impl Into<Vec<u8>>,
+        principal: Principal,
+    ) -> Self {
+        self.keys.insert(key_id.into(), (secret.into(), principal));
+        self
+    }
+}
+
+impl Authenticator for HmacAuthenticator {
+    fn authenticate<'a>(
+        &'a self,
+        request: &'a AuthRequest,
+    ) -> BoxFuture<'a, Result<Principal, String>> {
+        let result = match request {
+            AuthRequest::Hmac {
+                key_id,
+                challenge,
+                signature,
+            } => match self.keys.get(key_id) {
+                Some((secret, principal)) if verify_hmac(secret, challenge, signature) => {
+                    Ok(principal.clone())
+                }
+                // 未知の key id と署名不一致は区別しない
+                _ => Err("invalid signature".to_string()),
+            },
+            AuthRequest::Bearer { .. } => Err("unsupported scheme: bearer".to_string()),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+/// `__auth` ストリームのサーバー側 (= `handle_connection` から呼ばれる)
+pub(crate) async fn serve_auth(
+    authenticator: Option<Arc<dyn Authenticator>>,
+    ctx: &ConnectionContext,
+    request: ProtocolMessage,
+    mut send: BoxUnisonSend,
+    mut recv: BoxUnisonRecv,
+) {
+    let id = request.id;
+    let result = match authenticator {
+        None => Err("server does not accept credentials".to_string()),
+        Some(_) if ctx.principal().is_some() => Err("already authenticated".to_string()),
+        Some(_) if !ctx.begin_auth() => Err("authentication already attempted".to_string()),
+        Some(authenticator) => match read_attempt(id, &request, &mut send, &mut recv).await {
+            Ok(attempt) => authenticator.authenticate(&attempt).await,
+            Err(e) => Err(e),
+        },
+    };
+    let result = result.and_then(|principal| {
+        let payload = serde_json::json!({ "principal": principal });
+        let principal_id = principal.id.clone();
+        if ctx.set_principal(principal) {
+            Ok((principal_id, payload))
+        } else {
+            Err("already authenticated".to_string())
+        }
+    });
+    let reply = match result {
+        Ok((principal_id, payload)) => {
+            info!(
+                "Connection {} authenticated as '{}'",
+                ctx.connection_id, principal_id
+            );
+            auth_message(id, MessageType::Response, payload)
+        }
+        Err(reason) => {
+            warn!(
+                "Authentication failed for connection {}: {}",
+                ctx.connection_id, reason
+            );
+            serde_json::to_value(ProtocolError::new(ProtocolError::UNAUTHENTICATED, reason))
+                .map_err(NetworkError::from)
+                .and_then(|payload| auth_message(id, MessageType::Error, payload))
+        }
+    };
+    let sent = match reply {
+        Ok(msg) => write_auth_message(&mut send, &msg).await,
+        Err(e) => Err(e),
+    };
+    match sent {
+        Ok(()) => {
+            let _ = send.finish().await;
+        }
+        Err(e) => warn!("Failed to send auth reply: {}", e),
+    }
+}
+
+/// 最初の `__auth` Request (と HMAC なら署名) から認証要求を組み立てる
+async fn read_attempt(
+    id: u64,
+    request: &ProtocolMessage,
+    send: &mut BoxUnisonSend,
+    recv: &mut BoxUnisonRecv,
+) -> Result<AuthRequest, String> {
+    let payload = request.payload_as_value().map_err(|e| e.to_string())?;
+    match payload["scheme"].as_str() {
+        Some("bearer") => {
+            let token = payload["token"]
+                .as_str()
+                .ok_or("missing token")?
+                .to_string();
+            Ok(AuthRequest::Bearer { token })
+        }
+        Some("hmac") => {
+            let key_id = payload["key_id"]
+                .as_str()
+                .ok_or("missing key_id")?
+                .to_string();
+            let mut challenge = vec![0u8; CHALLENGE_LEN];
+            SystemRandom::new()
+                .fill(&mut challenge)
+                .map_err(|_| "failed to generate challenge".to_string())?;
+            let msg = auth_message(
+                id,
+                MessageType::Response,
+                serde_json::json!({ "challenge": to_hex(&challenge) }),
+            )
+            .map_err(|e| e.to_string())?;
+            write_auth_message(send, &msg)
+                .await
+                .map_err(|e| e.to_string())?;
+            let answer = read_auth_message(recv).await.map_err(|e| e.to_string())?;
+            let answer = answer.payload_as_value().map_err(|e| e.to_string())?;
+            let signature = answer["signature"]
+                .as_str()
+                .and_then(from_hex)
+                .ok_or("missing or malformed signature")?;
+            Ok(AuthRequest::Hmac {
+                key_id,
+                challenge,
+                signature,
+            })
+        }
+        Some(other) => Err(format!("unsupported scheme: {}", other)),
+        None => Err("missing scheme".to_string()),
+    }
+}
+
+/// `__auth` ストリームのクライアント側 (= `ProtocolClient::connect` から呼ばれる)
+pub(crate) async fn authenticate(
+    connection: &Arc<dyn UnisonConn>,
+    credentials: &Credentials,
+) -> Result<Principal, NetworkError> {
+    let (mut send, mut recv) = connection
+        .open_bi()
+        .await
+        .map_err(|e| NetworkError::Quic(format!("Failed to open auth stream: {}", e)))?;
+    let id = super::generate_request_id();
+    let hello = match credentials {
+        Credentials::Bearer(token) => serde_json::json!({ "scheme": "bearer", "token": token }),
+        Credentials::Hmac { key_id, .. } => {
+            serde_json::json!({ "scheme": "hmac", "key_id": key_id })
+        }
+    };
+    write_auth_message(&mut send, &auth_message(id, MessageType::Request, hello)?).await?;
+
+    let mut reply = read_auth_message(&mut recv).await?;
+    if let Credentials::Hmac { secret, .. } = credentials
+        && reply.msg_type == MessageType::Response
+    {
+        let challenge = reply
+            .payload_as_value()?
+            .get("challenge")
+            .and_then(|c| c.as_str())
+            .and_then(from_hex)
+            .ok_or_else(|| NetworkError::Protocol("Auth: missing challenge".to_string()))?;
+        let signature = to_hex(&sign_hmac(secret, &challenge));
+        let answer = auth_message(
+            id,
+            MessageType::Request,
+            serde_json::json!({ "signature": signature }),
+        )?;
+        write_auth_message(&mut send, &answer).await?;
+        reply = read_auth_message(&mut recv).await?;
+    }
+    let _ = send.finish().await;
+
+    let payload = reply.payload_as_value()?;
+    match reply.msg_type {
+        MessageType::Response => serde_json::from_value(payload["principal"].clone())
+            .map_err(|e| NetworkError::Protocol(format!("Auth: invalid principal: {}", e))),
+        MessageType::Error => {
+            let message = serde_json::from_value::<ProtocolError>(payload.clone())
+                .map(|err| err.message)
+                .unwrap_or_else(|_| payload.to_string());
+            Err(NetworkError::Unauthenticated(message))
+        }
+        other => Err(NetworkError::Protocol(format!(
+            "Auth: unexpected reply {:?}",
+            other
+        ))),
+    }
+}
+
+fn auth_message(
+    id: u64,
+    msg_type: MessageType,
+    payload: serde_json::Value,
+) -> Result<ProtocolMessage, NetworkError> {
+    ProtocolMessage::new_with_json(id, AUTH_METHOD.to_string(), msg_type, payload)
+}
+
+async fn write_auth_message(
+    send: &mut BoxUnisonSend,
+    msg: &ProtocolMessage,
+) -> Result<(), NetworkError> {
+    let frame = msg.clone().into_control_frame()?;
+    write_typed_frame(send, FRAME_TYPE_PROTOCOL, &frame.to_bytes())
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to send auth frame: {}", e)))
+}
+
+async fn read_auth_message(recv: &mut BoxUnisonRecv) -> Result<ProtocolMessage, NetworkError> {
+    let (frame_type, bytes) = read_typed_frame(recv)
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to read auth frame: {}", e)))?;
+    if frame_type != FRAME_TYPE_PROTOCOL {
+        return Err(NetworkError::Protocol(format!(
+            "Auth: unexpected frame type 0x{:02x}",
+            frame_type
+        )));
+    }
+    let msg = ProtocolMessage::from_frame(&ProtocolFrame::from_bytes(&bytes)?)?;
+    if msg.method != AUTH_METHOD {
+        return Err(NetworkError::Protocol(format!(
+            "Auth: expected method '{}', got '{}'",
+            AUTH_METHOD, msg.method
+        )));
+    }
+    Ok(msg)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_sign_and_verify_round_trip() {
+        let challenge = [7u8; CHALLENGE_LEN];
+        let signature = sign_hmac(b"secret", &challenge);
+        assert!(verify_hmac(b"secret", &challenge, &signature));
+        assert!(!verify_hmac(b"other", &challenge, &signature));
+        assert!(!verify_hmac(b"secret", &[0u8; CHALLENGE_LEN], &signature));
+    }
+
+    #[test]
+    fn hex_round_trip_and_rejects_malformed() {
+        let bytes = vec![0x00, 0x7f, 0xff, 0x10];
+        assert_eq!(to_hex(&bytes), "007fff10");
+        assert_eq!(from_hex("007fff10"), Some(bytes));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+
+    #[tokio::test]
+    async fn token_authenticator_maps_tokens() {
+        let auth = TokenAuthenticator::new().with_token("t1", Principal::new("alice"));
+        let ok = auth
+            .authenticate(&AuthRequest::Bearer {
+                token: "t1".to_string(),
+            })
+            .await;
+        assert_eq!(ok.unwrap().id, "alice");
+        let bad = auth
+            .authenticate(&AuthRequest::Bearer {
+                token: "t2".to_string(),
+            })
+            .await;
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn credentials_debug_hides_secrets() {
+        let bearer = format!("{:?}", Credentials::bearer("very-secret"));
+        assert!(!bearer.contains("very-secret"));
+        let hmac = format!("{:?}", Credentials::hmac("k1", b"very-secret".to_vec()));
+        assert!(hmac.contains("k1"));
+        assert!(!hmac.contains("very-secret"));
+    }
+}
@@ -14,6 +14,7 @@
 //! | Internal cluster mesh (server↔server) | [`CertSource::SelfSigned`] via [`InternalMeshKeypair`] |
 //! | Public server (Let's Encrypt etc.) | [`CertSource::Provided`] or [`CertSource::FromFile`] |
 //! | Dev quickstart (localhost only) | [`CertSource::dev_localhost`] |
+//! | Rotated certs (cert-manager, ACME renewals) | [`CertSource::Reloadable`] via [`CertReloader`] |
 //!
 //! # Example
 //!
@@ -29,14 +30,42 @@
 //!     key_path: "/etc/tls/tls.key".into(),
 //! };
 //! ```
+//!
+//! # Hot rotation
+//!
+//! [`CertReloader`] wraps another source and swaps the served certificate
+//! atomically: new handshakes get the new certificate, established
+//! connections keep running. Reload on demand with [`CertReloader::reload`],
+//! or let [`CertReloader::watch_files`] poll the files for changes.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use std::time::Duration;
+//! use unison::network::cert::{CertReloader, CertSource};
+//!
+//! let reloader = CertReloader::new(CertSource::FromFile {
+//!     cert_path: "/etc/tls/tls.crt".into(),
+//!     key_path: "/etc/tls/tls.key".into(),
+//! })?;
+//! let _watcher = reloader.watch_files(Duration::from_secs(30))?;
+//! let source = CertSource::Reloadable(reloader.clone());
+//! // pass `source` to QuicServer::builder().cert_source() / WebTransportServer::new()
+//! # Ok(())
+//! # }
+//! ```
 
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
 use rustls::crypto::ring::sign::any_supported_type;
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
 use rustls::sign::CertifiedKey;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 
 /// Certificate acquisition strategy for a Unison server.
 #[derive(Clone)]
@@ -62,6 +91,11 @@ pub enum CertSource {
         cert_path: PathBuf,
         key_path: PathBuf,
     },
+
+    /// Serve whatever certificate the [`CertReloader`] currently holds (v1.0).
+    ///
+    /// Suitable for: certificates rotated while the process keeps running.
+    Reloadable(Arc<CertReloader>),
 }
 
 impl CertSource {
@@ -92,6 +126,7 @@ impl CertSource {
     /// - [`Self::SelfSigned`]: generates in-memory (no disk I/O)
     /// - [`Self::Provided`]: returns the Arc directly (no work)
     /// - [`Self::FromFile`]: reads files synchronously (blocks the current task briefly)
+    /// - [`Self::Reloadable`]: returns the reloader's current key (no work)
     pub fn resolve(self) -> Result<Arc<CertifiedKey>> {
         match self {
             Self::SelfSigned { subject_alt_names } => generate_self_signed(subject_alt_names),
@@ -100,6 +135,7 @@ impl CertSource {
                 cert_path,
                 key_path,
             } => load_from_files(&cert_path, &key_path),
+            Self::Reloadable(reloader) => Ok(reloader.current()),
         }
     }
 
@@ -156,6 +192,7 @@ impl CertSource {
                     })?;
                 Ok((certs, key.secret_der().to_vec()))
             }
+            Self::Reloadable(reloader) => reloader.current_der(),
             Self::Provided { .. } => anyhow::bail!(
                 "CertSource::Provided は秘密鍵 DER を保持しないため WebTransport では \
                  使用できません。FromFile または SelfSigned を使ってください"
@@ -164,6 +201,204 @@ impl CertSource {
     }
 }
 
+/// Hot-swappable server certificate (v1.0).
+///
+/// Holds the key resolved from an inner [`CertSource`] and re-resolves it on
+/// [`Self::reload`]. Acts as the rustls cert resolver of a QUIC server bound
+/// with [`CertSource::Reloadable`], so a swap takes effect on the next
+/// handshake without touching established connections. WebTransport servers
+/// subscribe via [`Self::subscribe`] and reload their endpoint config from
+/// the same snapshot, so both ingresses serve the certificate whose
+/// [`Self::fingerprint`] is reported.
+///
+/// A [`CertSource::SelfSigned`] source is resolved like
+/// [`CertSource::resolve_der`] does (= a 13-day certificate usable with
+/// WebTransport cert-hash pinning), and a new one is generated on every reload.
+pub struct CertReloader {
+    source: CertSource,
+    /// Key, DER and fingerprint of the served certificate, swapped as one.
+    current: RwLock<Arc<CertSnapshot>>,
+    /// Announces the fingerprint of each swap; sent under the `current` lock.
+    rotations: watch::Sender<String>,
+}
+
+impl std::fmt::Debug for CertReloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CertReloader")
+            .field("fingerprint", &self.snapshot().fingerprint)
+            .finish()
+    }
+}
+
+impl CertReloader {
+    /// Resolve `source` once and wrap it for reloading.
+    ///
+    /// Fails if the initial resolution fails, or if `source` is itself
+    /// [`CertSource::Reloadable`].
+    pub fn new(source: CertSource) -> Result<Arc<Self>> {
+        if matches!(source, CertSource::Reloadable(_)) {
+            anyhow::bail!("CertReloader cannot wrap another CertSource::Reloadable");
+        }
+        let snapshot = CertSnapshot::load(&source)?;
+        info!(
+            "Loaded server certificate (sha256 {})",
+            snapshot.fingerprint
+        );
+        Ok(Arc::new(Self {
+            source,
+            rotations: watch::Sender::new(snapshot.fingerprint.clone()),
+            current: RwLock::new(Arc::new(snapshot)),
+        }))
+    }
+
+    /// The certificate currently served.
+    pub fn current(&self) -> Arc<CertifiedKey> {
+        Arc::clone(&self.snapshot().key)
+    }
+
+    /// DER material of the certificate currently served (= for WebTransport).
+    ///
+    /// Fails for a [`CertSource::Provided`] source, which has no key DER.
+    fn current_der(&self) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
+        self.snapshot().der.clone().context(
+            "CertReloader wraps a CertSource::Provided, which has no private key DER \
+             for WebTransport; use FromFile or SelfSigned",
+        )
+    }
+
+    fn snapshot(&self) -> Arc<CertSnapshot> {
+        Arc::clone(&self.current.read().expect("cert lock poisoned"))
+    }
+
+    /// SHA-256 (lowercase hex) of the current leaf certificate.
+    pub fn fingerprint(&self) -> String {
+        self.snapshot().fingerprint.clone()
+    }
+
+    /// Observe swaps: the receiver yields the new fingerprint after each one.
+    pub fn subscribe(&self) -> watch::Receiver<String> {
+        self.rotations.subscribe()
+    }
+
+    /// Re-resolve the inner source and swap it in; returns the new fingerprint.
+    ///
+    /// On failure (e.g. a half-written file) the current certificate stays in
+    /// place. Reloading an unchanged certificate is a no-op for subscribers.
+    pub fn reload(&self) -> Result<String> {
+        let snapshot = CertSnapshot::load(&self.source)?;
+        let fingerprint = snapshot.fingerprint.clone();
+        // notify under the lock so concurrent reloads announce in swap order
+        let mut current = self.current.write().expect("cert lock poisoned");
+        *current = Arc::new(snapshot);
+        let changed = self.rotations.send_if_modified(|announced| {
+            if *announced == fingerprint {
+                false
+            } else {
+                *announced = fingerprint.clone();
+                true
+            }
+        });
+        drop(current);
+        if changed {
+            info!("Rotated server certificate (sha256 {})", fingerprint);
+        }
+        Ok(fingerprint)
+    }
+
+    /// Poll the files of a [`CertSource::FromFile`] source every `interval`
+    /// and [`Self::reload`] when either modification time changes.
+    ///
+    /// The task stops once the reloader is dropped. Fails for sources that
+    /// are not file-backed.
+    pub fn watch_files(self: &Arc<Self>, interval: Duration) -> Result<JoinHandle<()>> {
+        let CertSource::FromFile {
+            cert_path,
+            key_path,
+        } = &self.source
+        else {
+            anyhow::bail!("CertReloader::watch_files requires a CertSource::FromFile source");
+        };
+        let (cert_path, key_path) = (cert_path.clone(), key_path.clone());
+        let reloader: Weak<Self> = Arc::downgrade(self);
+        let mut seen = (modified(&cert_path), modified(&key_path));
+        Ok(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(reloader) = reloader.upgrade() else {
+                    return;
+                };
+                let now = (modified(&cert_path), modified(&key_path));
+                if now == seen {
+                    continue;
+                }
+                match reloader.reload() {
+                    Ok(_) => seen = now,
+                    // keep `seen` so the next tick retries (= e.g. key not yet written)
+                    Err(e) => warn!("Certificate reload failed, keeping current: {:#}", e),
+                }
+            }
+        }))
+    }
+}
+
+/// One resolution of a [`CertReloader`]'s source: the rustls key served over
+/// QUIC, the DER it was built from and its fingerprint, swapped together.
+struct CertSnapshot {
+    key: Arc<CertifiedKey>,
+    /// `(cert chain DER, key DER)`; `None` for [`CertSource::Provided`]
+    der: Option<(Vec<Vec<u8>>, Vec<u8>)>,
+    /// [`certificate_fingerprint`] of `key`
+    fingerprint: String,
+}
+
+impl CertSnapshot {
+    fn load(source: &CertSource) -> Result<Self> {
+        if let CertSource::Provided { certified_key } = source {
+            return Ok(Self {
+                fingerprint: certificate_fingerprint(certified_key)?,
+                key: Arc::clone(certified_key),
+                der: None,
+            });
+        }
+        // resolve once and build the served key from that DER (= a second
+        // resolution could generate or read a different certificate)
+        let (chain, key_der) = source.resolve_der()?;
+        let certs = chain.iter().cloned().map(CertificateDer::from).collect();
+        let private_key = PrivateKeyDer::try_from(key_der.clone())
+            .map_err(|e| anyhow::anyhow!("private key parse: {}", e))?;
+        let signing_key = any_supported_type(&private_key)
+            .map_err(|e| anyhow::anyhow!("rustls signing_key build: {}", e))?;
+        let key = Arc::new(CertifiedKey::new(certs, signing_key));
+        Ok(Self {
+            fingerprint: certificate_fingerprint(&key)?,
+            key,
+            der: Some((chain, key_der)),
+        })
+    }
+}
+
+impl ResolvesServerCert for CertReloader {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current())
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// SHA-256 (lowercase hex) of the leaf certificate of `key`.
+pub fn certificate_fingerprint(key: &CertifiedKey) -> Result<String> {
+    let leaf = key
+        .cert
+        .first()
+        .context("certified key has an empty certificate chain")?;
+    let digest = ring::digest::digest(&ring::digest::SHA256, leaf.as_ref());
+    Ok(digest.as_ref().iter().map(|b| format!("{b:02x}")).collect())
+}
+
 /// Generate a self-signed cert + key pair and wrap as [`CertifiedKey`].
 ///
 /// Also returns the raw cert DER for callers that need to derive a trust
@@ -247,3 +482,98 @@ fn load_from_files(
 
     Ok(Arc::new(CertifiedKey::new(certs, signing_key)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a fresh self-signed cert + key for `localhost` to the given paths.
+    fn write_cert(cert_path: &Path, key_path: &Path) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        std::fs::write(cert_path, cert.cert.pem()).unwrap();
+        std::fs::write(key_path, cert.signing_key.serialize_pem()).unwrap();
+    }
+
+    #[test]
+    fn reload_swaps_certificate_and_notifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = (dir.path().join("tls.crt"), dir.path().join("tls.key"));
+        write_cert(&cert_path, &key_path);
+
+        let reloader = CertReloader::new(CertSource::FromFile {
+            cert_path: cert_path.clone(),
+            key_path: key_path.clone(),
+        })
+        .unwrap();
+        let first = reloader.fingerprint();
+        assert_eq!(first, certificate_fingerprint(&reloader.current()).unwrap());
+        let mut rotations = reloader.subscribe();
+
+        // unchanged files: no notification
+        assert_eq!(reloader.reload().unwrap(), first);
+        assert!(!rotations.has_changed().unwrap());
+
+        write_cert(&cert_path, &key_path);
+        let second = reloader.reload().unwrap();
+        assert_ne!(second, first);
+        assert!(rotations.has_changed().unwrap());
+        assert_eq!(*rotations.borrow_and_update(), second);
+
+        // a broken file keeps the current certificate
+        std::fs::write(&key_path, "not a key").unwrap();
+        assert!(reloader.reload().is_err());
+        assert_eq!(reloader.fingerprint(), second);
+    }
+
+    #[test]
+    fn reloader_rejects_nesting_and_non_file_watch() {
+        let reloader = CertReloader::new(CertSource::dev_localhost()).unwrap();
+        assert!(CertReloader::new(CertSource::Reloadable(reloader.clone())).is_err());
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            assert!(reloader.watch_files(Duration::from_millis(10)).is_err());
+        });
+    }
+
+    #[tokio::test]
+    async fn watch_files_picks_up_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = (dir.path().join("tls.crt"), dir.path().join("tls.key"));
+        write_cert(&cert_path, &key_path);
+        let reloader = CertReloader::new(CertSource::FromFile {
+            cert_path: cert_path.clone(),
+            key_path: key_path.clone(),
+        })
+        .unwrap();
+        let first = reloader.fingerprint();
+        let mut rotations = reloader.subscribe();
+        let watcher = reloader.watch_files(Duration::from_millis(20)).unwrap();
+
+        // make sure the mtime moves even on coarse-grained filesystems
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        write_cert(&cert_path, &key_path);
+        let modified = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&cert_path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), rotations.changed())
+            .await
+            .expect("watcher should reload")
+            .unwrap();
+        assert_ne!(reloader.fingerprint(), first);
+
+        drop(reloader);
+        tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("watcher should stop once the reloader is dropped")
+            .unwrap();
+    }
+}
@@ -6,121 +6,244 @@
 //! `UnisonChannel<C: Codec>` — 統合チャネル型（request/response + event push + raw bytes）
 //!
 //! Codec 型パラメータにより、JSON / protobuf 等のフォーマットを差し替え可能。
+//!
+//! ## Server-streaming (v1.0 で追加)
+//!
+//! `request_stream()` は 1 request に対し複数の `Response` を受け取る。 wire 上は
+//! 同じ `id` の `Response` が N 本流れ、 `__stream_end` method の `Response` で終端する
+//! ([`super::frame::STREAM_END_METHOD`])。 サーバー側は `send_stream_item()` /
+//! `end_stream()` で応答する。
+//!
+//! ## Cancellation (v1.0 で追加)
+//!
+//! `request()` の future を drop / timeout した場合、 および `request_stream()` の
+//! stream を終端前に drop した場合、 クライアントは `__cancel`
+//! ([`super::frame::CANCEL_METHOD`]) を `PacketType::Control` フレームで送る。
+//! サーバー側は受信した Request ごとに `CancellationToken` を持ち、 `__cancel` /
+//! 接続断でそれを cancel する。 handler は `cancellation_token(id)` で token を取り、
+//! 長い処理を中断できる。 cancel 後の `send_stream_item()` は
+//! [`NetworkError::Cancelled`] を返す。
+//!
+//! ## 受信キューと backpressure (v1.0 で追加)
+//!
+//! recv ループが積む Event / Raw キューの深さと満杯時の振る舞いは
+//! [`ChannelConfig`] で指定する (`with_config()`)。 デフォルトの
+//! [`OverflowPolicy::CloseWithError`] では recv ループは待たず、 溢れたキューを閉じて
+//! consumer に [`NetworkError::QueueOverflow`] を返す (= 同じ stream 上の Response 配送は
+//! 止まらない)。 従来の lossless な [`OverflowPolicy::Block`] は opt-in で、 event
+//! consumer が遅いと recv ループごと止まり Response 配送も詰まる。 受信 Request は
+//! policy に関わらず捨てず、 キューが満杯なら `Block` と同じく空きを待つ。
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::sync::{Mutex, mpsc, oneshot};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use crate::codec::{Codec, Decodable, Encodable, JsonCodec};
 
+use super::channel_config::{BoundedQueue, ChannelConfig, ChannelStats};
+use super::frame::{CANCEL_METHOD, CHANNEL_ERROR_METHOD, STREAM_END_METHOD};
 use super::quic::{TypedFrame, UnisonStream};
-use super::{MessageType, NetworkError, ProtocolMessage};
+use super::{MessageType, NetworkError, ProtocolError, ProtocolMessage};
+
+/// 応答待ち request の受け口
+enum PendingReply {
+    /// `request()` — Response / Error を 1 本だけ受ける
+    Single(oneshot::Sender<ProtocolMessage>),
+    /// `request_stream()` — 終端 (`__stream_end` / Error) まで複数受ける
+    Stream(mpsc::Sender<ProtocolMessage>),
+}
+
+type PendingMap = Arc<Mutex<HashMap<u64, PendingReply>>>;
+
+type InflightMap = Arc<Mutex<Inflight>>;
+
+/// cancel 済みの Request id を覚えておく上限 (= 応答しない handler があっても増え続けない)
+const CANCELLED_HISTORY: usize = 1024;
+
+/// 受信済みで未応答の Request（サーバー側パターン、cancel 判定用）
+///
+/// token は応答 (`send_response` / `send_error` / `end_stream`) か peer の `__cancel` で
+/// map から外す。 cancel された id は応答するまで `cancelled` に残し、 以降の
+/// `send_stream_item` を [`NetworkError::Cancelled`] にする (= 古いものから捨てる)。
+#[derive(Default)]
+struct Inflight {
+    /// request id → cancel 通知用 token
+    tokens: HashMap<u64, CancellationToken>,
+    /// cancel 済みで未応答の request id (= 最大 [`CANCELLED_HISTORY`] 件)
+    cancelled: VecDeque<u64>,
+}
+
+impl Inflight {
+    /// 受信した Request を登録する
+    fn insert(&mut self, id: u64) {
+        self.tokens.insert(id, CancellationToken::new());
+    }
+
+    /// Request を cancel して map から外す (= 未応答の Request のみ、 応答済みなら無視)
+    fn cancel(&mut self, id: u64) {
+        if let Some(token) = self.tokens.remove(&id) {
+            token.cancel();
+            self.remember_cancelled(id);
+        }
+    }
+
+    /// 全 Request を cancel して map から外す (= channel close / 再接続)
+    fn cancel_all(&mut self) {
+        let ids: Vec<u64> = self.tokens.keys().copied().collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    /// 応答した Request を外す。 cancel 済みだったら true
+    fn finish(&mut self, id: u64) -> bool {
+        let token = self.tokens.remove(&id);
+        let was_cancelled = self.forget_cancelled(id);
+        was_cancelled || token.is_some_and(|t| t.is_cancelled())
+    }
+
+    /// handler に届かなかった Request を外す (= 受信キューの overflow で捨てた)
+    fn discard(&mut self, id: u64) {
+        self.tokens.remove(&id);
+    }
+
+    fn is_cancelled(&self, id: u64) -> bool {
+        self.cancelled.contains(&id) || self.tokens.get(&id).is_some_and(|t| t.is_cancelled())
+    }
+
+    /// Request の token (= cancel 済みなら cancel された token)
+    fn token(&self, id: u64) -> Option<CancellationToken> {
+        if let Some(token) = self.tokens.get(&id) {
+            return Some(token.clone());
+        }
+        self.cancelled.contains(&id).then(|| {
+            let token = CancellationToken::new();
+            token.cancel();
+            token
+        })
+    }
+
+    fn remember_cancelled(&mut self, id: u64) {
+        if self.cancelled.len() == CANCELLED_HISTORY {
+            self.cancelled.pop_front();
+        }
+        self.cancelled.push_back(id);
+    }
 
-/// デフォルトの request タイムアウト（30秒）
-const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+    fn forget_cancelled(&mut self, id: u64) -> bool {
+        match self.cancelled.iter().position(|&c| c == id) {
+            Some(pos) => {
+                self.cancelled.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// recv ループが接続断で pending を解決するときの method (= wire には流れない)
+///
+/// 再接続を待つ channel の応答待ちは [`NetworkError::Reconnecting`] で失敗させる。
+const RECONNECTING_METHOD: &str = "__reconnecting";
+
+/// recv ループが溢れた `request_stream()` を終わらせるときの method (= wire には流れない)
+///
+/// consumer には [`NetworkError::QueueOverflow`] (`queue = "stream"`) として届く。
+const STREAM_OVERFLOW_METHOD: &str = "__stream_overflow";
 
 /// 統合チャネル型 — Request/Response、Event、Raw bytes をサポート
 ///
 /// 内部に recv ループを持ち、受信フレームを type tag で振り分ける:
 /// - Protocol frame (0x00):
-///   - `Response` → pending の oneshot に送る
-///   - `Event` / その他 → event_rx に流す
-/// - Raw frame (0x01) → raw_rx に流す
+///   - `Response` → pending の oneshot (stream request なら item キュー) に送る
+///   - `__channel_error` Error → 全 pending をそのエラーで解決し、 Event キューにも流す
+///   - `__cancel` Event → 該当 Request を cancelled にする (event_rx には流さない)
+///   - `Event` / その他 → Event キューに流す
+/// - Raw frame (0x01) → Raw キューに流す
+///
+/// Event / Raw キューの満杯時の振る舞いは [`ChannelConfig::overflow`] に従う (= 受信 Request は除く)。
 pub struct UnisonChannel<C: Codec = JsonCodec> {
-    /// QUIC ストリームへの参照（送信用）
-    stream: Arc<UnisonStream>,
-    /// 応答待ちの Request を管理（message_id → oneshot::Sender）
-    pending: Arc<Mutex<HashMap<u64, oneshot::Sender<ProtocolMessage>>>>,
-    /// Event 受信キュー
-    event_rx: Mutex<mpsc::Receiver<ProtocolMessage>>,
-    /// Raw bytes 受信キュー
-    raw_rx: Mutex<mpsc::Receiver<Vec<u8>>>,
+    /// stream と受信状態 (= 再接続で stream だけ差し替わる)
+    shared: Arc<ChannelShared>,
     /// メッセージ ID カウンター
     next_id: AtomicU64,
-    /// バックグラウンド受信タスク
-    recv_task: Mutex<Option<JoinHandle<()>>>,
     /// request() のタイムアウト
     request_timeout: Duration,
+    /// request_stream() 1 本あたりの item キュー深さ
+    stream_queue_depth: usize,
     /// Codec 型マーカー
     _codec: PhantomData<C>,
 }
 
+/// [`UnisonChannel`] の stream と受信状態（v1.0 で分離）
+///
+/// `ReconnectPolicy` 付きの `ProtocolClient` が開いた channel は `resumable` で、
+/// 接続断では閉じずに応答待ちだけを [`NetworkError::Reconnecting`] で失敗させ、
+/// 再接続後の [`Self::resume`] で新しい stream の recv ループを起動し直す。
+pub(crate) struct ChannelShared {
+    /// QUIC ストリームへの参照（送信用、 `resume()` で差し替わる）
+    stream: std::sync::RwLock<Arc<UnisonStream>>,
+    /// 応答待ちの Request を管理（message_id → 受け口）
+    pending: PendingMap,
+    /// 受信済みで未応答の Request（サーバー側パターン、cancel 判定用）
+    inflight: InflightMap,
+    /// Event 受信キュー
+    events: Arc<BoundedQueue<ProtocolMessage>>,
+    /// Raw bytes 受信キュー
+    raw: Arc<BoundedQueue<Vec<u8>>>,
+    /// バックグラウンド受信タスク
+    recv_task: Mutex<Option<JoinHandle<()>>>,
+    /// recv ループ終了 (= peer close / 接続断) または `close()` で cancel される
+    closed: CancellationToken,
+    /// 接続断で閉じずに `resume()` を待つか
+    resumable: bool,
+}
+
 impl<C: Codec> UnisonChannel<C> {
     /// UnisonStream から UnisonChannel を構築し、recv ループを起動する
+    ///
+    /// `ChannelConfig::default()` と同じ (= 各キュー 256、 `OverflowPolicy::CloseWithError`)。
     pub fn new(stream: UnisonStream) -> Self {
-        let stream = Arc::new(stream);
-        let pending: Arc<Mutex<HashMap<u64, oneshot::Sender<ProtocolMessage>>>> =
-            Arc::new(Mutex::new(HashMap::new()));
-        let (event_tx, event_rx) = mpsc::channel(256);
-        let (raw_tx, raw_rx) = mpsc::channel(256);
-
-        // recv ループ — recv_typed_frame() で type tag ベースの振り分け
-        let recv_stream = Arc::clone(&stream);
-        let recv_pending = Arc::clone(&pending);
-        let recv_task = tokio::spawn(async move {
-            loop {
-                match recv_stream.recv_typed_frame().await {
-                    Ok(TypedFrame::Protocol(msg)) => {
-                        match msg.msg_type {
-                            MessageType::Response => {
-                                let mut map = recv_pending.lock().await;
-                                if let Some(sender) = map.remove(&msg.id) {
-                                    let _ = sender.send(msg);
-                                }
-                            }
-                            MessageType::Error => {
-                                let mut map = recv_pending.lock().await;
-                                if let Some(sender) = map.remove(&msg.id) {
-                                    let _ = sender.send(msg);
-                                } else {
-                                    drop(map);
-                                    let _ = event_tx.send(msg).await;
-                                }
-                            }
-                            _ => {
-                                // Event, Request, その他 → event_rx に流す
-                                let _ = event_tx.send(msg).await;
-                            }
-                        }
-                    }
-                    Ok(TypedFrame::Raw(data)) => {
-                        let _ = raw_tx.send(data).await;
-                    }
-                    Err(_) => {
-                        // 接続断 — 全 pending を Error で解決
-                        let mut map = recv_pending.lock().await;
-                        for (_, sender) in map.drain() {
-                            if let Ok(err_msg) = ProtocolMessage::new_with_json(
-                                0,
-                                "error".to_string(),
-                                MessageType::Error,
-                                serde_json::json!({"error": "connection closed"}),
-                            ) {
-                                let _ = sender.send(err_msg);
-                            }
-                        }
-                        break;
-                    }
-                }
-            }
-        });
+        Self::with_config(stream, ChannelConfig::default())
+    }
+
+    /// キュー深さ / overflow policy を指定して構築する（v1.0 で追加）
+    pub fn with_config(stream: UnisonStream, config: ChannelConfig) -> Self {
+        Self::from_shared(ChannelShared::spawn(stream, &config, false), &config)
+    }
+
+    /// 再接続で stream を差し替えられる channel を構築する (= `ProtocolClient` 用)
+    pub(crate) fn resumable(stream: UnisonStream, config: ChannelConfig) -> Self {
+        Self::from_shared(ChannelShared::spawn(stream, &config, true), &config)
+    }
 
+    fn from_shared(shared: Arc<ChannelShared>, config: &ChannelConfig) -> Self {
         Self {
-            stream,
-            pending,
-            event_rx: Mutex::new(event_rx),
-            raw_rx: Mutex::new(raw_rx),
+            shared,
             next_id: AtomicU64::new(1),
-            recv_task: Mutex::new(Some(recv_task)),
-            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            request_timeout: config.request_timeout,
+            stream_queue_depth: config.stream_queue_depth,
             _codec: PhantomData,
         }
     }
 
+    /// stream と受信状態 (= client の再接続 registry が弱参照で持つ)
+    pub(crate) fn shared(&self) -> &Arc<ChannelShared> {
+        &self.shared
+    }
+
+    /// 現在の stream
+    fn stream(&self) -> Arc<UnisonStream> {
+        self.shared.stream()
+    }
+
     /// request タイムアウトを設定（ビルダーパターン）
     pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
         self.request_timeout = timeout;
@@ -143,46 +266,95 @@ impl<C: Codec> UnisonChannel<C> {
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
         let (tx, rx) = oneshot::channel();
 
+        // Request メッセージを Codec でエンコード
+        let payload = req.encode().map_err(NetworkError::Codec)?;
+
         // pending に登録
         {
-            let mut map = self.pending.lock().await;
-            map.insert(id, tx);
+            let mut map = self.shared.pending.lock().await;
+            map.insert(id, PendingReply::Single(tx));
         }
+        // 以降この future が drop / timeout したらサーバーへ `__cancel` を送る
+        let stream = self.stream();
+        let mut guard = CancelOnDrop::new(id, &stream, &self.shared.pending);
 
-        // Request メッセージを Codec でエンコードしてフレームとして送信
-        let payload = req.encode().map_err(NetworkError::Codec)?;
         let msg =
             ProtocolMessage::new_encoded(id, method.to_string(), MessageType::Request, payload);
-        self.stream.send_frame(&msg).await?;
+        if let Err(e) = stream.send_frame(&msg).await {
+            guard.disarm();
+            self.shared.pending.lock().await.remove(&id);
+            return Err(e);
+        }
 
-        // Response を待つ（タイムアウト付き）
+        // Response を待つ（タイムアウト付き、timeout 時は guard が cancel を送る）
         let response = match tokio::time::timeout(self.request_timeout, rx).await {
-            Ok(Ok(msg)) => msg,
+            Ok(Ok(msg)) => {
+                guard.disarm();
+                msg
+            }
             Ok(Err(_)) => {
-                self.pending.lock().await.remove(&id);
+                guard.disarm();
+                self.shared.pending.lock().await.remove(&id);
                 return Err(NetworkError::Protocol(
                     "Request cancelled: channel closed".to_string(),
                 ));
             }
-            Err(_) => {
-                self.pending.lock().await.remove(&id);
-                return Err(NetworkError::Timeout);
-            }
+            Err(_) => return Err(NetworkError::Timeout),
         };
 
         match response.msg_type {
-            MessageType::Error => {
-                // エラーレスポンスは常に JSON (プロトコル内部)
-                let payload = response.payload_as_value()?;
-                Err(NetworkError::Protocol(format!(
-                    "Request error: {}",
-                    payload
-                )))
-            }
+            MessageType::Error => Err(error_from_reply(&response)),
             _ => response.decode_payload::<Resp, C>(),
         }
     }
 
+    /// 型付き Server-streaming Request（v1.0 で追加）
+    ///
+    /// 1 つの Request に対してサーバーが `send_stream_item()` で返す `Response` を
+    /// 順に yield し、 `end_stream()` を受けると `None` で終わる。 Error 応答は
+    /// `Err` を 1 つ yield して終わる。
+    ///
+    /// 終端前に返り値の stream を drop すると、 サーバーへ `__cancel` を送る。
+    /// `request()` と異なり全体タイムアウトは掛けない (= 長寿命の tail 用途)。
+    ///
+    /// item キュー ([`ChannelConfig::stream_queue_depth`]) が溢れると、 その stream だけ
+    /// [`NetworkError::QueueOverflow`] を 1 つ yield して終わり、 サーバーへ `__cancel` を
+    /// 送る (= 遅い consumer が同じ channel の他の応答を止めない)。
+    pub async fn request_stream<Req, Resp>(
+        &self,
+        method: &str,
+        req: &Req,
+    ) -> Result<ResponseStream<Resp, C>, NetworkError>
+    where
+        Req: Encodable<C>,
+        Resp: Decodable<C>,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        // +1 は終端 (= end marker / Error / overflow) 用に常に空けておく枠
+        let (tx, rx) = mpsc::channel(self.stream_queue_depth + 1);
+
+        let payload = req.encode().map_err(NetworkError::Codec)?;
+        self.shared
+            .pending
+            .lock()
+            .await
+            .insert(id, PendingReply::Stream(tx));
+
+        let msg =
+            ProtocolMessage::new_encoded(id, method.to_string(), MessageType::Request, payload);
+        let stream = self.stream();
+        if let Err(e) = stream.send_frame(&msg).await {
+            self.shared.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        Ok(ResponseStream {
+            rx,
+            cancel: CancelOnDrop::new(id, &stream, &self.shared.pending),
+            _marker: PhantomData,
+        })
+    }
+
     /// 型付き Event 送信（応答不要）
     pub async fn send_event<T: Encodable<C>>(
         &self,
@@ -191,7 +363,7 @@ impl<C: Codec> UnisonChannel<C> {
     ) -> Result<(), NetworkError> {
         let bytes = payload.encode().map_err(NetworkError::Codec)?;
         let msg = ProtocolMessage::new_encoded(0, method.to_string(), MessageType::Event, bytes);
-        self.stream.send_frame(&msg).await
+        self.stream().send_frame(&msg).await
     }
 
     /// 型付き Response 送信（サーバー側パターン）
@@ -204,6 +376,56 @@ impl<C: Codec> UnisonChannel<C> {
         method: &str,
         payload: &T,
     ) -> Result<(), NetworkError> {
+        let bytes = payload.encode().map_err(NetworkError::Codec)?;
+        self.shared.inflight.lock().await.finish(request_id);
+        let msg = ProtocolMessage::new_encoded(
+            request_id,
+            method.to_string(),
+            MessageType::Response,
+            bytes,
+        );
+        self.stream().send_frame(&msg).await
+    }
+
+    /// 構造化エラー送信（サーバー側パターン、v1.0 で追加）
+    ///
+    /// `recv()` で受け取った Request の `id` に `Error` メッセージを返す。 payload は
+    /// Codec に関わらず JSON で、 クライアント側の `request()` / `request_stream()` は
+    /// [`NetworkError::Remote`] を返す。 Server-streaming の途中でも終端として使える。
+    pub async fn send_error(
+        &self,
+        request_id: u64,
+        error: ProtocolError,
+    ) -> Result<(), NetworkError> {
+        self.shared.inflight.lock().await.finish(request_id);
+        let msg = ProtocolMessage::new_with_json(
+            request_id,
+            "error".to_string(),
+            MessageType::Error,
+            serde_json::to_value(error)?,
+        )?;
+        self.stream().send_frame(&msg).await
+    }
+
+    /// Server-streaming の item 送信（サーバー側パターン、v1.0 で追加）
+    ///
+    /// `recv()` で受け取った Request の `id` に対して `Response` を 1 本送る。
+    /// 何度でも呼べ、 最後に `end_stream()` で閉じる。
+    ///
+    /// クライアントが stream を drop して `__cancel` が届いていた場合は送信せず
+    /// [`NetworkError::Cancelled`] を返す (= `end_stream()` までは何度呼んでも同じ)。
+    /// handler はそこで処理を打ち切ればよい。
+    pub async fn send_stream_item<T: Encodable<C>>(
+        &self,
+        request_id: u64,
+        method: &str,
+        payload: &T,
+    ) -> Result<(), NetworkError> {
+        // cancel 済みの id は `end_stream` / `send_response` / `send_error` まで残す
+        // (= 以降の呼び出しも全て Cancelled になる)
+        if self.is_cancelled(request_id).await {
+            return Err(NetworkError::Cancelled { request_id });
+        }
         let bytes = payload.encode().map_err(NetworkError::Codec)?;
         let msg = ProtocolMessage::new_encoded(
             request_id,
@@ -211,41 +433,464 @@ impl<C: Codec> UnisonChannel<C> {
             MessageType::Response,
             bytes,
         );
-        self.stream.send_frame(&msg).await
+        self.stream().send_frame(&msg).await
+    }
+
+    /// Server-streaming の終端送信（サーバー側パターン、v1.0 で追加）
+    ///
+    /// `__stream_end` の `Response` を送り、 クライアント側の stream を `None` で閉じる。
+    /// 既に cancel 済みの request に対しては何も送らない。
+    pub async fn end_stream(&self, request_id: u64) -> Result<(), NetworkError> {
+        if self.shared.inflight.lock().await.finish(request_id) {
+            return Ok(());
+        }
+        let msg = ProtocolMessage::new_with_json(
+            request_id,
+            STREAM_END_METHOD.to_string(),
+            MessageType::Response,
+            serde_json::json!({}),
+        )?;
+        self.stream().send_frame(&msg).await
+    }
+
+    /// 受信した Request が peer にキャンセルされたか（サーバー側パターン）
+    ///
+    /// 応答済み / 未知の id は `false`。
+    pub async fn is_cancelled(&self, request_id: u64) -> bool {
+        self.shared.inflight.lock().await.is_cancelled(request_id)
+    }
+
+    /// 受信した Request の `CancellationToken`（サーバー側パターン、v1.0 で追加）
+    ///
+    /// peer が `__cancel` を送った / 接続が切れた / チャネルを閉じたときに cancel
+    /// される。 handler は `token.cancelled()` と処理を `select!` して早期に中断できる。
+    /// 応答済み / 未知の id は `None`。
+    pub async fn cancellation_token(&self, request_id: u64) -> Option<CancellationToken> {
+        self.shared.inflight.lock().await.token(request_id)
     }
 
     /// Raw bytes 送信（buffa/zstd をバイパス、最小オーバーヘッド）
     ///
     /// オーディオストリーミング等のバイナリデータに使用。
     pub async fn send_raw(&self, data: &[u8]) -> Result<(), NetworkError> {
-        self.stream.send_raw_frame(data).await
+        self.stream().send_raw_frame(data).await
     }
 
     /// Raw bytes 受信
     ///
-    /// recv ループが type tag 0x01 のフレームを受信すると raw_rx に流す。
+    /// recv ループが type tag 0x01 のフレームを受信すると Raw キューに流す。
+    /// `OverflowPolicy::CloseWithError` で溢れた場合は溜まった分を返した後に
+    /// [`NetworkError::QueueOverflow`] を返す。
     pub async fn recv_raw(&self) -> Result<Vec<u8>, NetworkError> {
-        let mut rx = self.raw_rx.lock().await;
-        rx.recv()
-            .await
+        self.shared
+            .raw
+            .recv()
+            .await?
             .ok_or_else(|| NetworkError::Protocol("Raw channel closed".to_string()))
     }
 
     /// Event 受信（サーバーからのプッシュ、または非 Response メッセージ）
+    ///
+    /// `OverflowPolicy::CloseWithError` で溢れた場合は溜まった分を返した後に
+    /// [`NetworkError::QueueOverflow`] を返す。
     pub async fn recv(&self) -> Result<ProtocolMessage, NetworkError> {
-        let mut rx = self.event_rx.lock().await;
-        rx.recv()
-            .await
+        self.shared
+            .events
+            .recv()
+            .await?
             .ok_or_else(|| NetworkError::Protocol("Channel closed".to_string()))
     }
 
+    /// 受信キューの統計（v1.0 で追加）
+    ///
+    /// 現在の滞留数と、 満杯に遭遇した回数 / 捨てた要素数を返す。
+    pub fn stats(&self) -> ChannelStats {
+        ChannelStats {
+            events: self.shared.events.stats(),
+            raw: self.shared.raw.stats(),
+        }
+    }
+
+    /// チャネルが閉じていれば true（v1.0 で追加）
+    ///
+    /// peer の close / 接続断で recv ループが終わった場合と、 `close()` を呼んだ場合。
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.is_cancelled()
+    }
+
+    /// チャネルが閉じるまで待つ（v1.0 で追加）
+    pub async fn closed(&self) {
+        self.shared.closed.cancelled().await
+    }
+
+    /// 構築済みの `ProtocolMessage` をそのまま送る (= `ChannelGroup` の fan-out 用)
+    pub(crate) async fn send_message(&self, msg: &ProtocolMessage) -> Result<(), NetworkError> {
+        self.stream().send_frame(msg).await
+    }
+
     /// チャネルを閉じる
     pub async fn close(&self) -> Result<(), NetworkError> {
+        self.shared.close().await
+    }
+}
+
+impl ChannelShared {
+    /// 受信状態を作り、 `stream` の recv ループを起動する
+    fn spawn(stream: UnisonStream, config: &ChannelConfig, resumable: bool) -> Arc<Self> {
+        let stream = Arc::new(stream);
+        let shared = Arc::new(Self {
+            stream: std::sync::RwLock::new(Arc::clone(&stream)),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            inflight: Arc::new(Mutex::new(Inflight::default())),
+            events: Arc::new(
+                BoundedQueue::new("event", config.event_queue_depth, config.overflow)
+                    .with_lossless(|msg: &ProtocolMessage| msg.msg_type == MessageType::Request),
+            ),
+            raw: Arc::new(BoundedQueue::new(
+                "raw",
+                config.raw_queue_depth,
+                config.overflow,
+            )),
+            recv_task: Mutex::new(None),
+            closed: CancellationToken::new(),
+            resumable,
+        });
+        let task = shared.spawn_recv_loop(stream);
+        // 構築直後で他に lock を取る者はいない
+        *shared.recv_task.try_lock().expect("fresh channel lock") = Some(task);
+        shared
+    }
+
+    /// 現在の stream
+    fn stream(&self) -> Arc<UnisonStream> {
+        Arc::clone(&self.stream.read().expect("stream lock poisoned"))
+    }
+
+    /// `close()` 済み、 または recv ループが接続断以外の理由で終わったか
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.is_cancelled()
+    }
+
+    /// `closed` の clone (= channel の終わりを別 task で待つ用)
+    pub(crate) fn closed_token(&self) -> CancellationToken {
+        self.closed.clone()
+    }
+
+    /// recv ループ — recv_typed_frame() で type tag ベースの振り分け
+    fn spawn_recv_loop(&self, recv_stream: Arc<UnisonStream>) -> JoinHandle<()> {
+        let recv_pending = Arc::clone(&self.pending);
+        let recv_inflight = Arc::clone(&self.inflight);
+        let event_tx = Arc::clone(&self.events);
+        let raw_tx = Arc::clone(&self.raw);
+        let recv_closed = self.closed.clone();
+        let resumable = self.resumable;
+        tokio::spawn(async move {
+            loop {
+                match recv_stream.recv_typed_frame().await {
+                    Ok(TypedFrame::Protocol(msg)) => match msg.msg_type {
+                        MessageType::Response | MessageType::Error => {
+                            let is_error = msg.msg_type == MessageType::Error;
+                            let mut map = recv_pending.lock().await;
+                            match map.remove(&msg.id) {
+                                Some(PendingReply::Single(sender)) => {
+                                    let _ = sender.send(msg);
+                                }
+                                Some(PendingReply::Stream(sender)) => {
+                                    // 終端 (= end marker / Error) は予約枠に必ず入る
+                                    if is_error || msg.method == STREAM_END_METHOD {
+                                        let _ = sender.try_send(msg);
+                                    } else if sender.capacity() > 1 {
+                                        map.insert(msg.id, PendingReply::Stream(sender.clone()));
+                                        let _ = sender.try_send(msg);
+                                    } else if !sender.is_closed() {
+                                        // consumer が遅い — recv ループは待たず、 この stream
+                                        // だけ終わらせてサーバーに止めてもらう
+                                        let id = msg.id;
+                                        if let Ok(overflow) = ProtocolMessage::new_with_json(
+                                            id,
+                                            STREAM_OVERFLOW_METHOD.to_string(),
+                                            MessageType::Error,
+                                            serde_json::json!({ "capacity": sender.max_capacity() - 1 }),
+                                        ) {
+                                            let _ = sender.try_send(overflow);
+                                        }
+                                        let stream = Arc::clone(&recv_stream);
+                                        tokio::spawn(async move { send_cancel(&stream, id).await });
+                                    }
+                                }
+                                None if is_error && msg.method == CHANNEL_ERROR_METHOD => {
+                                    // 相手の handler が panic した — 応答待ちは全てこのエラー
+                                    for (id, reply) in map.drain() {
+                                        let mut err_msg = msg.clone();
+                                        err_msg.id = id;
+                                        reply.resolve(err_msg);
+                                    }
+                                    drop(map);
+                                    event_tx.push(msg).await;
+                                }
+                                None => {
+                                    drop(map);
+                                    if is_error {
+                                        event_tx.push(msg).await;
+                                    }
+                                }
+                            }
+                        }
+                        MessageType::Event if msg.method == CANCEL_METHOD => {
+                            recv_inflight.lock().await.cancel(msg.id);
+                        }
+                        MessageType::Request => {
+                            recv_inflight.lock().await.insert(msg.id);
+                            if let Some(dropped) = event_tx.push(msg).await {
+                                discard_request(&recv_inflight, &dropped).await;
+                            }
+                        }
+                        MessageType::Event => {
+                            if let Some(dropped) = event_tx.push(msg).await {
+                                discard_request(&recv_inflight, &dropped).await;
+                            }
+                        }
+                    },
+                    Ok(TypedFrame::Raw(data)) => {
+                        raw_tx.push(data).await;
+                    }
+                    Err(_) => {
+                        // 接続断 — 処理中の Request は全て cancel
+                        recv_inflight.lock().await.cancel_all();
+                        // v1.0: 再接続を待つ channel は応答待ちだけを失敗させて開いたままにする
+                        if resumable && recv_stream.is_connection_closed() {
+                            fail_pending(&recv_pending, RECONNECTING_METHOD, "reconnecting").await;
+                            break;
+                        }
+                        // 全 pending を Error で解決
+                        fail_pending(&recv_pending, "error", "connection closed").await;
+                        // consumer 側の recv() / recv_raw() を終端させる
+                        event_tx.close();
+                        raw_tx.close();
+                        recv_closed.cancel();
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    /// 再接続後の新しい stream に差し替え、 recv ループを起動し直す
+    ///
+    /// 旧 stream の応答待ち / 処理中の Request はここで全て打ち切る
+    /// (= request id は新しい stream に引き継がない)。 Event / Raw キューはそのまま。
+    pub(crate) async fn resume(&self, stream: UnisonStream) {
+        let mut task = self.recv_task.lock().await;
+        if let Some(old) = task.take() {
+            old.abort();
+            let _ = old.await;
+        }
+        fail_pending(&self.pending, RECONNECTING_METHOD, "reconnecting").await;
+        self.inflight.lock().await.cancel_all();
+        let stream = Arc::new(stream);
+        *self.stream.write().expect("stream lock poisoned") = Arc::clone(&stream);
+        *task = Some(self.spawn_recv_loop(stream));
+    }
+
+    /// チャネルを閉じる
+    pub(crate) async fn close(&self) -> Result<(), NetworkError> {
         // recv タスクを中止
         if let Some(task) = self.recv_task.lock().await.take() {
             task.abort();
         }
+        self.closed.cancel();
+        self.events.close();
+        self.raw.close();
+        // 応答待ちを解放 (= request / request_stream 側に channel closed を伝える)
+        self.pending.lock().await.clear();
+        // 処理中の Request を cancel
+        self.inflight.lock().await.cancel_all();
         // ストリームを閉じる
-        self.stream.close_stream().await
+        self.stream().close_stream().await
+    }
+}
+
+impl PendingReply {
+    /// 応答待ちを `msg` で解決する (= stream request は終端として 1 本だけ積む)
+    fn resolve(self, msg: ProtocolMessage) {
+        match self {
+            PendingReply::Single(sender) => {
+                let _ = sender.send(msg);
+            }
+            PendingReply::Stream(sender) => {
+                let _ = sender.try_send(msg);
+            }
+        }
+    }
+}
+
+/// 全 pending を `{"error": error}` の Error 応答で解決する
+async fn fail_pending(pending: &PendingMap, method: &str, error: &str) {
+    let mut map = pending.lock().await;
+    for (id, reply) in map.drain() {
+        if let Ok(err_msg) = ProtocolMessage::new_with_json(
+            id,
+            method.to_string(),
+            MessageType::Error,
+            serde_json::json!({ "error": error }),
+        ) {
+            reply.resolve(err_msg);
+        }
+    }
+}
+
+/// Error 応答を NetworkError に変換する
+///
+/// `send_error` 由来の `{code, message, details}` は [`NetworkError::Remote`]、
+/// それ以外 (= 接続断など内部生成の `{"error": ...}`) は従来どおり Protocol error。
+/// 再接続待ちの接続断は [`NetworkError::Reconnecting`]、 溢れた `request_stream()` は
+/// [`NetworkError::QueueOverflow`]。
+/// `ChannelRouter` の未登録 method 応答 ([`ProtocolError::METHOD_NOT_FOUND`] +
+/// `details.method`) は [`NetworkError::HandlerNotFound`] に復元する。
+fn error_from_reply(msg: &ProtocolMessage) -> NetworkError {
+    if msg.method == RECONNECTING_METHOD {
+        return NetworkError::Reconnecting;
+    }
+    // エラーレスポンスは常に JSON (プロトコル内部)
+    let payload = match msg.payload_as_value() {
+        Ok(payload) => payload,
+        Err(e) => return e,
+    };
+    if msg.method == STREAM_OVERFLOW_METHOD {
+        return NetworkError::QueueOverflow {
+            queue: "stream".to_string(),
+            capacity: payload["capacity"].as_u64().unwrap_or(0) as usize,
+        };
+    }
+    match serde_json::from_value::<ProtocolError>(payload.clone()) {
+        Ok(err) if err.code == ProtocolError::METHOD_NOT_FOUND => {
+            match err
+                .details
+                .as_ref()
+                .and_then(|d| d.get("method"))
+                .and_then(|m| m.as_str())
+            {
+                Some(method) => NetworkError::HandlerNotFound {
+                    method: method.to_string(),
+                },
+                None => NetworkError::Remote(err),
+            }
+        }
+        Ok(err) => NetworkError::Remote(err),
+        Err(_) => NetworkError::Protocol(format!("Request error: {}", payload)),
+    }
+}
+
+/// `request_stream()` が返す応答 stream（v1.0 で追加）
+///
+/// `futures_util::Stream<Item = Result<Resp, NetworkError>>` を実装する。
+/// 終端前に drop するとサーバーへ `__cancel` を送る (= best-effort、 tokio runtime 上のみ)。
+pub struct ResponseStream<Resp, C: Codec = JsonCodec> {
+    rx: mpsc::Receiver<ProtocolMessage>,
+    /// 終端を受けるまで armed (= drop で `__cancel`)
+    cancel: CancelOnDrop,
+    _marker: PhantomData<fn() -> (Resp, C)>,
+}
+
+impl<Resp, C: Codec> ResponseStream<Resp, C> {
+    /// 対応する Request の message id
+    pub fn request_id(&self) -> u64 {
+        self.cancel.id
+    }
+}
+
+impl<Resp: Decodable<C>, C: Codec> futures_util::Stream for ResponseStream<Resp, C> {
+    type Item = Result<Resp, NetworkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if !self.cancel.armed {
+            return Poll::Ready(None);
+        }
+        match self.rx.poll_recv(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(None) => {
+                self.cancel.disarm();
+                Poll::Ready(Some(Err(NetworkError::Protocol(
+                    "Request cancelled: channel closed".to_string(),
+                ))))
+            }
+            Poll::Ready(Some(msg)) => {
+                if msg.msg_type == MessageType::Error {
+                    self.cancel.disarm();
+                    Poll::Ready(Some(Err(error_from_reply(&msg))))
+                } else if msg.method == STREAM_END_METHOD {
+                    self.cancel.disarm();
+                    Poll::Ready(None)
+                } else {
+                    Poll::Ready(Some(msg.decode_payload::<Resp, C>()))
+                }
+            }
+        }
+    }
+}
+
+/// 応答待ちの request を放棄したときに pending 解除 + `__cancel` 送信を行う guard
+///
+/// `request()` の future と `ResponseStream` が保持し、 応答 (終端) を受けたら
+/// `disarm()` する。 armed のまま drop されると best-effort で cancel を送る
+/// (= Drop は同期なので tokio runtime 上で spawn、 runtime 外では何もしない)。
+struct CancelOnDrop {
+    id: u64,
+    stream: Arc<UnisonStream>,
+    pending: PendingMap,
+    armed: bool,
+}
+
+impl CancelOnDrop {
+    fn new(id: u64, stream: &Arc<UnisonStream>, pending: &PendingMap) -> Self {
+        Self {
+            id,
+            stream: Arc::clone(stream),
+            pending: Arc::clone(pending),
+            armed: true,
+        }
+    }
+
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let id = self.id;
+        let stream = Arc::clone(&self.stream);
+        let pending = Arc::clone(&self.pending);
+        handle.spawn(async move {
+            pending.lock().await.remove(&id);
+            send_cancel(&stream, id).await;
+        });
+    }
+}
+
+/// 受信キューの overflow で捨てた要素が Request なら inflight から外す
+///
+/// handler に届かないので応答されず、 token が残り続けるのを防ぐ。
+async fn discard_request(inflight: &InflightMap, dropped: &ProtocolMessage) {
+    if dropped.msg_type == MessageType::Request {
+        inflight.lock().await.discard(dropped.id);
+    }
+}
+
+/// request `id` の `__cancel` を制御フレームで送る (= best-effort)
+async fn send_cancel(stream: &UnisonStream, id: u64) {
+    if let Ok(msg) = ProtocolMessage::new_with_json(
+        id,
+        CANCEL_METHOD.to_string(),
+        MessageType::Event,
+        serde_json::json!({}),
+    ) {
+        let _ = stream.send_control_frame(&msg).await;
     }
 }
@@ -0,0 +1,434 @@
+//! `UnisonChannel` の受信キュー設定と overflow 制御 (v1.0 で追加)
+//!
+//! `UnisonChannel` の recv ループは受信フレームを Event キュー / Raw キューに積む。
+//! 旧実装は `mpsc::channel(256)` 固定で、 キューが満杯だと recv ループ自体が
+//! `await` で止まり、 同じ stream 上の Response 配送まで詰まっていた
+//! (= 遅い event consumer による head-of-line blocking)。
+//!
+//! [`ChannelConfig`] でキュー深さと [`OverflowPolicy`] を選べる。 デフォルトの
+//! `CloseWithError` を含め `Block` 以外の policy では recv ループは待たないため、
+//! event が溜まっても request/response は流れ続ける。 overflow 回数は [`ChannelStats`]
+//! で観測できる。
+//!
+//! 受信した Request だけは policy に関わらず捨てない: Event キューが Request で
+//! 埋まっている間は recv ループが空きを待ち、 QUIC の flow control で送信側に
+//! backpressure が掛かる (= handler より速く Request を送られても channel は閉じない)。
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use super::NetworkError;
+
+/// デフォルトのキュー深さ (= 旧実装の `mpsc::channel(256)` と同じ)
+pub const DEFAULT_QUEUE_DEPTH: usize = 256;
+
+/// キュー満杯時の振る舞い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// 空きが出るまで recv ループを止める (= 旧挙動、 lossless)。
+    ///
+    /// QUIC の flow control で送信側にも backpressure が掛かるが、 同じ stream 上の
+    /// Response 配送も止まる点に注意 (= opt-in)。
+    Block,
+    /// 最も古い要素を捨てて新しい要素を積む (= 最新状態だけ欲しい event 向け)
+    DropOldest,
+    /// 新しく届いた要素を捨てる
+    DropNewest,
+    /// キューを閉じ、 consumer に [`NetworkError::QueueOverflow`] を返す (デフォルト)。
+    ///
+    /// 以降そのキュー宛ての要素は捨てられる (= request/response は影響を受けない)。
+    #[default]
+    CloseWithError,
+}
+
+/// `UnisonChannel` の構築時設定
+///
+/// デフォルトの overflow は [`OverflowPolicy::CloseWithError`]。 event consumer が
+/// 遅くても Response 配送は止まらない代わりに、 Event / Raw が溢れた時点でそのキューが
+/// 閉じ、 `recv()` / `recv_raw()` は溜まった分の後に [`NetworkError::QueueOverflow`] を
+/// 返す。 受信 Request は overflow の対象外で、 キューが満杯なら空きが出るまで recv
+/// ループを止める (= Request の burst でキューは閉じない)。 Event も全件を受け取る必要が
+/// あり、 その間 Response が止まってもよい用途では `Block`、 最新の event だけ欲しい
+/// 用途では `DropOldest` を選ぶ。
+///
+/// ```rust
+/// use unison::network::{ChannelConfig, OverflowPolicy};
+///
+/// let config = ChannelConfig::default()
+///     .with_event_queue_depth(1024)
+///     .with_overflow(OverflowPolicy::DropOldest);
+/// assert_eq!(config.event_queue_depth, 1024);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChannelConfig {
+    /// Event キュー (= `recv()` が読む、 受信 Request / Event) の深さ
+    pub event_queue_depth: usize,
+    /// Raw bytes キュー (= `recv_raw()` が読む) の深さ
+    pub raw_queue_depth: usize,
+    /// `request_stream()` 1 本あたりの item キューの深さ
+    ///
+    /// `overflow` に関わらず、 溢れるとその stream だけ `QueueOverflow` で終わる。
+    pub stream_queue_depth: usize,
+    /// Event / Raw キュー満杯時の振る舞い (= 受信 Request には適用しない)
+    pub overflow: OverflowPolicy,
+    /// `request()` のタイムアウト
+    pub request_timeout: Duration,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            event_queue_depth: DEFAULT_QUEUE_DEPTH,
+            raw_queue_depth: DEFAULT_QUEUE_DEPTH,
+            stream_queue_depth: DEFAULT_QUEUE_DEPTH,
+            overflow: OverflowPolicy::CloseWithError,
+            request_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ChannelConfig {
+    /// Event キューの深さを設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_event_queue_depth(mut self, depth: usize) -> Self {
+        self.event_queue_depth = depth.max(1);
+        self
+    }
+
+    /// Raw キューの深さを設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_raw_queue_depth(mut self, depth: usize) -> Self {
+        self.raw_queue_depth = depth.max(1);
+        self
+    }
+
+    /// `request_stream()` の item キューの深さを設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_stream_queue_depth(mut self, depth: usize) -> Self {
+        self.stream_queue_depth = depth.max(1);
+        self
+    }
+
+    /// overflow policy を設定（ビルダーパターン）
+    pub fn with_overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// `request()` のタイムアウトを設定（ビルダーパターン）
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+}
+
+/// 1 キュー分の統計
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// 現在キューに溜まっている要素数
+    pub len: usize,
+    /// キュー満杯に遭遇した回数 (= `Block` では待った回数)
+    pub overflows: u64,
+    /// overflow で捨てた要素数 (= `Block` では常に 0)
+    pub dropped: u64,
+}
+
+/// `UnisonChannel::stats()` の返り値
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Event キュー
+    pub events: QueueStats,
+    /// Raw bytes キュー
+    pub raw: QueueStats,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    /// producer (= recv ループ) 終了
+    closed: bool,
+    /// `CloseWithError` で閉じた
+    overflowed: bool,
+}
+
+/// policy 付きの bounded キュー (single producer)
+///
+/// producer は recv ループ、 consumer は `recv()` / `recv_raw()`。 consumer は
+/// `consumer` の lock で直列化し、 `item_ready` を待つのは常に 1 つだけにする
+/// (= 複数の待機者が 1 つの permit を取り合って通知を取りこぼさない)。
+/// `Notify::notify_one` は待機者がいなくても permit を 1 つ保持するため、
+/// lock 解放〜 `notified().await` 間の通知も取りこぼさない。
+pub(crate) struct BoundedQueue<T> {
+    name: &'static str,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// policy に関わらず捨てない要素 (= 空きが出るまで待って積む)
+    lossless: Option<fn(&T) -> bool>,
+    state: Mutex<QueueState<T>>,
+    consumer: tokio::sync::Mutex<()>,
+    item_ready: Notify,
+    space_ready: Notify,
+    overflows: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl<T> BoundedQueue<T> {
+    pub(crate) fn new(name: &'static str, capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            name,
+            capacity: capacity.max(1),
+            policy,
+            lossless: None,
+            state: Mutex::new(QueueState {
+                items: VecDeque::new(),
+                closed: false,
+                overflowed: false,
+            }),
+            consumer: tokio::sync::Mutex::new(()),
+            item_ready: Notify::new(),
+            space_ready: Notify::new(),
+            overflows: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// `lossless` を満たす要素は policy に関わらず `Block` で積む（ビルダーパターン）
+    ///
+    /// `DropOldest` でも追い出さない。 キューが `CloseWithError` で閉じた後は捨てる。
+    pub(crate) fn with_lossless(mut self, lossless: fn(&T) -> bool) -> Self {
+        self.lossless = Some(lossless);
+        self
+    }
+
+    fn is_lossless(&self, item: &T) -> bool {
+        self.lossless.is_some_and(|lossless| lossless(item))
+    }
+
+    /// 要素を積む。 `Block` (と lossless な要素) では空きが出るまで待つ。
+    ///
+    /// overflow で捨てた要素 (= 積めなかった `item`、 `DropOldest` なら追い出した要素)
+    /// を返す。
+    pub(crate) async fn push(&self, item: T) -> Option<T> {
+        let policy = if self.is_lossless(&item) {
+            OverflowPolicy::Block
+        } else {
+            self.policy
+        };
+        let mut item = Some(item);
+        let mut counted = false;
+        loop {
+            {
+                let mut state = self.state.lock().expect("queue lock poisoned");
+                if state.closed || state.overflowed {
+                    if state.overflowed {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    return item;
+                }
+                if state.items.len() < self.capacity {
+                    state.items.extend(item.take());
+                    drop(state);
+                    self.item_ready.notify_one();
+                    return None;
+                }
+                if !counted {
+                    self.overflows.fetch_add(1, Ordering::Relaxed);
+                    counted = true;
+                }
+                match policy {
+                    OverflowPolicy::Block => {}
+                    OverflowPolicy::DropOldest => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        // lossless な要素しか無ければ新しい要素を捨てる
+                        let Some(pos) = state.items.iter().position(|q| !self.is_lossless(q))
+                        else {
+                            return item;
+                        };
+                        let oldest = state.items.remove(pos);
+                        state.items.extend(item.take());
+                        drop(state);
+                        self.item_ready.notify_one();
+                        return oldest;
+                    }
+                    OverflowPolicy::DropNewest => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        return item;
+                    }
+                    OverflowPolicy::CloseWithError => {
+                        state.overflowed = true;
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        drop(state);
+                        self.item_ready.notify_one();
+                        return item;
+                    }
+                }
+            }
+            self.space_ready.notified().await;
+        }
+    }
+
+    /// 要素を 1 つ取り出す。 producer 終了後に空なら `Ok(None)`、
+    /// `CloseWithError` で閉じていれば溜まった要素を返し切った後に Err。
+    pub(crate) async fn recv(&self) -> Result<Option<T>, NetworkError> {
+        let _consumer = self.consumer.lock().await;
+        loop {
+            {
+                let mut state = self.state.lock().expect("queue lock poisoned");
+                if let Some(item) = state.items.pop_front() {
+                    drop(state);
+                    self.space_ready.notify_one();
+                    return Ok(Some(item));
+                }
+                if state.overflowed {
+                    return Err(NetworkError::QueueOverflow {
+                        queue: self.name.to_string(),
+                        capacity: self.capacity,
+                    });
+                }
+                if state.closed {
+                    return Ok(None);
+                }
+            }
+            self.item_ready.notified().await;
+        }
+    }
+
+    /// producer 終了を通知する (= 溜まった要素は引き続き読める)
+    pub(crate) fn close(&self) {
+        self.state.lock().expect("queue lock poisoned").closed = true;
+        self.item_ready.notify_one();
+        self.space_ready.notify_one();
+    }
+
+    /// `CloseWithError` で閉じていれば true
+    pub(crate) fn is_overflowed(&self) -> bool {
+        self.state.lock().expect("queue lock poisoned").overflowed
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn stats(&self) -> QueueStats {
+        QueueStats {
+            len: self.state.lock().expect("queue lock poisoned").items.len(),
+            overflows: self.overflows.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn fill(queue: &BoundedQueue<u32>, items: impl IntoIterator<Item = u32>) {
+        for i in items {
+            queue.push(i).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_latest_items() {
+        let queue = BoundedQueue::new("event", 2, OverflowPolicy::DropOldest);
+        fill(&queue, [1, 2, 3, 4]).await;
+        assert_eq!(queue.recv().await.unwrap(), Some(3));
+        assert_eq!(queue.recv().await.unwrap(), Some(4));
+        let stats = queue.stats();
+        assert_eq!((stats.overflows, stats.dropped, stats.len), (2, 2, 0));
+    }
+
+    #[tokio::test]
+    async fn drop_newest_keeps_earliest_items() {
+        let queue = BoundedQueue::new("event", 2, OverflowPolicy::DropNewest);
+        fill(&queue, [1, 2, 3]).await;
+        assert_eq!(queue.recv().await.unwrap(), Some(1));
+        assert_eq!(queue.recv().await.unwrap(), Some(2));
+        assert_eq!(queue.stats().dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn push_returns_the_dropped_item() {
+        let oldest = BoundedQueue::new("event", 1, OverflowPolicy::DropOldest);
+        assert_eq!(oldest.push(1).await, None);
+        assert_eq!(oldest.push(2).await, Some(1));
+        let newest = BoundedQueue::new("event", 1, OverflowPolicy::DropNewest);
+        newest.push(1).await;
+        assert_eq!(newest.push(2).await, Some(2));
+        let closing = BoundedQueue::new("event", 1, OverflowPolicy::CloseWithError);
+        closing.push(1).await;
+        assert_eq!(closing.push(2).await, Some(2));
+        assert_eq!(closing.push(3).await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn close_with_error_drains_then_fails() {
+        let queue = BoundedQueue::new("raw", 1, OverflowPolicy::CloseWithError);
+        fill(&queue, [1, 2, 3]).await;
+        assert_eq!(queue.recv().await.unwrap(), Some(1));
+        match queue.recv().await {
+            Err(NetworkError::QueueOverflow { queue, capacity }) => {
+                assert_eq!((queue.as_str(), capacity), ("raw", 1));
+            }
+            other => panic!("expected QueueOverflow, got {other:?}"),
+        }
+        // 閉じた後の要素も dropped に数える
+        assert_eq!(queue.stats().dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_space() {
+        let queue = std::sync::Arc::new(BoundedQueue::new("event", 1, OverflowPolicy::Block));
+        queue.push(1).await;
+        let producer = {
+            let queue = std::sync::Arc::clone(&queue);
+            tokio::spawn(async move { queue.push(2).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!producer.is_finished(), "push should block on a full queue");
+        assert_eq!(queue.recv().await.unwrap(), Some(1));
+        producer.await.unwrap();
+        assert_eq!(queue.recv().await.unwrap(), Some(2));
+        assert_eq!(queue.stats().overflows, 1);
+        assert_eq!(queue.stats().dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn lossless_items_wait_instead_of_overflowing() {
+        let even = |i: &u32| i.is_multiple_of(2);
+        // 追い出すのは lossless でない要素だけ
+        let queue = BoundedQueue::new("event", 2, OverflowPolicy::DropOldest).with_lossless(even);
+        fill(&queue, [2, 1]).await;
+        assert_eq!(queue.push(3).await, Some(1));
+        assert_eq!(queue.push(5).await, Some(3));
+        assert_eq!(queue.recv().await.unwrap(), Some(2));
+
+        // lossless な要素は policy に関わらず空きを待つ
+        let queue = std::sync::Arc::new(
+            BoundedQueue::new("event", 2, OverflowPolicy::CloseWithError).with_lossless(even),
+        );
+        fill(&queue, [2, 4]).await;
+        let producer = {
+            let queue = std::sync::Arc::clone(&queue);
+            tokio::spawn(async move { queue.push(6).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(
+            !producer.is_finished(),
+            "lossless push should wait for space"
+        );
+        assert_eq!(queue.recv().await.unwrap(), Some(2));
+        assert_eq!(producer.await.unwrap(), None);
+        assert_eq!(queue.recv().await.unwrap(), Some(4));
+        assert_eq!(queue.recv().await.unwrap(), Some(6));
+        assert_eq!(queue.stats().dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn close_ends_recv_after_drain() {
+        let queue = BoundedQueue::new("event", 4, OverflowPolicy::Block);
+        queue.push(1).await;
+        queue.close();
+        assert_eq!(queue.recv().await.unwrap(), Some(1));
+        assert_eq!(queue.recv().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,323 @@
+//! ChannelGroup: 同名 channel の購読者への reliable fan-out (v1.0 で追加)
+//!
+//! `ProtocolServer::broadcast` は datagram channel 専用で、 到達保証が無い。
+//! `ChannelGroup` は接続をまたいで同じ名前の `UnisonChannel` を束ね、
+//! `publish_event()` で全メンバーの stream に Event を送る (= QUIC stream 上なので
+//! lossless・順序保証あり)。
+//!
+//! ```rust,ignore
+//! let feed = ChannelGroup::<JsonCodec>::new("feed")
+//!     .with_queue_depth(64)
+//!     .with_overflow(OverflowPolicy::CloseWithError);
+//! server.register_channel_group(feed.clone()).await;
+//!
+//! // どこからでも publish できる (= Clone は同じ group を指す)
+//! feed.publish_event("Posted", &post).await?;
+//! ```
+//!
+//! - メンバーごとに送信キューと writer task を持つ。 遅いメンバーがいても他の
+//!   メンバーへの配送は止まらない (`Block` を除く)。
+//! - キュー満杯時の振る舞いは [`OverflowPolicy`] で選ぶ。 `CloseWithError` は
+//!   そのメンバーを group から外し、 channel を閉じる (= slow consumer の切り離し)。
+//! - channel が閉じる (peer close / 接続断 / `close()`) とメンバーは自動で外れる。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+use crate::codec::{Codec, Encodable, JsonCodec};
+
+use super::channel::UnisonChannel;
+use super::channel_config::{BoundedQueue, ChannelConfig, OverflowPolicy, QueueStats};
+use super::context::{ConnectionContext, ConnectionId};
+use super::quic::UnisonStream;
+use super::router::ChannelRouter;
+use super::{MessageType, NetworkError, ProtocolMessage};
+
+/// group 内でメンバーを識別する ID (= `join()` の返り値)
+pub type MemberId = u64;
+
+/// デフォルトのメンバーごと送信キュー深さ
+pub const DEFAULT_GROUP_QUEUE_DEPTH: usize = 256;
+
+struct Member<C: Codec> {
+    connection_id: ConnectionId,
+    queue: Arc<BoundedQueue<Arc<ProtocolMessage>>>,
+    channel: Arc<UnisonChannel<C>>,
+    /// `CloseWithError` の overflow で cancel (= 送信中でも writer を止める)
+    evict: CancellationToken,
+}
+
+type MemberMap<C> = Arc<Mutex<HashMap<MemberId, Member<C>>>>;
+
+/// 同名 channel の購読者集合
+///
+/// `Clone` は同じ group を共有する (= 内部は `Arc`)。
+pub struct ChannelGroup<C: Codec = JsonCodec> {
+    name: String,
+    members: MemberMap<C>,
+    next_id: Arc<AtomicU64>,
+    queue_depth: usize,
+    overflow: OverflowPolicy,
+    config: ChannelConfig,
+}
+
+impl<C: Codec> Clone for ChannelGroup<C> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            members: Arc::clone(&self.members),
+            next_id: Arc::clone(&self.next_id),
+            queue_depth: self.queue_depth,
+            overflow: self.overflow,
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<C: Codec> std::fmt::Debug for ChannelGroup<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelGroup")
+            .field("name", &self.name)
+            .field("members", &self.len())
+            .field("queue_depth", &self.queue_depth)
+            .field("overflow", &self.overflow)
+            .finish()
+    }
+}
+
+impl<C: Codec> ChannelGroup<C> {
+    /// 空の group を作成（キュー深さ 256、 `OverflowPolicy::Block`）
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            members: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+            queue_depth: DEFAULT_GROUP_QUEUE_DEPTH,
+            overflow: OverflowPolicy::Block,
+            config: ChannelConfig::default(),
+        }
+    }
+
+    /// メンバーごとの送信キュー深さ（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_queue_depth(mut self, depth: usize) -> Self {
+        self.queue_depth = depth.max(1);
+        self
+    }
+
+    /// 送信キュー満杯時の振る舞い（ビルダーパターン）
+    ///
+    /// - `Block`: 空きが出るまで `publish_event()` が待つ (= 最も遅いメンバーに律速)
+    /// - `DropOldest` / `DropNewest`: そのメンバー宛ての Event を捨てる
+    /// - `CloseWithError`: そのメンバーを外して channel を閉じる
+    pub fn with_overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// `serve()` が構築する `UnisonChannel` の設定（ビルダーパターン）
+    pub fn with_channel_config(mut self, config: ChannelConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// channel 名
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 現在のメンバー数
+    pub fn len(&self) -> usize {
+        self.members.lock().expect("group lock poisoned").len()
+    }
+
+    /// メンバーがいなければ true
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 現在のメンバー (= `(MemberId, ConnectionId)` の組)
+    pub fn members(&self) -> Vec<(MemberId, ConnectionId)> {
+        self.members
+            .lock()
+            .expect("group lock poisoned")
+            .iter()
+            .map(|(id, m)| (*id, m.connection_id))
+            .collect()
+    }
+
+    /// メンバーの送信キュー統計
+    pub fn member_stats(&self, member: MemberId) -> Option<QueueStats> {
+        self.members
+            .lock()
+            .expect("group lock poisoned")
+            .get(&member)
+            .map(|m| m.queue.stats())
+    }
+
+    /// channel を group に加える
+    ///
+    /// メンバーごとの writer task を起動する。 channel が閉じると自動で外れる。
+    pub fn join(&self, ctx: &ConnectionContext, channel: Arc<UnisonChannel<C>>) -> MemberId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let queue = Arc::new(BoundedQueue::new("group", self.queue_depth, self.overflow));
+        let evict = CancellationToken::new();
+        self.members.lock().expect("group lock poisoned").insert(
+            id,
+            Member {
+                connection_id: ctx.connection_id,
+                queue: Arc::clone(&queue),
+                channel: Arc::clone(&channel),
+                evict: evict.clone(),
+            },
+        );
+        debug!(
+            "Connection {} joined channel group '{}' as member {}",
+            ctx.connection_id, self.name, id
+        );
+
+        let members = Arc::clone(&self.members);
+        let name = self.name.clone();
+        tokio::spawn(async move {
+            let overflow = || NetworkError::QueueOverflow {
+                queue: "group".to_string(),
+                capacity: queue.capacity(),
+            };
+            let result = loop {
+                tokio::select! {
+                    _ = channel.closed() => break Ok(()),
+                    _ = evict.cancelled() => break Err(overflow()),
+                    item = queue.recv() => match item {
+                        Ok(Some(msg)) => {
+                            // peer が読まず flow control で止まっていても evict で抜ける
+                            tokio::select! {
+                                sent = channel.send_message(&msg) => {
+                                    if let Err(e) = sent {
+                                        break Err(e);
+                                    }
+                                }
+                                _ = evict.cancelled() => break Err(overflow()),
+                            }
+                        }
+                        // leave() で閉じた
+                        Ok(None) => break Ok(()),
+                        Err(e) => break Err(e),
+                    },
+                }
+            };
+            // Block で待っている publisher を起こす
+            queue.close();
+            members.lock().expect("group lock poisoned").remove(&id);
+            match result {
+                Ok(()) => debug!("Member {} left channel group '{}'", id, name),
+                Err(e @ NetworkError::QueueOverflow { .. }) => {
+                    warn!(
+                        "Removing slow member {} from channel group '{}': {}",
+                        id, name, e
+                    );
+                    let _ = channel.close().await;
+                }
+                Err(e) => debug!("Member {} of channel group '{}' failed: {}", id, name, e),
+            }
+        });
+        id
+    }
+
+    /// メンバーを外す（channel 自体は閉じない）
+    ///
+    /// キューに溜まった Event は送り切ってから writer task が終わる。
+    pub fn leave(&self, member: MemberId) -> bool {
+        match self
+            .members
+            .lock()
+            .expect("group lock poisoned")
+            .remove(&member)
+        {
+            Some(m) => {
+                m.queue.close();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 全メンバーに Event を送る
+    ///
+    /// payload の encode は 1 回だけ。 返り値は配送対象にしたメンバー数
+    /// (= `DropNewest` 等で捨てられた分も含む)。
+    pub async fn publish_event<T: Encodable<C>>(
+        &self,
+        method: &str,
+        payload: &T,
+    ) -> Result<usize, NetworkError> {
+        let bytes = payload.encode().map_err(NetworkError::Codec)?;
+        let msg = Arc::new(ProtocolMessage::new_encoded(
+            0,
+            method.to_string(),
+            MessageType::Event,
+            bytes,
+        ));
+        let targets: Vec<_> = self
+            .members
+            .lock()
+            .expect("group lock poisoned")
+            .values()
+            .map(|m| (Arc::clone(&m.queue), m.evict.clone()))
+            .collect();
+        for (queue, evict) in &targets {
+            queue.push(Arc::clone(&msg)).await;
+            if queue.is_overflowed() {
+                evict.cancel();
+            }
+        }
+        Ok(targets.len())
+    }
+
+    /// 全メンバーの channel を閉じて group を空にする
+    pub async fn close_all(&self) {
+        let members: Vec<_> = self
+            .members
+            .lock()
+            .expect("group lock poisoned")
+            .drain()
+            .map(|(_, m)| m)
+            .collect();
+        for member in members {
+            member.queue.close();
+            let _ = member.channel.close().await;
+        }
+    }
+
+    /// stream を channel にして group に加え、 閉じるまで待つ (= 購読専用 channel)
+    ///
+    /// peer からの Request には [`ProtocolError::METHOD_NOT_FOUND`] を返す。
+    ///
+    /// [`ProtocolError::METHOD_NOT_FOUND`]: super::ProtocolError::METHOD_NOT_FOUND
+    pub async fn serve(
+        &self,
+        ctx: Arc<ConnectionContext>,
+        stream: UnisonStream,
+    ) -> Result<(), NetworkError> {
+        self.serve_with_router(ctx, stream, &ChannelRouter::new())
+            .await
+    }
+
+    /// stream を channel にして group に加え、 `router` で受信を処理する
+    ///
+    /// channel は group の `with_channel_config()` で構築する
+    /// (= router 側の `with_channel_config()` は使わない)。
+    pub async fn serve_with_router(
+        &self,
+        ctx: Arc<ConnectionContext>,
+        stream: UnisonStream,
+        router: &ChannelRouter<C>,
+    ) -> Result<(), NetworkError> {
+        let channel = Arc::new(UnisonChannel::<C>::with_config(stream, self.config.clone()));
+        let member = self.join(&ctx, Arc::clone(&channel));
+        let result = router.serve_channel(ctx, channel).await;
+        self.leave(member);
+        result
+    }
+}
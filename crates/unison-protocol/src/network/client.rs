@@ -1,21 +1,32 @@
 use anyhow::Result;
+use std::collections::HashSet;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio::sync::broadcast;
 
 use crate::codec::{Codec, JsonCodec};
+use crate::parser::ParsedSchema;
 
-use super::channel::UnisonChannel;
+use super::NetworkError;
+use super::auth::{Credentials, authenticate};
+use super::channel::{ChannelShared, UnisonChannel};
+use super::channel_config::ChannelConfig;
 use super::context::ConnectionContext;
 use super::datagram_channel::DatagramChannel;
 use super::datagram_dispatcher::DatagramDispatcher;
-use super::identity::ServerIdentity;
-use super::quic::{
-    CHANNEL_ACK_METHOD, FRAME_TYPE_PROTOCOL, QuicClient, UnisonStream, read_typed_frame,
-    write_typed_frame,
+use super::dispatch::open_channel_stream;
+use super::heartbeat::{
+    HEARTBEAT_TIMEOUT_REASON, HeartbeatConfig, HeartbeatOutcome, HeartbeatStats, run_heartbeat,
 };
-use super::{MessageType, NetworkError, ProtocolMessage};
+use super::identity::{ChannelUpdate, ServerIdentity};
+use super::quic::{EarlyDataState, QuicClient, UnisonStream};
+use super::reconnect::ReconnectPolicy;
+use super::router::ChannelRouter;
+use super::server::ChannelHandler;
 
 /// Client side connection event (v0.10.0 で追加、 [`ProtocolServer::ConnectionEvent`] と parallel)
 ///
@@ -26,13 +37,16 @@ use super::{MessageType, NetworkError, ProtocolMessage};
 /// **冪等性を持つ**形で扱う責務がある (= 「Disconnected を連続で受けても 1 回の disconnect」
 /// として扱う、 reason 文字列を見て filter する 等)。
 ///
-/// Library は auto-reconnect しない (= caller がこの event を見て自身のポリシーで
-/// 再接続を実行する責務を持つ)。
+/// [`ProtocolClient::with_reconnect`] で [`ReconnectPolicy`] を設定しない限り、 library は
+/// auto-reconnect しない (= caller がこの event を見て自身のポリシーで再接続を実行する
+/// 責務を持つ)。 設定した場合は `Disconnected` の後に `Reconnecting` → `Reconnected`
+/// (または `ReconnectFailed`) が続く。
 #[derive(Debug, Clone)]
 pub enum ClientConnectionEvent {
     /// Server へ接続確立 (= `connect()` 成功時に fire)
     Connected {
-        /// 接続先 server の SocketAddr
+        /// 接続先 server の SocketAddr (v1.0: hostname が複数の address に解決された
+        /// 場合は happy-eyeballs で確立した address)
         remote_addr: SocketAddr,
     },
     /// Server との接続切断 (= 明示 `disconnect()` / 受動 drop どちらでも fire)
@@ -40,6 +54,42 @@ pub enum ClientConnectionEvent {
         /// 切断理由 (= caller が再接続判断に使う、 free text)
         reason: String,
     },
+    /// Server が graceful shutdown を開始した (= `__goaway` 受信、 v1.0 で追加)
+    ///
+    /// 以降の `open_channel` は `server-draining` で拒否され、 `deadline` 以内に
+    /// 接続が閉じられる。 caller は処理中の request を切り上げ、 別 server への
+    /// 再接続を準備する。
+    Draining {
+        /// server が接続を閉じるまでの猶予
+        deadline: Duration,
+    },
+    /// 再接続を試みる (v1.0 で追加、 [`ReconnectPolicy`] 設定時のみ)
+    ///
+    /// `delay` 待ってから `attempt` 回目 (1 始まり) の dial を行う。
+    Reconnecting {
+        /// 何回目の試行か
+        attempt: u32,
+        /// dial までの待ち時間 (= jitter 込みの backoff)
+        delay: Duration,
+    },
+    /// 再接続し、 接続断の時点で開いていた channel を開き直した (v1.0 で追加)
+    Reconnected {
+        /// 接続先 server の SocketAddr
+        remote_addr: SocketAddr,
+        /// 成功までに要した試行回数
+        attempts: u32,
+        /// 開き直した channel の名前 (= 開き直せなかった channel は閉じられる)
+        channels: Vec<String>,
+    },
+    /// `max_attempts` 回失敗して再接続を諦めた (v1.0 で追加)
+    ///
+    /// 再接続待ちだった channel は閉じられる。 以降は caller が `connect()` し直す。
+    ReconnectFailed {
+        /// 試行回数
+        attempts: u32,
+        /// 最後の試行の失敗理由
+        error: String,
+    },
 }
 
 /// [`ProtocolClient::subscribe_connection_events`] が返す event receiver
@@ -78,26 +128,63 @@ impl ClientConnectionEventReceiver {
 
 /// QUIC protocol client implementation
 pub struct ProtocolClient {
+    /// drop detection / 再接続 task と共有する状態
+    shared: Arc<ClientShared>,
+    /// Datagram dispatcher (= lazy spawn on first `open_datagram_channel`、 v0.10.0 で追加)
+    datagram_dispatcher: Mutex<Option<Arc<DatagramDispatcher>>>,
+}
+
+/// [`ProtocolClient`] の接続手順と再接続に必要な状態 (v1.0 で分離)
+///
+/// drop detection task は弱参照で持ち、 client が drop されたら再接続しない。
+struct ClientShared {
     transport: Arc<QuicClient>,
     /// 接続コンテキスト（Identity情報・チャネル状態）
     context: Arc<ConnectionContext>,
-    /// Datagram dispatcher (= lazy spawn on first `open_datagram_channel`、 v0.10.0 で追加)
-    datagram_dispatcher: Mutex<Option<Arc<DatagramDispatcher>>>,
     /// Connection event broadcast (v0.10.0 で追加、 server `connection_event_tx` と parallel)
     ///
     /// capacity 16: 1 client の lifecycle event (= Connected / Disconnected) は
     /// 再接続 burst でも 10/秒 を超えない想定、 16 件 buffer で十分。
     connection_event_tx: broadcast::Sender<ClientConnectionEvent>,
+    /// `connect()` 時に `__auth` で提示する資格情報 (v1.0 で追加)
+    credentials: std::sync::RwLock<Option<Credentials>>,
+    /// 自動再接続の設定 (v1.0 で追加、 `None` なら再接続しない)
+    reconnect: std::sync::RwLock<Option<ReconnectPolicy>>,
+    /// heartbeat の設定 (v1.0 で追加、 `None` なら送らない)
+    heartbeat: std::sync::RwLock<Option<HeartbeatConfig>>,
+    /// 0-RTT の間に (= handshake を待たずに) 開いてよい channel (v1.0 で追加)
+    zero_rtt_channels: std::sync::RwLock<HashSet<String>>,
+    /// 最後に `connect()` した URL (= 再接続先)
+    url: std::sync::Mutex<Option<String>>,
+    /// 最後に `connect_with_server_name()` で指定した TLS の server name (v1.0 で追加)
+    server_name: std::sync::Mutex<Option<String>>,
+    /// 再接続で開き直す channel (= 名前と弱参照)
+    channels: std::sync::Mutex<Vec<(String, Weak<ChannelShared>)>>,
+    /// 明示 `disconnect()` 済み (= 再接続しない)
+    closing: AtomicBool,
+    /// 再接続ループが走っている
+    reconnecting: AtomicBool,
 }
 
 impl ProtocolClient {
     pub fn new(transport: QuicClient) -> Self {
         let (event_tx, _) = broadcast::channel(16);
         Self {
-            transport: Arc::new(transport),
-            context: Arc::new(ConnectionContext::new()),
+            shared: Arc::new(ClientShared {
+                context: Arc::clone(transport.context()),
+                transport: Arc::new(transport),
+                connection_event_tx: event_tx,
+                credentials: std::sync::RwLock::new(None),
+                reconnect: std::sync::RwLock::new(None),
+                heartbeat: std::sync::RwLock::new(None),
+                zero_rtt_channels: std::sync::RwLock::new(HashSet::new()),
+                url: std::sync::Mutex::new(None),
+                server_name: std::sync::Mutex::new(None),
+                channels: std::sync::Mutex::new(Vec::new()),
+                closing: AtomicBool::new(false),
+                reconnecting: AtomicBool::new(false),
+            }),
             datagram_dispatcher: Mutex::new(None),
-            connection_event_tx: event_tx,
         }
     }
 
@@ -110,14 +197,112 @@ impl ProtocolClient {
     ///
     /// [`TrustAnchors`]: crate::network::trust::TrustAnchors
     pub fn new_default() -> Result<Self> {
-        let transport = QuicClient::new()?;
-        let (event_tx, _) = broadcast::channel(16);
-        Ok(Self {
-            transport: Arc::new(transport),
-            context: Arc::new(ConnectionContext::new()),
-            datagram_dispatcher: Mutex::new(None),
-            connection_event_tx: event_tx,
-        })
+        Ok(Self::new(QuicClient::new()?))
+    }
+
+    /// 接続認証の資格情報を設定（ビルダーパターン、 v1.0 で追加）
+    ///
+    /// `connect()` は Identity handshake の後に `__auth` で認証し、 拒否されたら接続を
+    /// 閉じて [`NetworkError::Unauthenticated`] を返す。 認証された principal は
+    /// `context().principal()` で参照できる。 再接続時も同じ資格情報で認証し直す。
+    pub fn with_credentials(self, credentials: Credentials) -> Self {
+        *self
+            .shared
+            .credentials
+            .write()
+            .expect("credentials lock poisoned") = Some(credentials);
+        self
+    }
+
+    /// 自動再接続を有効にする（ビルダーパターン、 v1.0 で追加）
+    ///
+    /// 明示 `disconnect()` 以外で接続が切れると、 `policy` の backoff で同じ URL へ
+    /// 再接続し、 Identity handshake / 認証をやり直して、 開いていた channel を開き直す
+    /// (= 手元の `UnisonChannel` はそのまま使い続けられる)。 詳細は
+    /// [`super::reconnect`] を参照。
+    pub fn with_reconnect(self, policy: ReconnectPolicy) -> Self {
+        *self
+            .shared
+            .reconnect
+            .write()
+            .expect("reconnect lock poisoned") = Some(policy);
+        self
+    }
+
+    /// heartbeat を有効にする（ビルダーパターン、 v1.0 で追加）
+    ///
+    /// 接続 (と再接続) のたびに `config.interval` ごとに `__heartbeat` の ping を送り、
+    /// RTT を [`Self::rtt`] / [`Self::heartbeat_stats`] に記録する。 `max_missed` 回続けて
+    /// pong が届かなければサーバーを dead とみなして接続を閉じ、
+    /// `Disconnected { reason: "heartbeat timeout" }` を fire する (= [`ReconnectPolicy`]
+    /// があれば再接続に進む)。 詳細は [`super::heartbeat`] を参照。
+    pub fn with_heartbeat(self, config: HeartbeatConfig) -> Self {
+        *self
+            .shared
+            .heartbeat
+            .write()
+            .expect("heartbeat lock poisoned") = Some(config);
+        self
+    }
+
+    /// 0-RTT で開いてよい channel を設定する（ビルダーパターン、 v1.0 で追加）
+    ///
+    /// [`QuicClientBuilder::enable_0rtt`](super::quic::QuicClientBuilder::enable_0rtt) で
+    /// 0-RTT の再接続になったとき、 ここに挙げた channel は handshake の完了を待たずに
+    /// open する (= 1 RTT 早く使える)。 0-RTT のデータは第三者に replay され得るので、
+    /// 何度処理されても結果が変わる request を持たない channel だけを挙げること。
+    /// それ以外の channel の open と `__auth` は handshake の完了を待つ。 通常は schema の
+    /// `idempotent=#true` から [`Self::load_zero_rtt_channels`] で読み込む。
+    pub fn with_zero_rtt_channels<I, S>(self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.shared
+            .zero_rtt_channels
+            .write()
+            .expect("zero_rtt_channels lock poisoned")
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// schema の replay-safe な channel を 0-RTT で開く channel として読み込む（v1.0 で追加）
+    ///
+    /// 全ての request が `idempotent=#true` の stream channel
+    /// ([`Channel::is_replay_safe`](crate::parser::Channel::is_replay_safe)) を追加し、
+    /// 追加した数を返す。
+    pub fn load_zero_rtt_channels(&self, schema: &ParsedSchema) -> usize {
+        let Some(protocol) = &schema.protocol else {
+            return 0;
+        };
+        let mut names = self
+            .shared
+            .zero_rtt_channels
+            .write()
+            .expect("zero_rtt_channels lock poisoned");
+        let mut count = 0;
+        for channel in protocol.channels.iter().filter(|c| c.is_replay_safe()) {
+            names.insert(channel.name.clone());
+            count += 1;
+        }
+        count
+    }
+
+    /// 現在の接続の 0-RTT の状態（v1.0 で追加）
+    pub fn early_data(&self) -> EarlyDataState {
+        self.shared.transport.early_data()
+    }
+
+    /// heartbeat で測った最新の RTT（v1.0 で追加）
+    ///
+    /// heartbeat が無効か、 まだ pong が届いていなければ `None`。
+    pub fn rtt(&self) -> Option<Duration> {
+        self.shared.context.rtt()
+    }
+
+    /// heartbeat の計測値（v1.0 で追加、 現在の接続の分）
+    pub fn heartbeat_stats(&self) -> HeartbeatStats {
+        self.shared.context.heartbeat_stats()
     }
 
     /// Connection lifecycle event (= Connected / Disconnected) を subscribe する
@@ -127,22 +312,32 @@ impl ProtocolClient {
     /// parallel な API。 caller は subscribe 後に [`ClientConnectionEventReceiver::recv`]
     /// で event を読む。 複数の caller が同時に subscribe 可能 (= broadcast)。
     ///
-    /// 注: library は自動 reconnect しない。 caller が `Disconnected` を受け取ったら
-    /// 自身のポリシーで `client.connect(url)` を再呼び出しする責務を持つ。
+    /// 注: [`Self::with_reconnect`] を設定しない限り library は自動 reconnect しない。
+    /// caller が `Disconnected` を受け取ったら自身のポリシーで `client.connect(url)` を
+    /// 再呼び出しする責務を持つ。
     pub fn subscribe_connection_events(&self) -> ClientConnectionEventReceiver {
         ClientConnectionEventReceiver {
-            inner: self.connection_event_tx.subscribe(),
+            inner: self.shared.connection_event_tx.subscribe(),
         }
     }
 
     /// 接続コンテキストを取得
     pub fn context(&self) -> &Arc<ConnectionContext> {
-        &self.context
+        &self.shared.context
     }
 
     /// サーバーから受信したIdentity情報を取得
     pub async fn server_identity(&self) -> Option<ServerIdentity> {
-        self.context.identity().await
+        self.shared.context.identity().await
+    }
+
+    /// サーバーから push されたチャネル更新を subscribe する（v1.0 で追加）
+    ///
+    /// サーバーがチャネルを登録 / 解除 / 状態変更すると `ChannelUpdate` が届く。
+    /// 受信時点で [`Self::server_identity`] には反映済み。 `Lagged` で取りこぼした場合は
+    /// [`Self::server_identity`] を読み直せばよい。 再接続後も同じ receiver で受け取れる。
+    pub fn subscribe_channel_updates(&self) -> broadcast::Receiver<ChannelUpdate> {
+        self.shared.transport.subscribe_channel_updates()
     }
 
     /// チャネルを開く（UnisonChannel を返す）
@@ -155,79 +350,65 @@ impl ProtocolClient {
     /// `open_ack` を待つことで、 fire-and-forget だった旧挙動の「accept されたか
     /// 分からない」問題を解消する。
     pub async fn open_channel(&self, channel_name: &str) -> Result<UnisonChannel, NetworkError> {
-        let connection_guard = self.transport.connection().read().await;
-        let connection = connection_guard
-            .as_ref()
-            .ok_or(NetworkError::NotConnected)?;
-
-        // 新しい双方向ストリームを開く
-        let (mut send_stream, mut recv_stream) = connection
-            .open_bi()
+        self.open_channel_with_config(channel_name, ChannelConfig::default())
             .await
-            .map_err(|e| NetworkError::Quic(format!("Failed to open channel stream: {}", e)))?;
-
-        // チャネル識別メッセージを送信（length-prefixed）
-        let method = format!("__channel:{}", channel_name);
-        let request_id = generate_request_id();
-        let message = ProtocolMessage::new_with_json(
-            request_id,
-            method,
-            MessageType::Request,
-            serde_json::json!({}),
-        )?;
-
-        let frame = message.into_frame().map_err(|e| {
-            NetworkError::Protocol(format!("Failed to create channel frame: {}", e))
-        })?;
-        let frame_bytes = frame.to_bytes();
-        write_typed_frame(&mut send_stream, FRAME_TYPE_PROTOCOL, &frame_bytes)
-            .await
-            .map_err(|e| NetworkError::Protocol(format!("Failed to send channel open: {}", e)))?;
-
-        // Phase 6c: サーバーの open_ack を待つ。 server は handler を起動する前に
-        // 同 stream へ `__channel_ack` frame を 1 本返す。 これを recv loop に渡る
-        // 前にここで read することで、 accept されたかを確定させる。
-        let ack = read_channel_ack(&mut recv_stream).await?;
-        match ack.msg_type {
-            MessageType::Response => {
-                tracing::debug!("Channel '{}' open_ack received", channel_name);
-            }
-            MessageType::Error => {
-                let payload = ack.payload_as_value().unwrap_or_default();
-                return Err(NetworkError::Protocol(format!(
-                    "Channel '{}' open rejected: {}",
-                    channel_name, payload
-                )));
-            }
-            other => {
-                return Err(NetworkError::Protocol(format!(
-                    "Channel '{}' open: unexpected ack msg_type {:?}",
-                    channel_name, other
-                )));
-            }
-        }
+    }
 
-        // UnisonStreamを作成してUnisonChannelでラップ
-        // quinn のストリームを transport 非依存の trait object へ box する。
-        let conn_arc: Arc<dyn super::conn::UnisonConn> = Arc::new(connection.clone());
-        let stream = UnisonStream::from_streams(
-            request_id,
-            format!("__channel:{}", channel_name),
-            conn_arc,
-            Box::new(send_stream),
-            Box::new(recv_stream),
-        );
+    /// 受信キュー設定を指定してチャネルを開く（v1.0 で追加）
+    ///
+    /// handshake は [`Self::open_channel`] と同じ。 `config` で Event / Raw キューの深さと
+    /// overflow policy を指定できる (= [`UnisonChannel::with_config`])。
+    pub async fn open_channel_with_config(
+        &self,
+        channel_name: &str,
+        config: ChannelConfig,
+    ) -> Result<UnisonChannel, NetworkError> {
+        let stream = self.shared.open_stream(channel_name).await?;
+        self.shared.register_channel(channel_name, &stream).await;
 
-        // コンテキストにチャネルを登録
-        self.context
-            .register_channel(super::context::ChannelHandle {
-                channel_name: channel_name.to_string(),
-                stream_id: request_id,
-                direction: super::identity::ChannelDirection::Bidirectional,
-            })
-            .await;
+        if !self.shared.reconnect_enabled() {
+            return Ok(UnisonChannel::with_config(stream, config));
+        }
+        // v1.0: 再接続で開き直せるよう registry に弱参照で載せる
+        let channel = UnisonChannel::resumable(stream, config);
+        let mut channels = self.shared.channels.lock().expect("channels lock poisoned");
+        channels.retain(|(_, weak)| weak.strong_count() > 0);
+        channels.push((channel_name.to_string(), Arc::downgrade(channel.shared())));
+        drop(channels);
+        Ok(channel)
+    }
+
+    /// サーバー発信 channel のハンドラーを登録（v1.0 で追加）
+    ///
+    /// [`ProtocolServer::register_channel`](super::server::ProtocolServer::register_channel)
+    /// と同じ形。 サーバーが `ProtocolServer::open_channel(connection_id, name)` で
+    /// この client への channel を開くと、 `open_ack` を返してから handler を起動する。
+    /// 未登録の name は nack で reject される。 `connect()` の前後どちらで登録してもよい。
+    pub async fn register_channel<F, Fut>(&self, name: &str, handler: F)
+    where
+        F: Fn(Arc<ConnectionContext>, UnisonStream) -> Fut + Send + Sync + 'static,
+        Fut: futures_util::Future<Output = Result<(), NetworkError>> + Send + 'static,
+    {
+        let handler: ChannelHandler = Arc::new(move |ctx, stream| {
+            Box::pin(handler(ctx, stream))
+                as Pin<Box<dyn futures_util::Future<Output = Result<(), NetworkError>> + Send>>
+        });
+        self.shared
+            .transport
+            .channel_handlers()
+            .write()
+            .await
+            .insert(name.to_string(), handler);
+    }
 
-        Ok(UnisonChannel::new(stream))
+    /// `ChannelRouter` をサーバー発信 channel のハンドラーとして登録（v1.0 で追加）
+    pub async fn register_channel_router<C: Codec>(&self, name: &str, router: ChannelRouter<C>) {
+        let router = Arc::new(router);
+        self.register_channel(name, move |ctx, stream| {
+            let router = Arc::clone(&router);
+            async move { router.serve(ctx, stream).await }
+        })
+        .await;
     }
 
     /// Datagram channel を open (v0.10.0 で追加、 default codec = JsonCodec)
@@ -256,12 +437,7 @@ impl ProtocolClient {
         channel_id: u64,
     ) -> Result<DatagramChannel<C>, NetworkError> {
         // 接続中の connection を取得
-        let connection_guard = self.transport.connection().read().await;
-        let connection = connection_guard
-            .as_ref()
-            .ok_or(NetworkError::NotConnected)?;
-        let connection_arc: Arc<dyn super::conn::UnisonConn> = Arc::new(connection.clone());
-        drop(connection_guard);
+        let connection_arc = self.shared.connection().await?;
 
         // Datagram dispatcher を lazy spawn
         let dispatcher = {
@@ -286,55 +462,204 @@ impl ProtocolClient {
         ))
     }
 
-    /// 接続後にサーバーからIdentityを受信する
-    ///
-    /// Identity 専用の oneshot チャネルから受信するため、
-    /// 他のメッセージが先に到着しても影響を受けない。
-    async fn receive_identity(&self) -> Result<ServerIdentity, NetworkError> {
-        let response = self
-            .transport
-            .receive_identity(std::time::Duration::from_secs(10))
-            .await
-            .map_err(|e| NetworkError::Protocol(format!("Failed to receive identity: {}", e)))?;
-
-        // oneshot に送られるのは常に __identity のみ（client_accept_bi_loop で振り分け済み）
-        debug_assert_eq!(
-            response.method, "__identity",
-            "oneshot routing invariant violated"
-        );
+    /// Unisonサーバーへの接続（Identity Handshake 含む）
+    pub async fn connect(&self, url: &str) -> Result<(), NetworkError> {
+        self.connect_as(url, None).await
+    }
 
-        let identity = ServerIdentity::from_protocol_message(&response)
-            .map_err(|e| NetworkError::Protocol(format!("Failed to parse identity: {}", e)))?;
-        self.context.set_identity(identity.clone()).await;
-        Ok(identity)
+    /// TLS の server name を指定して接続する（v1.0 で追加）
+    ///
+    /// サーバーの証明書は `url` の host ではなく `server_name` で検証する
+    /// (= [`QuicClient::connect_with_server_name`](super::quic::QuicClient::connect_with_server_name))。
+    /// 再接続でも同じ名前を使う。
+    pub async fn connect_with_server_name(
+        &self,
+        url: &str,
+        server_name: &str,
+    ) -> Result<(), NetworkError> {
+        self.connect_as(url, Some(server_name)).await
     }
 
-    /// Unisonサーバーへの接続（Identity Handshake 含む）
-    pub async fn connect(&self, url: &str) -> Result<(), NetworkError> {
-        self.transport
-            .connect(url)
-            .await
-            .map_err(|e| NetworkError::Connection(e.to_string()))?;
+    async fn connect_as(&self, url: &str, server_name: Option<&str>) -> Result<(), NetworkError> {
+        self.shared.closing.store(false, Ordering::SeqCst);
+        *self.shared.url.lock().expect("url lock poisoned") = Some(url.to_string());
+        *self
+            .shared
+            .server_name
+            .lock()
+            .expect("server_name lock poisoned") = server_name.map(str::to_string);
+        let remote_addr = self.shared.dial(url).await?;
 
         // v0.10.0 Step 2: Connected event を fire (= subscribe している caller に通知)
-        // remote_addr を connection から取得 (= ない場合は空 SocketAddr で fallback)
-        let remote_addr = {
-            let guard = self.transport.connection().read().await;
-            guard
-                .as_ref()
-                .map(|c| c.remote_address())
-                .unwrap_or_else(|| "[::]:0".parse().expect("fallback addr parse"))
-        };
         let _ = self
+            .shared
             .connection_event_tx
             .send(ClientConnectionEvent::Connected { remote_addr });
 
         // v0.10.0 Step 2: drop detection task を spawn
         // QUIC connection の `closed()` future が resolve したら自動的に Disconnected を fire
         // (= server 側 close / network error / 明示 disconnect 何れでも発火)
-        self.spawn_drop_detection_task(remote_addr).await;
+        ClientShared::spawn_drop_detection_task(&self.shared, remote_addr).await;
+
+        // Identity Handshake と接続認証 (= 認証の失敗は接続ごと捨てる)
+        if let Err(e) = self.shared.handshake().await {
+            let _ = self.disconnect().await;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// サーバーからの切断
+    pub async fn disconnect(&self) -> Result<(), NetworkError> {
+        // v1.0: 明示 disconnect では再接続せず、 再接続待ちの channel も閉じる
+        self.shared.closing.store(true, Ordering::SeqCst);
+        self.shared.close_channels().await;
+        self.shared
+            .transport
+            .disconnect()
+            .await
+            .map_err(|e| NetworkError::Connection(e.to_string()))?;
+        // v0.10.0 Step 2: 明示 disconnect でも Disconnected event を fire (= subscribe
+        // 側で「自分で disconnect した」 を別 path で識別したい場合は reason 文字列で判定)
+        // 注: spawn_drop_detection_task の `closed().await` も同時に fire するため、
+        // 同 disconnect で 2 件 event が流れる可能性がある。 Subscriber 側は冪等性を持つ
+        // (= 「Disconnected を 2 回連続で受けても 1 回の disconnect」 として扱う) のが原則。
+        let _ = self
+            .shared
+            .connection_event_tx
+            .send(ClientConnectionEvent::Disconnected {
+                reason: "explicit disconnect by caller".to_string(),
+            });
+        Ok(())
+    }
+
+    /// クライアント接続状態の確認
+    pub async fn is_connected(&self) -> bool {
+        self.shared.transport.is_connected().await
+    }
+}
+
+impl ClientShared {
+    /// 現在の connection (= 未接続なら `NotConnected`)
+    async fn connection(&self) -> Result<Arc<dyn super::conn::UnisonConn>, NetworkError> {
+        let guard = self.transport.connection().read().await;
+        Ok(Arc::new(
+            guard.as_ref().ok_or(NetworkError::NotConnected)?.clone(),
+        ))
+    }
+
+    fn reconnect_enabled(&self) -> bool {
+        self.reconnect
+            .read()
+            .expect("reconnect lock poisoned")
+            .is_some()
+    }
+
+    /// channel の stream を開く (= 0-RTT の間は replay-safe な channel だけ先に開く)
+    ///
+    /// 0-RTT で開いた stream はサーバーが early data を拒否すると失敗するので、
+    /// handshake の完了後に 1 回だけ開き直す。
+    async fn open_stream(&self, channel_name: &str) -> Result<UnisonStream, NetworkError> {
+        let mut early = self.transport.early_data() == EarlyDataState::Pending;
+        if early
+            && !self
+                .zero_rtt_channels
+                .read()
+                .expect("zero_rtt_channels lock poisoned")
+                .contains(channel_name)
+        {
+            self.transport.wait_handshake().await;
+            early = false;
+        }
+        let result = open_channel_stream(self.connection().await?, channel_name).await;
+        match result {
+            Err(e) if early => {
+                if self.transport.wait_handshake().await != EarlyDataState::Rejected {
+                    return Err(e);
+                }
+                tracing::debug!(
+                    "0-RTT rejected, reopening channel '{}' after handshake",
+                    channel_name
+                );
+                open_channel_stream(self.connection().await?, channel_name).await
+            }
+            result => result,
+        }
+    }
+
+    /// コンテキストにチャネルを登録
+    async fn register_channel(&self, channel_name: &str, stream: &UnisonStream) {
+        self.context
+            .register_channel(super::context::ChannelHandle {
+                channel_name: channel_name.to_string(),
+                stream_id: stream.stream_id(),
+                direction: super::identity::ChannelDirection::Bidirectional,
+            })
+            .await;
+    }
+
+    /// QUIC 接続を張り、 接続先の SocketAddr を返す
+    async fn dial(&self, url: &str) -> Result<SocketAddr, NetworkError> {
+        let server_name = self
+            .server_name
+            .lock()
+            .expect("server_name lock poisoned")
+            .clone();
+        let connected = match server_name {
+            Some(server_name) => {
+                self.transport
+                    .connect_with_server_name(url, &server_name)
+                    .await
+            }
+            None => self.transport.connect(url).await,
+        };
+        // connect timeout は `NetworkError::Timeout` のまま返す
+        connected.map_err(|e| match e.downcast::<NetworkError>() {
+            Ok(e) => e,
+            Err(e) => NetworkError::Connection(format!("{:#}", e)),
+        })?;
+
+        // remote_addr を connection から取得 (= ない場合は空 SocketAddr で fallback)
+        let guard = self.transport.connection().read().await;
+        Ok(guard
+            .as_ref()
+            .map(|c| c.remote_address())
+            .unwrap_or_else(|| "[::]:0".parse().expect("fallback addr parse")))
+    }
+
+    /// Identity の受信と `__auth` (= `dial()` の直後に行う)
+    ///
+    /// Identity の受信失敗は non-fatal、 認証の失敗は `Err`。 v1.0: 0-RTT の接続では
+    /// Identity を待たずに返し (= 受信は background)、 資格情報は handshake の完了後に
+    /// 送る (= replay されないように)。
+    async fn handshake(self: &Arc<Self>) -> Result<(), NetworkError> {
+        let credentials = self
+            .credentials
+            .read()
+            .expect("credentials lock poisoned")
+            .clone();
+        if self.transport.early_data() == EarlyDataState::Pending && credentials.is_none() {
+            let shared = Arc::clone(self);
+            tokio::spawn(async move { shared.log_identity().await });
+            return Ok(());
+        }
 
         // Identity Handshake: サーバーからIdentityを受信
+        self.log_identity().await;
+
+        // v1.0: 接続認証
+        if let Some(credentials) = credentials {
+            self.transport.wait_handshake().await;
+            let connection = self.connection().await?;
+            let principal = authenticate(&connection, &credentials).await?;
+            tracing::info!("Authenticated as '{}'", principal.id);
+            self.context.set_principal(principal);
+        }
+        Ok(())
+    }
+
+    /// Identity を受信してログに残す (= 失敗は non-fatal)
+    async fn log_identity(&self) {
         match self.receive_identity().await {
             Ok(identity) => {
                 tracing::info!(
@@ -347,93 +672,234 @@ impl ProtocolClient {
                 tracing::warn!("Failed to receive identity (non-fatal): {}", e);
             }
         }
+    }
 
-        Ok(())
+    /// 接続後にサーバーからIdentityを受信する
+    ///
+    /// Identity 専用の oneshot チャネルから受信するため、
+    /// 他のメッセージが先に到着しても影響を受けない。
+    async fn receive_identity(&self) -> Result<ServerIdentity, NetworkError> {
+        let response = self
+            .transport
+            .receive_identity(Duration::from_secs(10))
+            .await
+            .map_err(|e| NetworkError::Protocol(format!("Failed to receive identity: {}", e)))?;
+
+        // oneshot に送られるのは常に __identity のみ（client_accept_bi_loop で振り分け済み）
+        debug_assert_eq!(
+            response.method, "__identity",
+            "oneshot routing invariant violated"
+        );
+
+        // `context` への設定は client_accept_bi_loop 側で済んでいる (= 以降のチャネル更新と
+        // 順序を保つため、 ここで上書きしない)
+        ServerIdentity::from_protocol_message(&response)
+            .map_err(|e| NetworkError::Protocol(format!("Failed to parse identity: {}", e)))
     }
 
     /// Connection の `closed()` future を await して Disconnected event を fire する task
     /// を spawn (v0.10.0 Step 2)
     ///
     /// connect() のたびに新 task が spawn される。 連続 reconnect で task が重複しても
-    /// 古い connection は既に closed なので即終了する (= leak しない)。
-    async fn spawn_drop_detection_task(&self, remote_addr: SocketAddr) {
-        let event_tx = self.connection_event_tx.clone();
+    /// 古い connection は既に closed なので即終了する (= leak しない)。 v1.0: 明示
+    /// `disconnect()` 以外の切断で [`ReconnectPolicy`] があれば再接続ループを始める。
+    async fn spawn_drop_detection_task(this: &Arc<Self>, remote_addr: SocketAddr) {
+        let event_tx = this.connection_event_tx.clone();
+        let mut goaway_rx = this.transport.subscribe_goaway();
+        let shared = Arc::downgrade(this);
+        let heartbeat_config = this
+            .heartbeat
+            .read()
+            .expect("heartbeat lock poisoned")
+            .clone();
+        let context = Arc::clone(&this.context);
         let connection_handle = {
-            let guard = self.transport.connection().read().await;
+            let guard = this.transport.connection().read().await;
             guard.as_ref().cloned()
         };
         if let Some(connection) = connection_handle {
             tokio::spawn(async move {
-                let close_reason = connection.closed().await;
+                // v1.0: heartbeat を設定していれば同じ task で走らせ、 dead なら接続を閉じる
+                let mut heartbeat_running = heartbeat_config.is_some();
+                let heartbeat = {
+                    let connection: Arc<dyn super::conn::UnisonConn> = Arc::new(connection.clone());
+                    async move {
+                        match heartbeat_config {
+                            Some(config) => run_heartbeat(connection, config, context).await,
+                            None => std::future::pending().await,
+                        }
+                    }
+                };
+                tokio::pin!(heartbeat);
+                // v1.0: 接続が閉じるまでの間に届いた `__goaway` を Draining として転送
+                let close_reason = loop {
+                    tokio::select! {
+                        reason = connection.closed() => {
+                            break format!("connection closed: {}", reason);
+                        }
+                        Ok(deadline) = goaway_rx.recv() => {
+                            let _ = event_tx.send(ClientConnectionEvent::Draining { deadline });
+                        }
+                        outcome = &mut heartbeat, if heartbeat_running => {
+                            heartbeat_running = false;
+                            if outcome == HeartbeatOutcome::Dead {
+                                connection.close(0u32.into(), HEARTBEAT_TIMEOUT_REASON.as_bytes());
+                                break HEARTBEAT_TIMEOUT_REASON.to_string();
+                            }
+                        }
+                    }
+                };
                 let _ = event_tx.send(ClientConnectionEvent::Disconnected {
-                    reason: format!("connection closed: {}", close_reason),
+                    reason: close_reason.clone(),
                 });
                 tracing::debug!(
                     "Drop detection task fired Disconnected for {}: {}",
                     remote_addr,
                     close_reason
                 );
+                drop(connection);
+                if let Some(shared) = shared.upgrade() {
+                    shared.reconnect().await;
+                }
             });
         }
     }
 
-    /// サーバーからの切断
-    pub async fn disconnect(&self) -> Result<(), NetworkError> {
-        self.transport
-            .disconnect()
-            .await
-            .map_err(|e| NetworkError::Connection(e.to_string()))?;
-        // v0.10.0 Step 2: 明示 disconnect でも Disconnected event を fire (= subscribe
-        // 側で「自分で disconnect した」 を別 path で識別したい場合は reason 文字列で判定)
-        // 注: spawn_drop_detection_task の `closed().await` も同時に fire するため、
-        // 同 disconnect で 2 件 event が流れる可能性がある。 Subscriber 側は冪等性を持つ
-        // (= 「Disconnected を 2 回連続で受けても 1 回の disconnect」 として扱う) のが原則。
+    /// 再接続ループ (= [`ReconnectPolicy`] があり、 明示 disconnect でなければ)
+    ///
+    /// drop detection task と互いに呼び合うため、 future の型を box で切る。
+    fn reconnect(self: Arc<Self>) -> Pin<Box<dyn futures_util::Future<Output = ()> + Send>> {
+        Box::pin(self.reconnect_loop())
+    }
+
+    async fn reconnect_loop(self: Arc<Self>) {
+        let Some(policy) = self
+            .reconnect
+            .read()
+            .expect("reconnect lock poisoned")
+            .clone()
+        else {
+            return;
+        };
+        let Some(url) = self.url.lock().expect("url lock poisoned").clone() else {
+            return;
+        };
+        if self.closing.load(Ordering::SeqCst) || self.reconnecting.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let mut attempts = 0;
+        let mut last_error = String::new();
+        while policy.allows(attempts) {
+            attempts += 1;
+            let delay = policy.delay(attempts);
+            let _ = self
+                .connection_event_tx
+                .send(ClientConnectionEvent::Reconnecting {
+                    attempt: attempts,
+                    delay,
+                });
+            tokio::time::sleep(delay).await;
+            if self.closing.load(Ordering::SeqCst) {
+                self.reconnecting.store(false, Ordering::SeqCst);
+                return;
+            }
+
+            let attempt = tokio::time::timeout(policy.attempt_timeout, self.redial(&url)).await;
+            let remote_addr = match attempt.unwrap_or(Err(NetworkError::Timeout)) {
+                Ok(addr) => addr,
+                Err(e) => {
+                    // timeout で打ち切った試行の張りかけの接続も捨てる
+                    let _ = self.transport.disconnect().await;
+                    tracing::warn!("Reconnect attempt {} to {} failed: {}", attempts, url, e);
+                    last_error = e.to_string();
+                    continue;
+                }
+            };
+            let channels = self.reopen_channels().await;
+            self.reconnecting.store(false, Ordering::SeqCst);
+            Self::spawn_drop_detection_task(&self, remote_addr).await;
+            tracing::info!(
+                "Reconnected to {} after {} attempt(s), reopened {} channel(s)",
+                remote_addr,
+                attempts,
+                channels.len()
+            );
+            let _ = self
+                .connection_event_tx
+                .send(ClientConnectionEvent::Reconnected {
+                    remote_addr,
+                    attempts,
+                    channels,
+                });
+            return;
+        }
+
+        tracing::warn!(
+            "Giving up reconnecting to {} after {} attempt(s): {}",
+            url,
+            attempts,
+            last_error
+        );
+        self.close_channels().await;
+        self.reconnecting.store(false, Ordering::SeqCst);
         let _ = self
             .connection_event_tx
-            .send(ClientConnectionEvent::Disconnected {
-                reason: "explicit disconnect by caller".to_string(),
+            .send(ClientConnectionEvent::ReconnectFailed {
+                attempts,
+                error: last_error,
             });
-        Ok(())
     }
 
-    /// クライアント接続状態の確認
-    pub async fn is_connected(&self) -> bool {
-        self.transport.is_connected().await
+    /// 1 回分の再接続 (= dial + handshake)
+    async fn redial(self: &Arc<Self>, url: &str) -> Result<SocketAddr, NetworkError> {
+        let remote_addr = self.dial(url).await?;
+        self.handshake().await?;
+        Ok(remote_addr)
     }
-}
 
-use super::generate_request_id;
+    /// registry の channel を新しい接続で開き直し、 開き直せた名前を返す
+    ///
+    /// 開き直せなかった channel (= サーバーが nack した等) は閉じる。
+    async fn reopen_channels(&self) -> Vec<String> {
+        let entries: Vec<(String, Arc<ChannelShared>)> = {
+            let mut channels = self.channels.lock().expect("channels lock poisoned");
+            channels.retain(|(_, weak)| weak.upgrade().is_some_and(|c| !c.is_closed()));
+            channels
+                .iter()
+                .filter_map(|(name, weak)| Some((name.clone(), weak.upgrade()?)))
+                .collect()
+        };
+        let mut reopened = Vec::new();
+        for (name, channel) in entries {
+            match self.open_stream(&name).await {
+                Ok(stream) => {
+                    self.register_channel(&name, &stream).await;
+                    channel.resume(stream).await;
+                    reopened.push(name);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to reopen channel '{}' after reconnect: {}", name, e);
+                    let _ = channel.close().await;
+                }
+            }
+        }
+        reopened
+    }
 
-/// Channel open 後の `open_ack` (= `__channel_ack` frame) を recv ストリームから
-/// 1 本読む (= Phase 6c)。
-///
-/// サーバーは handler 起動の前に同 stream へ ack を返す。 frame type が
-/// PROTOCOL でない / method が `__channel_ack` でない場合はプロトコル違反として
-/// `Err`。 stream が ack 到着前に終端した場合も `Err` (= no-accept signal)。
-async fn read_channel_ack<R>(recv: &mut R) -> Result<ProtocolMessage, NetworkError>
-where
-    R: tokio::io::AsyncRead + Unpin + ?Sized,
-{
-    let (frame_type, frame_bytes) = read_typed_frame(recv)
-        .await
-        .map_err(|e| NetworkError::Protocol(format!("Failed to read open_ack frame: {}", e)))?;
-    if frame_type != FRAME_TYPE_PROTOCOL {
-        return Err(NetworkError::Protocol(format!(
-            "open_ack: unexpected frame type 0x{:02x}",
-            frame_type
-        )));
-    }
-    let frame = super::ProtocolFrame::from_bytes(&frame_bytes)
-        .map_err(|e| NetworkError::Protocol(format!("Failed to decode open_ack frame: {}", e)))?;
-    let msg = ProtocolMessage::from_frame(&frame)
-        .map_err(|e| NetworkError::Protocol(format!("Failed to parse open_ack: {}", e)))?;
-    if msg.method != CHANNEL_ACK_METHOD {
-        return Err(NetworkError::Protocol(format!(
-            "open_ack: expected method '{}', got '{}'",
-            CHANNEL_ACK_METHOD, msg.method
-        )));
-    }
-    Ok(msg)
+    /// registry の channel を全て閉じる
+    async fn close_channels(&self) {
+        let channels: Vec<_> = self
+            .channels
+            .lock()
+            .expect("channels lock poisoned")
+            .drain(..)
+            .filter_map(|(_, weak)| weak.upgrade())
+            .collect();
+        for channel in channels {
+            let _ = channel.close().await;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -449,6 +915,7 @@ mod tests {
 
         // 手動で event を publish (= 実 connect なしで broadcast 動作を確認)
         let _ = client
+            .shared
             .connection_event_tx
             .send(ClientConnectionEvent::Connected {
                 remote_addr: "127.0.0.1:1234".parse().unwrap(),
@@ -474,6 +941,7 @@ mod tests {
         let mut rx_b = client.subscribe_connection_events();
 
         let _ = client
+            .shared
             .connection_event_tx
             .send(ClientConnectionEvent::Disconnected {
                 reason: "test".to_string(),
@@ -497,6 +965,7 @@ mod tests {
 
         for i in 0..20 {
             let _ = client
+                .shared
                 .connection_event_tx
                 .send(ClientConnectionEvent::Connected {
                     remote_addr: format!("127.0.0.1:{}", 1000 + i).parse().unwrap(),
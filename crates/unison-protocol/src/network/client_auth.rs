@@ -0,0 +1,188 @@
+//! Client certificate authentication (mutual TLS) for QUIC servers.
+//!
+//! # Design
+//!
+//! Server-side counterpart of [`crate::network::trust::TrustAnchors`]: the
+//! operator picks whether clients must present a certificate and which CAs
+//! may issue it, via one of the [`ClientAuth`] variants. The client presents
+//! its certificate with [`crate::network::quic::QuicClientBuilder::client_cert`]
+//! (any [`crate::network::cert::CertSource`]).
+//!
+//! The verified leaf certificate is exposed to handlers as a
+//! [`PeerCertificate`] through `ConnectionContext::peer_certificate()`, so
+//! authorization can key off its SANs.
+//!
+//! # Mode mapping
+//!
+//! | Scenario | Variant |
+//! |----------|---------|
+//! | Public server, app-level auth only | [`ClientAuth::None`] (default) |
+//! | Mixed fleet (mesh peers + anonymous clients) | [`ClientAuth::Optional`] |
+//! | Internal mesh (both sides authenticated) | [`ClientAuth::Required`] (via [`super::mesh::InternalMeshKeypair::client_auth`]) |
+//!
+//! Only the raw QUIC ingress ([`crate::network::quic::QuicServer`]) supports
+//! client certificates; WebTransport sessions never carry one.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use unison::network::client_auth::ClientAuth;
+//! use unison::network::mesh::InternalMeshKeypair;
+//! use unison::network::quic::{QuicClient, QuicServer};
+//! use unison::ProtocolServer;
+//!
+//! let pair = InternalMeshKeypair::generate(["mesh.local".into()])?;
+//!
+//! let server = QuicServer::builder(Arc::new(ProtocolServer::new()))
+//!     .cert_source(pair.server_cert_source.clone())
+//!     .client_auth(pair.client_auth())
+//!     .build();
+//!
+//! let client = QuicClient::builder()
+//!     .trust_anchors(pair.client_trust_anchors.clone())
+//!     .client_cert(pair.client_cert_source())
+//!     .build()?;
+//! # Ok::<_, anyhow::Error>(())
+//! ```
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use rustls::RootCertStore;
+use rustls::pki_types::CertificateDer;
+use rustls::server::WebPkiClientVerifier;
+use rustls::server::danger::ClientCertVerifier;
+use x509_parser::extensions::GeneralName;
+
+/// Client certificate policy of a Unison QUIC server.
+#[derive(Clone, Default)]
+pub enum ClientAuth {
+    /// Do not request a client certificate (default).
+    #[default]
+    None,
+
+    /// Request a client certificate and verify it against these CAs if one is
+    /// presented. Clients without a certificate are still accepted (their
+    /// `ConnectionContext::peer_certificate()` is `None`).
+    Optional(Vec<CertificateDer<'static>>),
+
+    /// Require a client certificate issued by one of these CAs. The TLS
+    /// handshake fails for clients that present none or an untrusted one.
+    Required(Vec<CertificateDer<'static>>),
+}
+
+impl ClientAuth {
+    /// Build the rustls verifier for this mode (`None` for [`Self::None`]).
+    pub(crate) fn build_verifier(self) -> Result<Option<Arc<dyn ClientCertVerifier>>> {
+        let (certs, required) = match self {
+            Self::None => return Ok(None),
+            Self::Optional(certs) => (certs, false),
+            Self::Required(certs) => (certs, true),
+        };
+        if certs.is_empty() {
+            anyhow::bail!("ClientAuth requires at least one CA certificate");
+        }
+        let mut roots = RootCertStore::empty();
+        for cert in certs {
+            roots
+                .add(cert)
+                .context("failed to add client CA certificate to root store")?;
+        }
+        let builder = WebPkiClientVerifier::builder(Arc::new(roots));
+        let builder = if required {
+            builder
+        } else {
+            builder.allow_unauthenticated()
+        };
+        let verifier = builder
+            .build()
+            .map_err(|e| anyhow::anyhow!("failed to build client cert verifier: {}", e))?;
+        Ok(Some(verifier))
+    }
+}
+
+/// The verified leaf certificate a client presented during the TLS handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerCertificate {
+    /// Raw DER of the leaf certificate.
+    pub der: CertificateDer<'static>,
+    /// Subject distinguished name (RFC 4514 string form).
+    pub subject: String,
+    /// DNS names, IP addresses, URIs and e-mail addresses from the
+    /// subjectAltName extension, in certificate order.
+    pub subject_alt_names: Vec<String>,
+}
+
+impl PeerCertificate {
+    /// Parse the subject and SANs out of a DER certificate.
+    pub fn from_der(der: CertificateDer<'static>) -> Result<Self> {
+        let (subject, subject_alt_names) = {
+            let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref())
+                .map_err(|e| anyhow::anyhow!("failed to parse peer certificate: {}", e))?;
+            let sans = cert
+                .subject_alternative_name()
+                .map_err(|e| anyhow::anyhow!("invalid subjectAltName extension: {}", e))?
+                .map(|ext| {
+                    ext.value
+                        .general_names
+                        .iter()
+                        .filter_map(general_name_to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            (cert.subject().to_string(), sans)
+        };
+        Ok(Self {
+            der,
+            subject,
+            subject_alt_names,
+        })
+    }
+
+    /// Whether `name` appears among the SANs (exact match, no wildcard expansion).
+    pub fn has_san(&self, name: &str) -> bool {
+        self.subject_alt_names.iter().any(|san| san == name)
+    }
+}
+
+fn general_name_to_string(name: &GeneralName<'_>) -> Option<String> {
+    match name {
+        GeneralName::DNSName(s) | GeneralName::URI(s) | GeneralName::RFC822Name(s) => {
+            Some((*s).to_string())
+        }
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => <[u8; 4]>::try_from(*bytes)
+                .ok()
+                .map(|b| IpAddr::from(b).to_string()),
+            16 => <[u8; 16]>::try_from(*bytes)
+                .ok()
+                .map(|b| IpAddr::from(b).to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_certificate_extracts_dns_and_ip_sans() {
+        let cert =
+            rcgen::generate_simple_self_signed(vec!["agent.mesh.local".into(), "::1".into()])
+                .unwrap();
+        let peer = PeerCertificate::from_der(cert.cert.der().clone()).unwrap();
+        assert_eq!(peer.subject_alt_names, vec!["agent.mesh.local", "::1"]);
+        assert!(peer.has_san("agent.mesh.local"));
+        assert!(!peer.has_san("other.mesh.local"));
+    }
+
+    #[test]
+    fn client_auth_requires_a_ca() {
+        assert!(ClientAuth::None.build_verifier().unwrap().is_none());
+        assert!(ClientAuth::Required(Vec::new()).build_verifier().is_err());
+    }
+}
@@ -42,6 +42,11 @@ pub trait UnisonSend: AsyncWrite + Send + Unpin {
     fn finish(
         &mut self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<(), NetworkError>> + Send + '_>>;
+
+    /// ストリームを `code` で即座に打ち切る (= RESET_STREAM、 v1.0 で追加)。
+    ///
+    /// 未送信のデータは破棄される。 `finish` 同様、 閉じ済みならエラーにしない。
+    fn reset(&mut self, code: u32) -> Result<(), NetworkError>;
 }
 
 /// 受信ストリーム — `AsyncRead` + STOP 送出。
@@ -90,8 +95,18 @@ pub trait UnisonConn: Send + Sync {
     ) -> Pin<Box<dyn std::future::Future<Output = Result<bytes::Bytes, NetworkError>> + Send + '_>>;
 
     /// リモートのソケットアドレス。
+    ///
+    /// 現在の path のアドレスを返す (= client の path migration の後は新しいアドレス)。
     fn remote_address(&self) -> std::net::SocketAddr;
 
     /// 接続をクローズする (= アプリケーションレベルの close)。
     fn close(&self, code: u32, reason: &[u8]);
+
+    /// 接続が閉じているか (v1.0 で追加)。
+    ///
+    /// stream の読み込み失敗が接続断によるものか (= stream 単体の終了ではないか) の
+    /// 判定に使う。 判定できない transport は `false` を返す。
+    fn is_closed(&self) -> bool {
+        false
+    }
 }
@@ -28,6 +28,12 @@ impl UnisonSend for SendStream {
         };
         Box::pin(async move { result })
     }
+
+    fn reset(&mut self, code: u32) -> Result<(), NetworkError> {
+        match SendStream::reset(self, quinn::VarInt::from_u32(code)) {
+            Ok(()) | Err(quinn::ClosedStream { .. }) => Ok(()),
+        }
+    }
 }
 
 /// `quinn::RecvStream` を [`UnisonRecv`] として扱う。
@@ -92,4 +98,8 @@ impl UnisonConn for Connection {
     fn close(&self, code: u32, reason: &[u8]) {
         Connection::close(self, quinn::VarInt::from_u32(code), reason);
     }
+
+    fn is_closed(&self) -> bool {
+        self.close_reason().is_some()
+    }
 }
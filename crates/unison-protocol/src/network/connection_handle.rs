@@ -0,0 +1,186 @@
+//! ConnectionHandle: サーバー側から 1 接続を指定して操作するハンドル (v1.0 で追加)
+//!
+//! `ProtocolServer::broadcast` は全接続への fan-out しかできず、 active connection の
+//! map は `SocketAddr` で引いていたため NAT rebinding / 再接続で衝突し得た。
+//! v1.0 からは [`ConnectionId`] (= `ConnectionContext::connection_id`) で接続を引き、
+//! `ProtocolServer::connection(id)` が返す [`ConnectionHandle`] で datagram 送信 /
+//! channel open / close を行う。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+
+use crate::codec::{Codec, Encodable};
+
+use super::NetworkError;
+use super::channel::UnisonChannel;
+use super::channel_config::ChannelConfig;
+use super::conn::UnisonConn;
+use super::context::{ConnectionContext, ConnectionId};
+use super::dispatch::{open_channel_stream, send_goaway};
+use super::heartbeat::HeartbeatStats;
+use super::interceptor::{InterceptContext, InterceptorChain};
+use super::server::{DatagramHandlerEntry, Interceptors, encode_datagram};
+
+/// 接続相手の情報 (= ログ / 認可判断用のスナップショット)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// 接続 ID
+    pub connection_id: ConnectionId,
+    /// 現在のリモートアドレス
+    pub remote_addr: SocketAddr,
+    /// 接続確立時刻
+    pub connected_at: SystemTime,
+}
+
+/// サーバー側の 1 接続へのハンドル
+///
+/// `Clone` は安価 (= 内部は `Arc`)。 接続が切れた後も保持できるが、 送信系の
+/// メソッドはエラーを返す。
+#[derive(Clone)]
+pub struct ConnectionHandle {
+    connection: Arc<dyn UnisonConn>,
+    context: Arc<ConnectionContext>,
+    connected_at: SystemTime,
+    datagram_handlers: Arc<RwLock<HashMap<String, DatagramHandlerEntry>>>,
+    interceptors: Interceptors,
+}
+
+impl std::fmt::Debug for ConnectionHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionHandle")
+            .field("id", &self.id())
+            .field("remote_addr", &self.remote_address())
+            .finish()
+    }
+}
+
+impl ConnectionHandle {
+    pub(crate) fn new(
+        connection: Arc<dyn UnisonConn>,
+        context: Arc<ConnectionContext>,
+        datagram_handlers: Arc<RwLock<HashMap<String, DatagramHandlerEntry>>>,
+        interceptors: Interceptors,
+    ) -> Self {
+        Self {
+            connection,
+            context,
+            connected_at: SystemTime::now(),
+            datagram_handlers,
+            interceptors,
+        }
+    }
+
+    /// 接続 ID (= `ConnectionContext::connection_id`)
+    pub fn id(&self) -> ConnectionId {
+        self.context.connection_id
+    }
+
+    /// 現在のリモートアドレス
+    pub fn remote_address(&self) -> SocketAddr {
+        self.connection.remote_address()
+    }
+
+    /// 接続コンテキスト (= channel handler に渡されるものと同じ)
+    pub fn context(&self) -> &Arc<ConnectionContext> {
+        &self.context
+    }
+
+    /// 接続確立時刻
+    pub fn connected_at(&self) -> SystemTime {
+        self.connected_at
+    }
+
+    /// 接続相手の情報
+    pub fn peer_info(&self) -> PeerInfo {
+        PeerInfo {
+            connection_id: self.id(),
+            remote_addr: self.remote_address(),
+            connected_at: self.connected_at,
+        }
+    }
+
+    /// heartbeat の計測値（= `ProtocolServer::set_heartbeat` 設定時のみ更新される）
+    pub fn heartbeat_stats(&self) -> HeartbeatStats {
+        self.context.heartbeat_stats()
+    }
+
+    /// heartbeat で測った最新の RTT（未計測なら `None`）
+    pub fn rtt(&self) -> Option<Duration> {
+        self.context.rtt()
+    }
+
+    /// この接続にだけ datagram channel event を送る
+    ///
+    /// `channel_name` は `register_channel_datagram` で登録した名前。 datagram は
+    /// best-effort (= 到達保証なし)。
+    pub async fn send_datagram<T, C>(
+        &self,
+        channel_name: &str,
+        event: &T,
+    ) -> Result<(), NetworkError>
+    where
+        T: Encodable<C>,
+        C: Codec,
+    {
+        let payload = encode_datagram::<T, C>(&self.datagram_handlers, channel_name, event).await?;
+        self.send_raw_datagram(payload.into())
+    }
+
+    /// encode 済みの datagram をそのまま送る (= broadcast 用、 内部 API)
+    pub(crate) fn send_raw_datagram(&self, payload: bytes::Bytes) -> Result<(), NetworkError> {
+        self.connection.send_datagram(payload)
+    }
+
+    /// この接続に対してサーバー発信の channel を開く
+    pub async fn open_channel(&self, channel_name: &str) -> Result<UnisonChannel, NetworkError> {
+        self.open_channel_with_config(channel_name, ChannelConfig::default())
+            .await
+    }
+
+    /// 受信キュー設定を指定してサーバー発信の channel を開く
+    pub async fn open_channel_with_config(
+        &self,
+        channel_name: &str,
+        config: ChannelConfig,
+    ) -> Result<UnisonChannel, NetworkError> {
+        let mut stream = open_channel_stream(Arc::clone(&self.connection), channel_name).await?;
+        // サーバーの interceptor は発信側の channel の message にも掛かる
+        let intercept =
+            InterceptorChain::from_registered(&self.interceptors.read().await).map(|chain| {
+                let intercept_ctx = InterceptContext {
+                    connection: Arc::clone(&self.context),
+                    channel: channel_name.to_string(),
+                    stream_id: stream.stream_id(),
+                };
+                (chain, intercept_ctx)
+            });
+        if let Some((chain, intercept_ctx)) = &intercept {
+            stream = stream.with_interceptors(chain.clone(), intercept_ctx.clone());
+        }
+        let channel = UnisonChannel::with_config(stream, config);
+        // 受け側の channel と同じく、 閉じたら on_close を呼ぶ (= interceptor の per-channel 状態を片付ける)
+        if let Some((chain, intercept_ctx)) = intercept {
+            let closed = channel.shared().closed_token();
+            tokio::spawn(async move {
+                closed.cancelled().await;
+                chain.close(&intercept_ctx);
+            });
+        }
+        Ok(channel)
+    }
+
+    /// `__goaway` (= drain 開始通知) を送る
+    pub(crate) async fn send_goaway(&self, deadline: Duration) -> Result<(), NetworkError> {
+        send_goaway(&self.connection, deadline).await
+    }
+
+    /// 接続を閉じる (= QUIC application close、 code 0)
+    ///
+    /// `reason` は peer の close reason としてそのまま届く。
+    pub fn close(&self, reason: &str) {
+        self.connection.close(0, reason.as_bytes());
+    }
+}
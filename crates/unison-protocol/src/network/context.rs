@@ -3,22 +3,57 @@
 //! 各接続に対して、Identity情報とアクティブチャネルを追跡する。
 //! 複数のストリームハンドラーから並行アクセスされるため Arc<RwLock<>> で保護。
 
+use futures_util::FutureExt;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use super::identity::{ChannelDirection, ServerIdentity};
+use super::auth::Principal;
+use super::client_auth::PeerCertificate;
+use super::heartbeat::HeartbeatStats;
+use super::identity::{ChannelDirection, ChannelUpdate, ServerIdentity};
+
+/// 接続の一意識別子 (v1.0 で型名を付与)
+///
+/// `ConnectionContext::connection_id` そのもの。 サーバー側では
+/// `ProtocolServer::connection(id)` のキーになる。
+pub type ConnectionId = Uuid;
 
 /// 接続ごとの状態を管理する構造体
 #[derive(Debug)]
 pub struct ConnectionContext {
     /// 接続の一意識別子
-    pub connection_id: Uuid,
+    pub connection_id: ConnectionId,
     /// サーバーから受信したIdentity情報
     identity: Arc<RwLock<Option<ServerIdentity>>>,
     /// アクティブなチャネルのマップ（チャネル名 → ハンドル）
     channels: Arc<RwLock<HashMap<String, ChannelHandle>>>,
+    /// `__auth` で認証された相手 (v1.0 で追加、 接続中に 1 度だけ設定される)
+    principal: OnceLock<Principal>,
+    /// `__auth` を受け付けたか (v1.0 で追加、 認証の試行は接続ごとに 1 回だけ)
+    auth_attempted: AtomicBool,
+    /// TLS handshake で検証した client cert (v1.0 で追加、 mutual TLS の QUIC 接続のみ)
+    peer_certificate: OnceLock<PeerCertificate>,
+    /// 相手のアドレス (v1.0 で追加、 サーバー側の接続のみ、 migration で更新される)
+    remote_addr: std::sync::RwLock<Option<SocketAddr>>,
+    /// アプリが付与した role (v1.0 で追加、 channel のアクセスポリシーで参照)
+    assigned_roles: std::sync::RwLock<Vec<String>>,
+    /// heartbeat の計測値 (v1.0 で追加、 この側が heartbeat を送る場合のみ更新される)
+    heartbeat: std::sync::RwLock<HeartbeatStats>,
+    /// 0-RTT で受け入れた接続の handshake 完了通知 (v1.0 で追加、 完了後は `None`)
+    early_data: std::sync::Mutex<Option<EarlyData>>,
+}
+
+/// 0-RTT の handshake 完了通知 (= `quinn::ZeroRttAccepted` は `Debug` を持たない)
+struct EarlyData(quinn::ZeroRttAccepted);
+
+impl std::fmt::Debug for EarlyData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EarlyData(pending)")
+    }
 }
 
 /// チャネルのメタデータ
@@ -36,6 +71,13 @@ impl ConnectionContext {
             connection_id: Uuid::new_v4(),
             identity: Arc::new(RwLock::new(None)),
             channels: Arc::new(RwLock::new(HashMap::new())),
+            principal: OnceLock::new(),
+            auth_attempted: AtomicBool::new(false),
+            peer_certificate: OnceLock::new(),
+            remote_addr: std::sync::RwLock::new(None),
+            assigned_roles: std::sync::RwLock::new(Vec::new()),
+            heartbeat: std::sync::RwLock::new(HeartbeatStats::default()),
+            early_data: std::sync::Mutex::new(None),
         }
     }
 
@@ -45,11 +87,149 @@ impl ConnectionContext {
         *guard = Some(identity);
     }
 
+    /// Identity のチャネル一覧にチャネル更新を反映する（v1.0 で追加、 Identity 未受信なら無視）
+    pub(crate) async fn apply_channel_update(&self, update: &ChannelUpdate) {
+        if let Some(identity) = self.identity.write().await.as_mut() {
+            identity.apply(update);
+        }
+    }
+
     /// Identity情報を取得
     pub async fn identity(&self) -> Option<ServerIdentity> {
         self.identity.read().await.clone()
     }
 
+    /// 認証済みの相手（v1.0 で追加、 未認証なら `None`）
+    ///
+    /// サーバー側では client の principal、 クライアント側ではサーバーが認めた自分の
+    /// principal。
+    pub fn principal(&self) -> Option<&Principal> {
+        self.principal.get()
+    }
+
+    /// principal を設定する (= 既に設定済みなら `false`)
+    pub(crate) fn set_principal(&self, principal: Principal) -> bool {
+        self.principal.set(principal).is_ok()
+    }
+
+    /// 認証の試行を始める (= この接続で 2 回目以降なら `false`)
+    pub(crate) fn begin_auth(&self) -> bool {
+        !self.auth_attempted.swap(true, Ordering::SeqCst)
+    }
+
+    /// handshake が完了しているか（v1.0 で追加）
+    ///
+    /// サーバー側で 0-RTT のまま受け入れた接続は、 handshake が完了するまで `false`。
+    /// それまでに届いたデータは第三者の replay かもしれない。 0-RTT 以外の接続は常に `true`。
+    pub fn is_handshake_confirmed(&self) -> bool {
+        let mut early_data = self.early_data.lock().expect("early_data lock poisoned");
+        let Some(accepted) = early_data.as_mut() else {
+            return true;
+        };
+        // 接続の driver は handshake の完了を stream のデータより先に通知する
+        if (&mut accepted.0).now_or_never().is_none() {
+            return false;
+        }
+        *early_data = None;
+        true
+    }
+
+    /// 0-RTT で受け入れた接続の handshake 完了通知を設定する
+    pub(crate) fn set_early_data(&self, accepted: quinn::ZeroRttAccepted) {
+        *self.early_data.lock().expect("early_data lock poisoned") = Some(EarlyData(accepted));
+    }
+
+    /// 相手が提示した検証済みの client cert（v1.0 で追加）
+    ///
+    /// サーバー側で `ClientAuth::Optional` / `Required` の QUIC 接続にのみ入る。 SAN を
+    /// 認可判断に使える。
+    pub fn peer_certificate(&self) -> Option<&PeerCertificate> {
+        self.peer_certificate.get()
+    }
+
+    /// client cert を設定する (= 既に設定済みなら `false`)
+    pub(crate) fn set_peer_certificate(&self, cert: PeerCertificate) -> bool {
+        self.peer_certificate.set(cert).is_ok()
+    }
+
+    /// 相手のアドレス（v1.0 で追加）
+    ///
+    /// サーバー側で受けた接続にのみ入る。 `allow-from` ポリシーの判定に使う。 client の
+    /// path migration (= NAT rebinding 等) の後は新しいアドレスを返す。
+    pub fn remote_addr(&self) -> Option<SocketAddr> {
+        *self.remote_addr.read().expect("remote_addr lock poisoned")
+    }
+
+    /// 相手のアドレスを設定し、 直前のアドレスを返す
+    pub(crate) fn set_remote_addr(&self, addr: SocketAddr) -> Option<SocketAddr> {
+        self.remote_addr
+            .write()
+            .expect("remote_addr lock poisoned")
+            .replace(addr)
+    }
+
+    /// 接続に role を付与する（v1.0 で追加）
+    ///
+    /// principal の roles に加えて `require-role` ポリシーの判定に使われる。
+    /// interceptor や先に開いた channel の handler から、 以降の channel open を許可するのに使う。
+    pub fn assign_role(&self, role: impl Into<String>) {
+        let role = role.into();
+        let mut roles = self.assigned_roles.write().expect("roles lock poisoned");
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+
+    /// 接続が持つ role（v1.0 で追加、 principal の roles + [`Self::assign_role`] で付与した分）
+    pub fn roles(&self) -> Vec<String> {
+        let mut roles: Vec<String> = self
+            .principal()
+            .map(|p| p.roles.clone())
+            .unwrap_or_default();
+        for role in self
+            .assigned_roles
+            .read()
+            .expect("roles lock poisoned")
+            .iter()
+        {
+            if !roles.contains(role) {
+                roles.push(role.clone());
+            }
+        }
+        roles
+    }
+
+    /// 接続が `role` を持つか（v1.0 で追加）
+    pub fn has_role(&self, role: &str) -> bool {
+        self.principal().is_some_and(|p| p.has_role(role))
+            || self
+                .assigned_roles
+                .read()
+                .expect("roles lock poisoned")
+                .iter()
+                .any(|r| r == role)
+    }
+
+    /// heartbeat の計測値（v1.0 で追加）
+    ///
+    /// この側が heartbeat を送っていなければ初期値のまま (= `rtt` は `None`)。
+    pub fn heartbeat_stats(&self) -> HeartbeatStats {
+        self.heartbeat
+            .read()
+            .expect("heartbeat lock poisoned")
+            .clone()
+    }
+
+    /// heartbeat で測った最新の RTT（v1.0 で追加、 未計測なら `None`）
+    pub fn rtt(&self) -> Option<std::time::Duration> {
+        self.heartbeat.read().expect("heartbeat lock poisoned").rtt
+    }
+
+    /// heartbeat の計測値を更新する
+    pub(crate) fn update_heartbeat_stats(&self, f: impl FnOnce(&mut HeartbeatStats)) {
+        f(&mut self.heartbeat.write().expect("heartbeat lock poisoned"));
+    }
+
     /// チャネルを登録
     pub async fn register_channel(&self, handle: ChannelHandle) {
         let mut channels = self.channels.write().await;
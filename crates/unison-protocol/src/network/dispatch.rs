@@ -3,32 +3,73 @@
 //! raw QUIC と WebTransport の両 ingress が [`handle_connection`] へ収束する。
 //! クライアント側でサーバー発信ストリームを捌くループ ([`client_accept_bi_loop`])
 //! もここに置く。
+//!
+//! channel open の handshake (= `__channel:{name}` → `__channel_ack`) は開く側
+//! ([`open_channel_stream`]) と受ける側 ([`serve_channel_open`]) に分けてあり、
+//! v1.0 以降はサーバー発信の channel でも同じ関数を使う。
 
 use anyhow::Result;
+use futures_util::FutureExt;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
-use tokio::sync::{Mutex, mpsc, oneshot};
+use std::time::Duration;
+use tokio::sync::{Mutex, broadcast, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
-use super::conn::UnisonConn;
-use super::frame::{FRAME_TYPE_PROTOCOL, read_typed_frame, write_channel_ack, write_typed_frame};
+use super::auth::{AUTH_TIMEOUT, serve_auth};
+use super::conn::{BoxUnisonRecv, BoxUnisonSend, UnisonConn};
+use super::frame::{
+    AUTH_METHOD, CHANNEL_ACK_METHOD, CHANNEL_ERROR_METHOD, CHANNEL_UPDATE_METHOD,
+    FRAME_TYPE_PROTOCOL, GOAWAY_METHOD, HEARTBEAT_METHOD, NACK_CHANNEL_NOT_FOUND, NACK_EARLY_DATA,
+    NACK_FORBIDDEN, NACK_SERVER_DRAINING, NACK_UNAUTHENTICATED, RESET_HANDLER_PANICKED,
+    read_typed_frame, write_channel_ack, write_channel_ack_with, write_typed_frame,
+};
+use super::heartbeat::{
+    AbortOnDrop, HEARTBEAT_TIMEOUT_REASON, HeartbeatOutcome, run_heartbeat, serve_heartbeat,
+};
+use super::identity::{ChannelUpdate, ServerIdentity};
+use super::interceptor::{InterceptContext, InterceptorChain};
+use super::policy::PolicyViolation;
+use super::server::{ChannelHandler, ChannelHandlerMap};
 use super::stream::UnisonStream;
-use super::{ProtocolFrame, ProtocolMessage, context::ConnectionContext, server::ProtocolServer};
+use super::{
+    MessageType, NetworkError, ProtocolError, ProtocolFrame, ProtocolMessage,
+    context::ConnectionContext, generate_request_id, server::ProtocolServer,
+};
+
+/// 接続のアドレスが変わっていないか (= client の path migration) を確かめる間隔 (v1.0 で追加)
+const PATH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// クライアント側: サーバー発信の双方向ストリームを受け付けるループ
 ///
 /// サーバーが `connection.open_bi()` で開いたストリーム（Identity 送信等）を
 /// `accept_bi()` で受信し、ProtocolMessage に変換する。
-/// `__identity` メッセージは専用の oneshot チャネルに送り、それ以外は既存の mpsc に送る。
+/// - `__identity` メッセージは専用の oneshot チャネルに送り、 同じ stream に続く
+///   `__channel_update` を `ctx` に反映して `channel_update_tx` に流す (v1.0 で追加)
+/// - `__channel:{name}` は `handlers` に登録された channel handler に渡す
+///   (= サーバー発信 channel、 v1.0 で追加)
+/// - `__goaway` は `deadline_ms` を `goaway_tx` に流す (v1.0 で追加)
+/// - `__heartbeat` は同じ stream へ pong を返し続ける (v1.0 で追加)
+/// - それ以外は既存の mpsc に送る
 pub(crate) async fn client_accept_bi_loop(
     connection: quinn::Connection,
     tx: mpsc::UnboundedSender<ProtocolMessage>,
     identity_tx: Arc<Mutex<Option<oneshot::Sender<ProtocolMessage>>>>,
+    goaway_tx: broadcast::Sender<Duration>,
+    channel_update_tx: broadcast::Sender<ChannelUpdate>,
+    handlers: ChannelHandlerMap,
+    ctx: Arc<ConnectionContext>,
 ) {
     loop {
         match connection.accept_bi().await {
-            Ok((_send_stream, mut recv_stream)) => {
+            Ok((send_stream, mut recv_stream)) => {
                 let tx = tx.clone();
                 let identity_tx = identity_tx.clone();
+                let goaway_tx = goaway_tx.clone();
+                let channel_update_tx = channel_update_tx.clone();
+                let handlers = Arc::clone(&handlers);
+                let ctx = Arc::clone(&ctx);
+                let conn: Arc<dyn UnisonConn> = Arc::new(connection.clone());
                 tokio::spawn(async move {
                     match read_typed_frame(&mut recv_stream).await {
                         Ok((FRAME_TYPE_PROTOCOL, frame_bytes)) => {
@@ -36,14 +77,46 @@ pub(crate) async fn client_accept_bi_loop(
                                 && let Ok(message) = ProtocolMessage::from_frame(&frame)
                             {
                                 if message.method == "__identity" {
-                                    // Identity メッセージは専用 oneshot チャネルに送信
-                                    if let Some(id_tx) = identity_tx.lock().await.take() {
-                                        let _ = id_tx.send(message);
-                                    } else {
-                                        warn!(
-                                            "Identity oneshot already consumed, dropping identity message"
-                                        );
-                                    }
+                                    // v1.0: 同じ stream に続くチャネル更新も読み続ける
+                                    serve_identity_stream(
+                                        message,
+                                        recv_stream,
+                                        identity_tx,
+                                        channel_update_tx,
+                                        ctx,
+                                    )
+                                    .await;
+                                } else if message.method == GOAWAY_METHOD {
+                                    let deadline_ms = message
+                                        .payload_as_value()
+                                        .ok()
+                                        .and_then(|v| v["deadline_ms"].as_u64())
+                                        .unwrap_or(0);
+                                    info!("Server is draining (deadline {}ms)", deadline_ms);
+                                    let _ = goaway_tx.send(Duration::from_millis(deadline_ms));
+                                } else if message.method == HEARTBEAT_METHOD {
+                                    // v1.0: サーバーの heartbeat に pong を返す
+                                    serve_heartbeat(
+                                        message,
+                                        Box::new(send_stream),
+                                        Box::new(recv_stream),
+                                    )
+                                    .await;
+                                } else if let Some(channel_name) =
+                                    message.method.strip_prefix("__channel:")
+                                {
+                                    let handler = handlers.read().await.get(channel_name).cloned();
+                                    // クライアント側は log のみ (= 集計は server の責務)
+                                    let _ = serve_channel_open(
+                                        message,
+                                        handler,
+                                        conn,
+                                        Box::new(send_stream),
+                                        Box::new(recv_stream),
+                                        ctx,
+                                        None,
+                                    )
+                                    .await;
                                 } else {
                                     // それ以外は既存の mpsc チャネルに送信
                                     let _ = tx.send(message);
@@ -74,6 +147,71 @@ pub(crate) async fn client_accept_bi_loop(
     }
 }
 
+/// クライアント側: Identity stream を読む (v1.0 で追加)
+///
+/// 最初の `__identity` を `ctx` に設定してから oneshot へ渡す。 サーバーは stream を
+/// 閉じずにチャネル更新を push してくるので、 `__channel_update` は `ctx` の Identity に
+/// 反映して `channel_update_tx` へ流し、 再送された `__identity` (= サーバー側で更新を
+/// 取りこぼした時) は Identity を丸ごと置き換える。
+async fn serve_identity_stream(
+    message: ProtocolMessage,
+    mut recv_stream: quinn::RecvStream,
+    identity_tx: Arc<Mutex<Option<oneshot::Sender<ProtocolMessage>>>>,
+    channel_update_tx: broadcast::Sender<ChannelUpdate>,
+    ctx: Arc<ConnectionContext>,
+) {
+    // oneshot より先に設定し、 受け取った側が `server_identity()` を読めるようにする
+    match ServerIdentity::from_protocol_message(&message) {
+        Ok(identity) => ctx.set_identity(identity).await,
+        Err(e) => warn!("Failed to parse identity: {}", e),
+    }
+    // Identity メッセージは専用 oneshot チャネルに送信
+    if let Some(id_tx) = identity_tx.lock().await.take() {
+        let _ = id_tx.send(message);
+    } else {
+        warn!("Identity oneshot already consumed, dropping identity message");
+    }
+
+    loop {
+        let frame_bytes = match read_typed_frame(&mut recv_stream).await {
+            Ok((FRAME_TYPE_PROTOCOL, frame_bytes)) => frame_bytes,
+            Ok((frame_type, _)) => {
+                warn!(
+                    "Unexpected frame type in identity stream: 0x{:02x}",
+                    frame_type
+                );
+                continue;
+            }
+            // サーバーの finish / 接続終了
+            Err(_) => break,
+        };
+        let Ok(message) =
+            ProtocolFrame::from_bytes(&frame_bytes).and_then(|f| ProtocolMessage::from_frame(&f))
+        else {
+            warn!("Failed to parse identity stream frame");
+            continue;
+        };
+        match message.method.as_str() {
+            CHANNEL_UPDATE_METHOD => match ChannelUpdate::from_protocol_message(&message) {
+                Ok(update) => {
+                    debug!("Received channel update: {:?}", update);
+                    ctx.apply_channel_update(&update).await;
+                    let _ = channel_update_tx.send(update);
+                }
+                Err(e) => warn!("Failed to parse channel update: {}", e),
+            },
+            "__identity" => match ServerIdentity::from_protocol_message(&message) {
+                Ok(identity) => {
+                    debug!("Received identity resync");
+                    ctx.set_identity(identity).await;
+                }
+                Err(e) => warn!("Failed to parse identity: {}", e),
+            },
+            other => warn!("Unexpected message in identity stream: {}", other),
+        }
+    }
+}
+
 /// transport 非依存の接続ハンドラー。
 ///
 /// raw QUIC と WebTransport の両 ingress がこの関数へ収束する。 `connection` は
@@ -84,11 +222,15 @@ pub(crate) async fn handle_connection(
     ctx: Arc<ConnectionContext>,
 ) -> Result<()> {
     let remote_addr = connection.remote_address();
+    // v1.0: `allow-from` ポリシーの判定用 (= migration の後は `observe_path` で更新)
+    ctx.set_remote_addr(remote_addr);
 
     // v0.10.0: active connection に登録 (= server.broadcast の配信先)
+    // v1.0: key は connection_id (= `ProtocolServer::connection(id)` で引ける)
     let connection_arc = Arc::clone(&connection);
+    let connection_id = ctx.connection_id;
     server
-        .add_active_connection(remote_addr, Arc::clone(&connection_arc))
+        .add_active_connection(Arc::clone(&connection_arc), Arc::clone(&ctx))
         .await;
 
     // v0.10.0: datagram dispatcher を 1 connection に 1 個 spawn
@@ -117,9 +259,12 @@ pub(crate) async fn handle_connection(
     };
 
     // Identity Handshake: 接続直後にServerIdentityを送信
+    // v1.0: build より先に購読し、 間に起きたチャネル更新を取りこぼさない
+    let channel_updates = server.subscribe_channel_updates();
     let identity = server.build_identity().await;
     ctx.set_identity(identity.clone()).await;
 
+    let mut _identity_stream = None;
     let identity_msg = identity.to_protocol_message();
     match identity_msg.into_frame() {
         Ok(frame) => {
@@ -131,8 +276,13 @@ pub(crate) async fn handle_connection(
                     {
                         warn!("Failed to send identity: {}", e);
                     } else {
-                        let _ = send_stream.finish().await;
                         info!("Identity sent to client");
+                        // v1.0: stream は閉じずに、 以降のチャネル更新を同じ stream で push する
+                        _identity_stream = Some(AbortOnDrop(tokio::spawn(push_channel_updates(
+                            send_stream,
+                            channel_updates,
+                            Arc::clone(&server),
+                        ))));
                     }
                 }
                 Err(e) => {
@@ -146,16 +296,74 @@ pub(crate) async fn handle_connection(
         }
     }
 
+    // v1.0: 認証必須のサーバーは、 期限内に `__auth` を済ませない接続を閉じる
+    if server.authenticator().await.is_some() {
+        let connection = Arc::clone(&connection);
+        let ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            tokio::time::sleep(AUTH_TIMEOUT).await;
+            if ctx.principal().is_none() {
+                warn!(
+                    "Connection {} did not authenticate in time",
+                    ctx.connection_id
+                );
+                connection.close(0, b"authentication timeout");
+            }
+        });
+    }
+
     // 接続イベントを送信
     server.emit_connection_event(super::server::ConnectionEvent::Connected {
         remote_addr,
         context: Arc::clone(&ctx),
     });
 
+    // v1.0: heartbeat を設定したサーバーは接続ごとに ping を送り、 dead なら閉じる
+    let heartbeat_config = server.heartbeat().await;
+    let mut heartbeat_running = heartbeat_config.is_some();
+    let heartbeat = {
+        let connection = Arc::clone(&connection);
+        let ctx = Arc::clone(&ctx);
+        async move {
+            match heartbeat_config {
+                Some(config) => run_heartbeat(connection, config, ctx).await,
+                None => std::future::pending().await,
+            }
+        }
+    };
+    tokio::pin!(heartbeat);
+
+    // v1.0: quinn は path の変化を通知しないので、 定期的に見て `PathChanged` を出す
+    let mut path_check = tokio::time::interval(PATH_CHECK_INTERVAL);
+    path_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
     loop {
         let connection_clone = Arc::clone(&connection);
-        match connection.accept_bi().await {
+        let accepted = tokio::select! {
+            accepted = connection.accept_bi() => accepted,
+            _ = path_check.tick() => {
+                observe_path(connection.as_ref(), &ctx, &server);
+                continue;
+            }
+            outcome = &mut heartbeat, if heartbeat_running => {
+                heartbeat_running = false;
+                if outcome == HeartbeatOutcome::Dead {
+                    warn!("Connection {} missed heartbeats, closing", connection_id);
+                    connection.close(0, HEARTBEAT_TIMEOUT_REASON.as_bytes());
+                    server.emit_connection_event(super::server::ConnectionEvent::Disconnected {
+                        connection_id,
+                        remote_addr: observe_path(connection.as_ref(), &ctx, &server),
+                        reason: HEARTBEAT_TIMEOUT_REASON.to_string(),
+                    });
+                    break;
+                }
+                continue;
+            }
+        };
+        match accepted {
             Ok((send_stream, mut recv_stream)) => {
+                // `allow-from` の判定が migration 後のアドレスを見るように
+                observe_path(connection.as_ref(), &ctx, &server);
                 let server = Arc::clone(&server);
                 let connection = connection_clone;
                 let ctx = Arc::clone(&ctx);
@@ -181,84 +389,88 @@ pub(crate) async fn handle_connection(
                         Ok(request) => {
                             // チャネルルーティング: __channel: プレフィックスをチェック
                             if let Some(channel_name) = request.method.strip_prefix("__channel:") {
-                                let channel_name = channel_name.to_string();
-                                let mut send_stream = send_stream;
-                                if let Some(handler) =
-                                    server.get_channel_handler(&channel_name).await
-                                {
-                                    // channel lifecycle の "open" 側ログ。
-                                    // close 側 (= 下記の debug!) と対になり、 1 接続中の
-                                    // channel 開閉 trace が debug level で揃う。
-                                    // info level にしない理由: 1 接続で channel が頻繁に
-                                    // open/close される設計 (= 1 request/response = 1 channel)
-                                    // なので info noise になりがち。
-                                    debug!("Channel '{}' opened", channel_name);
-
-                                    // Phase 6c: open frame と同 stream へ open_ack
-                                    // (= Response) を 1 本返す。 id は open request の
-                                    // id を引き継ぎ、 クライアントが相関できるようにする。
-                                    if let Err(e) = write_channel_ack(
-                                        &mut send_stream,
-                                        request.id,
-                                        true,
-                                        &channel_name,
+                                // v1.0: drain 中は新しい channel を受け付けない
+                                if server.is_draining() {
+                                    reject_channel_open(
+                                        &request,
+                                        send_stream,
+                                        NACK_SERVER_DRAINING,
                                     )
-                                    .await
-                                    {
-                                        warn!(
-                                            "Failed to send open_ack for '{}': {}",
-                                            channel_name, e
-                                        );
-                                        return;
-                                    }
-
-                                    // チャネル用のUnisonStreamを作成（ストリームは生きたまま）
-                                    let stream = UnisonStream::from_streams(
-                                        request.id,
-                                        request.method.clone(),
-                                        connection,
+                                    .await;
+                                    return;
+                                }
+                                // v1.0: 0-RTT のデータは replay され得るので、 replay-safe な
+                                // channel 以外は handshake の完了まで受け付けない
+                                if !ctx.is_handshake_confirmed()
+                                    && !server.accepts_early_open(channel_name).await
+                                {
+                                    reject_channel_open(&request, send_stream, NACK_EARLY_DATA)
+                                        .await;
+                                    return;
+                                }
+                                // v1.0: 認証必須なら `__auth` が済むまで受け付けない
+                                if ctx.principal().is_none()
+                                    && server.authenticator().await.is_some()
+                                {
+                                    reject_channel_open(
+                                        &request,
                                         send_stream,
-                                        recv_stream,
+                                        NACK_UNAUTHENTICATED,
+                                    )
+                                    .await;
+                                    return;
+                                }
+                                // v1.0: channel のアクセスポリシー (= 満たさなければ forbidden)
+                                if let Some(policy) = server.channel_policy(channel_name).await
+                                    && let Err(violation) = policy.check(&ctx)
+                                {
+                                    info!(
+                                        "Channel '{}' open forbidden for {}: {}",
+                                        channel_name, ctx.connection_id, violation
                                     );
-                                    if let Err(e) = handler(ctx, stream).await {
-                                        // sender 側が request/response 完了後に正常 close した
-                                        // end-of-stream は real error ではないので debug level に
-                                        // degrade。 これにより毎 channel session の終端で発生する
-                                        // ERROR log noise (= journal で大半を占める) を抑制。
-                                        if e.is_normal_close() {
-                                            debug!(
-                                                "Channel '{}' closed normally (end of stream)",
-                                                channel_name
-                                            );
-                                        } else {
-                                            error!(
-                                                "Channel handler error for '{}': {}",
-                                                channel_name, e
-                                            );
-                                        }
-                                    }
-                                } else {
-                                    // Phase 6c: 未登録 channel への open は nack
-                                    // (= Error frame) を返してから stream を畳む。
-                                    // これによりクライアントの open は silent に
-                                    // hang せず channel-not-found で即 reject する。
-                                    warn!("No channel handler for: {}", channel_name);
-                                    if let Err(e) = write_channel_ack(
-                                        &mut send_stream,
-                                        request.id,
-                                        false,
-                                        &channel_name,
+                                    reject_channel_open_forbidden(
+                                        &request,
+                                        send_stream,
+                                        &violation,
                                     )
-                                    .await
-                                    {
-                                        warn!(
-                                            "Failed to send open nack for '{}': {}",
-                                            channel_name, e
-                                        );
-                                    } else {
-                                        let _ = send_stream.finish().await;
-                                    }
+                                    .await;
+                                    return;
                                 }
+                                // graceful shutdown は handler の完了を待つ
+                                let _inflight = server.track_handler();
+                                let channel_name = channel_name.to_string();
+                                let handler = server.get_channel_handler(&channel_name).await;
+                                let interceptors = server.interceptor_chain().await;
+                                let connection_id = ctx.connection_id;
+                                let outcome = serve_channel_open(
+                                    request,
+                                    handler,
+                                    connection,
+                                    send_stream,
+                                    recv_stream,
+                                    ctx,
+                                    interceptors,
+                                )
+                                .await;
+                                server.record_handler_outcome(
+                                    connection_id,
+                                    &channel_name,
+                                    outcome,
+                                );
+                                return;
+                            }
+
+                            // v1.0: クライアントの heartbeat に pong を返す
+                            if request.method == HEARTBEAT_METHOD {
+                                serve_heartbeat(request, send_stream, recv_stream).await;
+                                return;
+                            }
+
+                            // v1.0: 接続認証
+                            if request.method == AUTH_METHOD {
+                                let authenticator = server.authenticator().await;
+                                serve_auth(authenticator, &ctx, request, send_stream, recv_stream)
+                                    .await;
                                 return;
                             }
 
@@ -279,7 +491,9 @@ pub(crate) async fn handle_connection(
                 // transport を問わず接続ループを抜ける。
                 info!("Connection closed ({}), client disconnected", e);
                 server.emit_connection_event(super::server::ConnectionEvent::Disconnected {
-                    remote_addr,
+                    connection_id,
+                    remote_addr: observe_path(connection.as_ref(), &ctx, &server),
+                    reason: e.to_string(),
                 });
                 break;
             }
@@ -288,8 +502,390 @@ pub(crate) async fn handle_connection(
 
     // v0.10.0: connection 終了時に active_connections から remove
     // (= broadcast 配信先から自動除外、 datagram dispatcher は _datagram_dispatcher 変数の
-    // scope-exit drop で同時に abort される)
-    server.remove_active_connection(remote_addr).await;
+    // scope-exit drop で同時に abort される。 チャネル更新の push task も _identity_stream の
+    // drop で止まる)
+    server.remove_active_connection(connection_id).await;
 
     Ok(())
 }
+
+/// Identity stream にチャネル更新を push し続ける (v1.0 で追加)
+///
+/// 購読が溢れた (= `Lagged`) 場合は取りこぼした差分の代わりに最新の Identity を
+/// 送り直し、 クライアントに丸ごと置き換えさせる。
+async fn push_channel_updates(
+    mut send_stream: BoxUnisonSend,
+    mut updates: broadcast::Receiver<ChannelUpdate>,
+    server: Arc<ProtocolServer>,
+) {
+    loop {
+        let message = match updates.recv().await {
+            Ok(update) => update.to_protocol_message(),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Skipped {} channel updates, resending identity", skipped);
+                server.build_identity().await.to_protocol_message()
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let frame_bytes = match message.into_frame() {
+            Ok(frame) => frame.to_bytes(),
+            Err(e) => {
+                warn!("Failed to serialize channel update: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = write_typed_frame(&mut send_stream, FRAME_TYPE_PROTOCOL, &frame_bytes).await
+        {
+            debug!("Identity stream closed: {}", e);
+            return;
+        }
+    }
+    let _ = send_stream.finish().await;
+}
+
+/// 接続の現在のアドレスを記録し、 変わっていれば `PathChanged` を出す (v1.0 で追加)
+///
+/// 前のアドレスとの入れ替えは lock の中で行うので、 同時に呼ばれても event は 1 回。
+fn observe_path(
+    connection: &dyn UnisonConn,
+    ctx: &ConnectionContext,
+    server: &ProtocolServer,
+) -> std::net::SocketAddr {
+    let current = connection.remote_address();
+    if let Some(old) = ctx.set_remote_addr(current)
+        && old != current
+    {
+        info!(
+            "Connection {} migrated from {} to {}",
+            ctx.connection_id, old, current
+        );
+        server.emit_connection_event(super::server::ConnectionEvent::PathChanged {
+            connection_id: ctx.connection_id,
+            old,
+            new: current,
+        });
+    }
+    current
+}
+
+/// `__channel:{name}` の open を受けた stream を handler に渡す (Phase 6c)
+///
+/// サーバー ([`handle_connection`]) とクライアント ([`client_accept_bi_loop`]、 v1.0 で
+/// 追加) の両方が使う。 handler があれば `open_ack` を返してから handler を実行し、
+/// 無ければ nack を返して stream を畳む。 handler の終了まで戻らない。
+///
+/// サーバー側では `interceptors` が open を検査し、 stream の送受信に掛かる (v1.0 で追加)。
+/// handler の panic は捕まえて [`CHANNEL_ERROR_METHOD`] の Error frame を送り、 stream を
+/// [`RESET_HANDLER_PANICKED`] で reset する (v1.0 で追加)。
+pub(crate) async fn serve_channel_open(
+    request: ProtocolMessage,
+    handler: Option<ChannelHandler>,
+    connection: Arc<dyn UnisonConn>,
+    mut send_stream: BoxUnisonSend,
+    recv_stream: BoxUnisonRecv,
+    ctx: Arc<ConnectionContext>,
+    interceptors: Option<InterceptorChain>,
+) -> HandlerOutcome {
+    let channel_name = request
+        .method
+        .strip_prefix("__channel:")
+        .unwrap_or(&request.method)
+        .to_string();
+    let Some(handler) = handler else {
+        // Phase 6c: 未登録 channel への open は nack (= Error frame) を返してから
+        // stream を畳む。 これにより open 側は silent に hang せず
+        // channel-not-found で即 reject する。
+        warn!("No channel handler for: {}", channel_name);
+        reject_channel_open(&request, send_stream, NACK_CHANNEL_NOT_FOUND).await;
+        return HandlerOutcome::NotStarted;
+    };
+
+    // v1.0: interceptor は open_ack の前に open を検査できる (= 拒否理由は nack に載る)
+    let intercept = interceptors.map(|chain| {
+        let intercept_ctx = InterceptContext {
+            connection: Arc::clone(&ctx),
+            channel: channel_name.clone(),
+            stream_id: request.id,
+        };
+        (chain, intercept_ctx)
+    });
+    if let Some((chain, intercept_ctx)) = &intercept
+        && let Err(reason) = chain.check_open(intercept_ctx).await
+    {
+        info!("Channel '{}' open rejected: {}", channel_name, reason);
+        reject_channel_open(&request, send_stream, &reason).await;
+        return HandlerOutcome::NotStarted;
+    }
+
+    // channel lifecycle の "open" 側ログ。
+    // close 側 (= 下記の debug!) と対になり、 1 接続中の channel 開閉 trace が
+    // debug level で揃う。 info level にしない理由: 1 接続で channel が頻繁に
+    // open/close される設計 (= 1 request/response = 1 channel) なので info noise
+    // になりがち。
+    debug!("Channel '{}' opened", channel_name);
+
+    // Phase 6c: open frame と同 stream へ open_ack (= Response) を 1 本返す。
+    // id は open request の id を引き継ぎ、 open 側が相関できるようにする。
+    if let Err(e) = write_channel_ack(&mut send_stream, request.id, None, &channel_name).await {
+        warn!("Failed to send open_ack for '{}': {}", channel_name, e);
+        return HandlerOutcome::NotStarted;
+    }
+
+    // チャネル用のUnisonStreamを作成（ストリームは生きたまま）
+    let mut stream = UnisonStream::from_streams(
+        request.id,
+        request.method.clone(),
+        connection,
+        send_stream,
+        recv_stream,
+    );
+    if let Some((chain, intercept_ctx)) = &intercept {
+        stream = stream.with_interceptors(chain.clone(), intercept_ctx.clone());
+    }
+    // v1.0: handler の panic は呼び出し単位で捕まえる。 stream は handler に move
+    // されるので、 後始末用に送信側だけ手元に残しておく。
+    let abort = stream.abort_handle();
+    let result = AssertUnwindSafe(async move { handler(ctx, stream).await })
+        .catch_unwind()
+        .await;
+    let outcome = match result {
+        Ok(Ok(())) => HandlerOutcome::Completed,
+        // sender 側が request/response 完了後に正常 close した end-of-stream は
+        // real error ではないので debug level に degrade。 これにより毎 channel
+        // session の終端で発生する ERROR log noise (= journal で大半を占める) を抑制。
+        Ok(Err(e)) if e.is_normal_close() => {
+            debug!("Channel '{}' closed normally (end of stream)", channel_name);
+            HandlerOutcome::Completed
+        }
+        Ok(Err(e)) => {
+            error!("Channel handler error for '{}': {}", channel_name, e);
+            HandlerOutcome::Failed(e)
+        }
+        Err(panic) => {
+            let reason = panic_message(panic.as_ref());
+            error!(
+                "Channel handler for '{}' panicked: {}",
+                channel_name, reason
+            );
+            // peer が stalled stream で待ち続けないよう、 エラー frame + reset で畳む
+            match channel_error_message(&channel_name) {
+                Ok(msg) => abort.abort(&msg, RESET_HANDLER_PANICKED).await,
+                Err(e) => warn!(
+                    "Failed to build channel error for '{}': {}",
+                    channel_name, e
+                ),
+            }
+            HandlerOutcome::Panicked(reason)
+        }
+    };
+    if let Some((chain, intercept_ctx)) = &intercept {
+        chain.close(intercept_ctx);
+    }
+    outcome
+}
+
+/// channel handler の終わり方 (= [`serve_channel_open`] の返り値、 v1.0 で追加)
+pub(crate) enum HandlerOutcome {
+    /// handler を起動しなかった (= nack / open_ack 送信失敗)
+    NotStarted,
+    /// 正常終了 (= peer の close による end-of-stream を含む)
+    Completed,
+    /// handler が `Err` を返した
+    Failed(NetworkError),
+    /// handler が panic した (= panic message)
+    Panicked(String),
+}
+
+/// panic payload から message を取り出す (= `&str` / `String` 以外は固定文言)
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = panic.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// handler panic を peer に伝える `__channel_error` (= panic message は載せない)
+fn channel_error_message(channel_name: &str) -> Result<ProtocolMessage, NetworkError> {
+    let error = ProtocolError::new(ProtocolError::INTERNAL, "Channel handler panicked")
+        .with_details(serde_json::json!({
+            "channel": channel_name,
+            "reason": "handler-panicked",
+        }));
+    ProtocolMessage::new_with_json(
+        0,
+        CHANNEL_ERROR_METHOD.to_string(),
+        MessageType::Error,
+        serde_json::to_value(error)?,
+    )
+}
+
+/// `__channel:{name}` の open に nack (= `reason`) を返して stream を畳む
+async fn reject_channel_open(
+    request: &ProtocolMessage,
+    mut send_stream: BoxUnisonSend,
+    reason: &str,
+) {
+    let channel_name = request
+        .method
+        .strip_prefix("__channel:")
+        .unwrap_or(&request.method);
+    if let Err(e) =
+        write_channel_ack(&mut send_stream, request.id, Some(reason), channel_name).await
+    {
+        warn!("Failed to send open nack for '{}': {}", channel_name, e);
+    } else {
+        let _ = send_stream.finish().await;
+    }
+}
+
+/// アクセスポリシー違反の nack (= `policy` / `detail` 付き) を返して stream を畳む (v1.0 で追加)
+async fn reject_channel_open_forbidden(
+    request: &ProtocolMessage,
+    mut send_stream: BoxUnisonSend,
+    violation: &PolicyViolation,
+) {
+    let channel_name = request
+        .method
+        .strip_prefix("__channel:")
+        .unwrap_or(&request.method);
+    let mut extra = serde_json::Map::new();
+    extra.insert("policy".into(), violation.rule.into());
+    extra.insert("detail".into(), violation.detail.clone().into());
+    if let Err(e) = write_channel_ack_with(
+        &mut send_stream,
+        request.id,
+        Some(NACK_FORBIDDEN),
+        channel_name,
+        extra,
+    )
+    .await
+    {
+        warn!("Failed to send open nack for '{}': {}", channel_name, e);
+    } else {
+        let _ = send_stream.finish().await;
+    }
+}
+
+/// 新しい双方向ストリームで `__goaway` を送る (= graceful shutdown の開始通知、 v1.0 で追加)
+pub(crate) async fn send_goaway(
+    connection: &Arc<dyn UnisonConn>,
+    deadline: Duration,
+) -> Result<(), NetworkError> {
+    let msg = ProtocolMessage::new_with_json(
+        0,
+        GOAWAY_METHOD.to_string(),
+        MessageType::Event,
+        serde_json::json!({ "deadline_ms": deadline.as_millis() as u64 }),
+    )?;
+    let frame = msg
+        .into_control_frame()
+        .map_err(|e| NetworkError::Protocol(format!("Failed to encode goaway frame: {}", e)))?;
+    let (mut send_stream, _recv_stream) = connection
+        .open_bi()
+        .await
+        .map_err(|e| NetworkError::Quic(format!("Failed to open goaway stream: {}", e)))?;
+    write_typed_frame(&mut send_stream, FRAME_TYPE_PROTOCOL, &frame.to_bytes())
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to send goaway: {}", e)))?;
+    send_stream.finish().await
+}
+
+/// 新しい双方向ストリームで `__channel:{name}` を開き、 `open_ack` を待つ
+///
+/// クライアント ([`super::client::ProtocolClient::open_channel`]) とサーバー
+/// ([`super::server::ProtocolServer::open_channel`]、 v1.0 で追加) の両方が使う。
+/// 相手が nack を返した場合は [`NetworkError::Protocol`] で reject する。
+pub(crate) async fn open_channel_stream(
+    connection: Arc<dyn UnisonConn>,
+    channel_name: &str,
+) -> Result<UnisonStream, NetworkError> {
+    // 新しい双方向ストリームを開く
+    let (mut send_stream, mut recv_stream) = connection
+        .open_bi()
+        .await
+        .map_err(|e| NetworkError::Quic(format!("Failed to open channel stream: {}", e)))?;
+
+    // チャネル識別メッセージを送信（length-prefixed）
+    let method = format!("__channel:{}", channel_name);
+    let request_id = generate_request_id();
+    let message = ProtocolMessage::new_with_json(
+        request_id,
+        method.clone(),
+        MessageType::Request,
+        serde_json::json!({}),
+    )?;
+
+    let frame = message
+        .into_frame()
+        .map_err(|e| NetworkError::Protocol(format!("Failed to create channel frame: {}", e)))?;
+    let frame_bytes = frame.to_bytes();
+    write_typed_frame(&mut send_stream, FRAME_TYPE_PROTOCOL, &frame_bytes)
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to send channel open: {}", e)))?;
+
+    // Phase 6c: 相手の open_ack を待つ。 相手は handler を起動する前に同 stream へ
+    // `__channel_ack` frame を 1 本返す。 これを recv loop に渡る前にここで read
+    // することで、 accept されたかを確定させる。
+    let ack = read_channel_ack(&mut recv_stream).await?;
+    match ack.msg_type {
+        MessageType::Response => {
+            debug!("Channel '{}' open_ack received", channel_name);
+        }
+        MessageType::Error => {
+            let payload = ack.payload_as_value().unwrap_or_default();
+            return Err(NetworkError::Protocol(format!(
+                "Channel '{}' open rejected: {}",
+                channel_name, payload
+            )));
+        }
+        other => {
+            return Err(NetworkError::Protocol(format!(
+                "Channel '{}' open: unexpected ack msg_type {:?}",
+                channel_name, other
+            )));
+        }
+    }
+
+    Ok(UnisonStream::from_streams(
+        request_id,
+        method,
+        connection,
+        send_stream,
+        recv_stream,
+    ))
+}
+
+/// Channel open 後の `open_ack` (= `__channel_ack` frame) を recv ストリームから
+/// 1 本読む (= Phase 6c)。
+///
+/// 相手は handler 起動の前に同 stream へ ack を返す。 frame type が
+/// PROTOCOL でない / method が `__channel_ack` でない場合はプロトコル違反として
+/// `Err`。 stream が ack 到着前に終端した場合も `Err` (= no-accept signal)。
+async fn read_channel_ack<R>(recv: &mut R) -> Result<ProtocolMessage, NetworkError>
+where
+    R: tokio::io::AsyncRead + Unpin + ?Sized,
+{
+    let (frame_type, frame_bytes) = read_typed_frame(recv)
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to read open_ack frame: {}", e)))?;
+    if frame_type != FRAME_TYPE_PROTOCOL {
+        return Err(NetworkError::Protocol(format!(
+            "open_ack: unexpected frame type 0x{:02x}",
+            frame_type
+        )));
+    }
+    let frame = ProtocolFrame::from_bytes(&frame_bytes)
+        .map_err(|e| NetworkError::Protocol(format!("Failed to decode open_ack frame: {}", e)))?;
+    let msg = ProtocolMessage::from_frame(&frame)
+        .map_err(|e| NetworkError::Protocol(format!("Failed to parse open_ack: {}", e)))?;
+    if msg.method != CHANNEL_ACK_METHOD {
+        return Err(NetworkError::Protocol(format!(
+            "open_ack: expected method '{}', got '{}'",
+            CHANNEL_ACK_METHOD, msg.method
+        )));
+    }
+    Ok(msg)
+}
@@ -58,12 +58,93 @@ pub const FRAME_TYPE_RAW: u8 = 0x01;
 /// - **accept**: `msg_type = Response`、 `id` = open request の id、 payload `{}`
 /// - **nack** (= channel-not-found): `msg_type = Error`、 同 `id`、
 ///   payload `{"error":"channel-not-found","channel":"{name}"}`
+///   (v1.0 以降、 `error` には [`NACK_SERVER_DRAINING`] 等の理由も入る)
 ///
 /// `id` が open request と一致するため、 クライアントは自分の open request に
 /// 相関させられる。 `__identity` と同じ `__`-prefix の特殊 method であり、 新しい
 /// typed frame type は追加しない (= 既存 wire layout は不変、 additive)。
 pub const CHANNEL_ACK_METHOD: &str = "__channel_ack";
 
+/// Server-streaming 応答の終端 method 名 (v1.0 で追加)。
+///
+/// `UnisonChannel::request_stream` に対し、 サーバーは同じ `id` の `Response` を
+/// 0 個以上返したのち、 この method の `Response` (payload `{}`) を 1 本送って
+/// stream を閉じる。 途中で失敗した場合は同 `id` の `Error` が終端を兼ねる。
+pub const STREAM_END_METHOD: &str = "__stream_end";
+
+/// Request キャンセル通知の method 名 (v1.0 で追加)。
+///
+/// クライアントが応答待ちの request を放棄したとき (= `request()` の future を
+/// drop / timeout した、 `request_stream` の stream を drop した)、 `msg_type = Event`、
+/// `id` = 対象 request の id、 payload `{}` で送る。 packet header は
+/// `PacketType::Control` (= 制御メッセージ)。 受信側は該当 request の
+/// `CancellationToken` を cancel し、 以降の `send_stream_item` は
+/// [`super::NetworkError::Cancelled`] を返す。
+pub const CANCEL_METHOD: &str = "__cancel";
+
+/// Graceful shutdown 開始通知の method 名 (v1.0 で追加)。
+///
+/// `ServerHandle::shutdown_graceful` の開始時、 サーバーは各接続に新しい双方向
+/// ストリームを開き、 `msg_type = Event`、 `id = 0`、 payload `{"deadline_ms": N}` の
+/// この method を 1 本送って閉じる (packet header は `PacketType::Control`)。
+/// 以降の channel open は [`NACK_SERVER_DRAINING`] で拒否され、 `deadline_ms`
+/// 経過後に接続が閉じられる。
+pub const GOAWAY_METHOD: &str = "__goaway";
+
+/// チャネル更新通知の method 名 (v1.0 で追加)。
+///
+/// サーバーは `__identity` を送った stream を閉じずに持ち続け、 channel の登録 /
+/// 解除 / 状態変更のたびに `msg_type = Event`、 `id = 0`、 payload に JSON 化した
+/// [`ChannelUpdate`](super::identity::ChannelUpdate) のこの method を送る。 通知を
+/// 取りこぼした (= broadcast の lag) 場合は、 代わりに最新の `__identity` を送り直す。
+pub const CHANNEL_UPDATE_METHOD: &str = "__channel_update";
+
+/// 接続認証の method 名 (v1.0 で追加)。
+///
+/// クライアントが接続直後に新しい双方向ストリームを開いて送る。 手順は
+/// [`super::auth`] を参照。 サーバーに `Authenticator` が設定されている場合、
+/// 認証が済むまで channel open は [`NACK_UNAUTHENTICATED`] で拒否される。
+pub const AUTH_METHOD: &str = "__auth";
+
+/// Application-level heartbeat の method 名 (v1.0 で追加)。
+///
+/// heartbeat を有効にした側が接続直後に新しい双方向ストリームを開き、 `interval` ごとに
+/// `msg_type = Request`、 `id` = 連番、 payload `{}` のこの method を送る。 受けた側は
+/// 同じ stream へ同じ `id` の `Response` を返す。 どちらも packet header は
+/// `PacketType::Heartbeat` + `PacketFlags::KEEPALIVE`。 手順は [`super::heartbeat`] を参照。
+pub const HEARTBEAT_METHOD: &str = "__heartbeat";
+
+/// channel open nack の理由: 未登録 channel
+pub const NACK_CHANNEL_NOT_FOUND: &str = "channel-not-found";
+
+/// channel open nack の理由: サーバーが drain 中 (v1.0 で追加)
+pub const NACK_SERVER_DRAINING: &str = "server-draining";
+
+/// channel open nack の理由: 接続が未認証 (v1.0 で追加)
+pub const NACK_UNAUTHENTICATED: &str = "unauthenticated";
+
+/// channel open nack の理由: handshake の完了前 (= 0-RTT) に replay-safe でない channel を
+/// open した (v1.0 で追加)
+pub const NACK_EARLY_DATA: &str = "early-data";
+
+/// channel open nack の理由: channel のアクセスポリシーを満たさない (v1.0 で追加)
+///
+/// payload には `policy` (= 満たさなかった規則、 `"require-role"` / `"allow-from"`) と
+/// `detail` が加わる。
+pub const NACK_FORBIDDEN: &str = "forbidden";
+
+/// Channel 単位のエラー通知の method 名 (v1.0 で追加)。
+///
+/// channel handler が panic したとき、 受ける側は同 stream へ `msg_type = Error`、
+/// `id = 0`、 payload = `ProtocolError` (`code = INTERNAL`、 `details` に
+/// `{"channel": name, "reason": "handler-panicked"}`) のこの method を 1 本送り、
+/// 続けて送信側を [`RESET_HANDLER_PANICKED`] で reset する。 開いた側は応答待ちの
+/// request を全てこのエラーで解決する。
+pub const CHANNEL_ERROR_METHOD: &str = "__channel_error";
+
+/// handler panic で stream を reset するときの application error code (v1.0 で追加)
+pub const RESET_HANDLER_PANICKED: u32 = 0x01;
+
 /// Typed フレーム — type tag 付きの読み書き
 /// フォーマット: [4 bytes: length][1 byte: type tag][payload]
 /// length は type tag + payload の合計バイト数
@@ -121,8 +202,8 @@ pub async fn write_typed_frame<W: AsyncWrite + Unpin + ?Sized>(
 
 /// Channel open ack / nack を 1 本の typed protocol frame として送信する (= Phase 6c)。
 ///
-/// `accepted == true` なら [`MessageType::Response`] の `open_ack`、 `false` なら
-/// [`MessageType::Error`] の nack (= payload に `channel-not-found`) を `send`
+/// `rejection == None` なら [`MessageType::Response`] の `open_ack`、 `Some(reason)` なら
+/// [`MessageType::Error`] の nack (= payload の `error` に `reason`) を `send`
 /// ストリームへ書き出す。 `request_id` は open request の id を引き継ぎ、
 /// クライアントが自分の open と相関できるようにする。
 ///
@@ -131,21 +212,40 @@ pub async fn write_typed_frame<W: AsyncWrite + Unpin + ?Sized>(
 pub(crate) async fn write_channel_ack<W: AsyncWrite + Unpin + ?Sized>(
     send: &mut W,
     request_id: u64,
-    accepted: bool,
+    rejection: Option<&str>,
+    channel_name: &str,
+) -> Result<()> {
+    write_channel_ack_with(
+        send,
+        request_id,
+        rejection,
+        channel_name,
+        serde_json::Map::new(),
+    )
+    .await
+}
+
+/// nack の payload に `extra` の field を足して open_ack を書き出す (v1.0 で追加)
+///
+/// [`NACK_FORBIDDEN`] の `policy` / `detail` のような構造化された拒否理由に使う。
+/// accept (= `rejection` が `None`) では `extra` は無視する。
+pub(crate) async fn write_channel_ack_with<W: AsyncWrite + Unpin + ?Sized>(
+    send: &mut W,
+    request_id: u64,
+    rejection: Option<&str>,
     channel_name: &str,
+    extra: serde_json::Map<String, serde_json::Value>,
 ) -> Result<()> {
     use super::MessageType;
 
-    let (msg_type, payload) = if accepted {
-        (MessageType::Response, serde_json::json!({}))
-    } else {
-        (
-            MessageType::Error,
-            serde_json::json!({
-                "error": "channel-not-found",
-                "channel": channel_name,
-            }),
-        )
+    let (msg_type, payload) = match rejection {
+        None => (MessageType::Response, serde_json::json!({})),
+        Some(reason) => {
+            let mut payload = extra;
+            payload.insert("error".into(), reason.into());
+            payload.insert("channel".into(), channel_name.into());
+            (MessageType::Error, serde_json::Value::Object(payload))
+        }
     };
     let msg = ProtocolMessage::new_with_json(
         request_id,
@@ -0,0 +1,333 @@
+//! Application-level heartbeat (v1.0 で追加)
+//!
+//! QUIC の keep-alive (= `TransportOptions::keep_alive_interval`) は NAT mapping を
+//! 保つだけで、 相手のアプリが応答しているかは分からない。 [`HeartbeatConfig`] を
+//! `ProtocolClient::with_heartbeat()` / `ProtocolServer::set_heartbeat()` で設定すると、
+//! その側は接続ごとに heartbeat task を走らせる:
+//!
+//! 1. 新しい双方向ストリームを開き、 `interval` ごとに `__heartbeat` の ping
+//!    (`Request`、 `id` = 連番) を送る
+//! 2. 相手は同じ stream へ同じ `id` の pong (`Response`) を返す (= 設定の有無に関わらず)
+//! 3. pong が届けば RTT を記録し、 次の ping までに届かなければ miss として数える
+//! 4. `max_missed` 回続けて miss したら相手を dead とみなし、 接続を
+//!    `"heartbeat timeout"` で閉じて `Disconnected { reason: "heartbeat timeout" }` を fire する
+//!
+//! 計測値は [`HeartbeatStats`] として `ConnectionContext::heartbeat_stats()` に入る
+//! (= client は `ProtocolClient::rtt()`、 server は `ConnectionHandle::heartbeat_stats()`)。
+//!
+//! ```rust,ignore
+//! let client = ProtocolClient::new(transport)
+//!     .with_heartbeat(HeartbeatConfig::default().with_interval(Duration::from_secs(2)));
+//! client.connect("[::1]:8080").await?;
+//! // 最初の pong 以降は Some
+//! let rtt = client.rtt();
+//! ```
+//!
+//! 相手が stream を閉じた (= `__heartbeat` を知らない古い peer) 場合は heartbeat を止め、
+//! dead とはみなさない。 サーバー側の heartbeat は pong を返す peer (= Rust の
+//! `ProtocolClient`) を前提にする。
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use super::conn::{BoxUnisonRecv, BoxUnisonSend, UnisonConn};
+use super::context::ConnectionContext;
+use super::frame::{FRAME_TYPE_PROTOCOL, HEARTBEAT_METHOD, read_typed_frame, write_typed_frame};
+use super::{MessageType, NetworkError, ProtocolFrame, ProtocolMessage};
+
+/// heartbeat の送信間隔と dead 判定
+///
+/// ```rust
+/// use std::time::Duration;
+/// use unison::network::HeartbeatConfig;
+///
+/// let config = HeartbeatConfig::default()
+///     .with_interval(Duration::from_secs(2))
+///     .with_max_missed(3);
+/// assert_eq!(config.dead_after(), Duration::from_secs(6));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatConfig {
+    /// ping を送る間隔 (= pong を待つ時間でもある)
+    pub interval: Duration,
+    /// 相手を dead とみなすまでに続けて miss する回数
+    pub max_missed: u32,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            max_missed: 3,
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ping の間隔を設定（ビルダーパターン、0 は 1ms に丸める）
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// dead 判定までの連続 miss 回数を設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_max_missed(mut self, max_missed: u32) -> Self {
+        self.max_missed = max_missed.max(1);
+        self
+    }
+
+    /// 応答が途絶えてから dead とみなすまでの目安 (= `interval * max_missed`)
+    pub fn dead_after(&self) -> Duration {
+        self.interval.saturating_mul(self.max_missed)
+    }
+}
+
+/// heartbeat の計測値 (= 接続ごと)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeartbeatStats {
+    /// 最後の pong の RTT
+    pub rtt: Option<Duration>,
+    /// RTT の指数移動平均 (= RFC 6298 の SRTT と同じく 1/8 で更新)
+    pub smoothed_rtt: Option<Duration>,
+    /// 送った ping の数
+    pub sent: u64,
+    /// 受け取った pong の数
+    pub acked: u64,
+    /// 今続いている miss の回数 (= pong が届くと 0 に戻る)
+    pub missed: u32,
+    /// miss の累計
+    pub total_missed: u64,
+}
+
+impl HeartbeatStats {
+    fn record_rtt(&mut self, sample: Duration) {
+        self.rtt = Some(sample);
+        self.smoothed_rtt = Some(match self.smoothed_rtt {
+            Some(srtt) => (srtt * 7 + sample) / 8,
+            None => sample,
+        });
+        self.acked += 1;
+        self.missed = 0;
+    }
+
+    fn record_miss(&mut self) {
+        self.missed += 1;
+        self.total_missed += 1;
+    }
+}
+
+/// heartbeat task の終わり方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HeartbeatOutcome {
+    /// `max_missed` 回続けて pong が届かなかった (= 接続は呼び出し側が閉じる)
+    Dead,
+    /// 接続断 / 相手が stream を閉じた (= heartbeat だけ止める)
+    Stopped,
+}
+
+/// dead と判定したときの close reason / `Disconnected` の reason
+pub(crate) const HEARTBEAT_TIMEOUT_REASON: &str = "heartbeat timeout";
+
+/// heartbeat task 本体 (= heartbeat を有効にした側)
+///
+/// `connection` に `__heartbeat` stream を開き、 `config.interval` ごとに ping を送って
+/// `ctx` の [`HeartbeatStats`] を更新する。 接続は閉じない (= [`HeartbeatOutcome::Dead`]
+/// を受けた呼び出し側が閉じ、 `Disconnected` に reason を載せる)。
+pub(crate) async fn run_heartbeat(
+    connection: Arc<dyn UnisonConn>,
+    config: HeartbeatConfig,
+    ctx: Arc<ConnectionContext>,
+) -> HeartbeatOutcome {
+    ctx.update_heartbeat_stats(|stats| *stats = HeartbeatStats::default());
+    let (mut send_stream, recv_stream) = match connection.open_bi().await {
+        Ok(stream) => stream,
+        Err(e) => {
+            debug!("Failed to open heartbeat stream: {}", e);
+            return HeartbeatOutcome::Stopped;
+        }
+    };
+
+    // pong の読み取りは別 task (= read_typed_frame は cancel safe でないため)
+    let (pong_tx, mut pong_rx) = mpsc::unbounded_channel();
+    let reader = tokio::spawn(read_pongs(recv_stream, pong_tx));
+    let _reader = AbortOnDrop(reader);
+
+    let mut ticker = tokio::time::interval(config.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // 送ったが pong の届いていない ping (= 連番と送信時刻)
+    let mut outstanding: VecDeque<(u64, Instant)> = VecDeque::new();
+    let mut seq = 0u64;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                // 直前の ping に pong が届いていなければ miss
+                if !outstanding.is_empty() {
+                    let mut missed = 0;
+                    ctx.update_heartbeat_stats(|stats| {
+                        stats.record_miss();
+                        missed = stats.missed;
+                    });
+                    if missed >= config.max_missed {
+                        warn!(
+                            "Heartbeat: no pong for {} consecutive beat(s), peer is dead",
+                            missed
+                        );
+                        return HeartbeatOutcome::Dead;
+                    }
+                }
+                seq += 1;
+                if let Err(e) = write_heartbeat(&mut send_stream, seq, MessageType::Request).await {
+                    debug!("Heartbeat stream closed: {}", e);
+                    return HeartbeatOutcome::Stopped;
+                }
+                outstanding.push_back((seq, Instant::now()));
+                // 遅れて届く pong の照合用に、 dead 判定までの分だけ覚えておく
+                while outstanding.len() > config.max_missed as usize + 1 {
+                    outstanding.pop_front();
+                }
+                ctx.update_heartbeat_stats(|stats| stats.sent += 1);
+            }
+            pong = pong_rx.recv() => {
+                let Some(id) = pong else {
+                    debug!("Heartbeat stream closed by peer");
+                    return HeartbeatOutcome::Stopped;
+                };
+                // 遅れて届いた pong でも相手は生きている (= それ以前の ping も片付ける)
+                if let Some(pos) = outstanding.iter().position(|(seq, _)| *seq == id) {
+                    let sample = outstanding[pos].1.elapsed();
+                    outstanding.drain(..=pos);
+                    ctx.update_heartbeat_stats(|stats| stats.record_rtt(sample));
+                }
+            }
+        }
+    }
+}
+
+/// heartbeat stream の pong の id を `tx` に流す (= stream が閉じたら終わる)
+async fn read_pongs(mut recv_stream: BoxUnisonRecv, tx: mpsc::UnboundedSender<u64>) {
+    while let Ok((FRAME_TYPE_PROTOCOL, frame_bytes)) = read_typed_frame(&mut recv_stream).await {
+        let Ok(message) =
+            ProtocolFrame::from_bytes(&frame_bytes).and_then(|f| ProtocolMessage::from_frame(&f))
+        else {
+            continue;
+        };
+        if message.method == HEARTBEAT_METHOD
+            && message.msg_type == MessageType::Response
+            && tx.send(message.id).is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// 相手が開いた `__heartbeat` stream に pong を返し続ける (= 受ける側)
+///
+/// `first` は stream の最初の ping。 相手が stream を閉じるか接続が切れたら終わる。
+pub(crate) async fn serve_heartbeat(
+    first: ProtocolMessage,
+    mut send_stream: BoxUnisonSend,
+    mut recv_stream: BoxUnisonRecv,
+) {
+    if !answer_ping(&mut send_stream, &first).await {
+        return;
+    }
+    loop {
+        match read_typed_frame(&mut recv_stream).await {
+            Ok((FRAME_TYPE_PROTOCOL, frame_bytes)) => {
+                match ProtocolFrame::from_bytes(&frame_bytes)
+                    .and_then(|f| ProtocolMessage::from_frame(&f))
+                {
+                    Ok(ping) => {
+                        if !answer_ping(&mut send_stream, &ping).await {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!("Failed to parse heartbeat frame: {}", e),
+                }
+            }
+            Ok((frame_type, _)) => {
+                warn!("Unexpected frame type in heartbeat: 0x{:02x}", frame_type);
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+/// ping なら pong を返す (= 書けなければ `false`)
+async fn answer_ping(send_stream: &mut BoxUnisonSend, ping: &ProtocolMessage) -> bool {
+    if ping.method != HEARTBEAT_METHOD || ping.msg_type != MessageType::Request {
+        return true;
+    }
+    write_heartbeat(send_stream, ping.id, MessageType::Response)
+        .await
+        .is_ok()
+}
+
+/// ping / pong を 1 本書く
+async fn write_heartbeat(
+    send_stream: &mut BoxUnisonSend,
+    id: u64,
+    msg_type: MessageType,
+) -> Result<(), NetworkError> {
+    let frame =
+        ProtocolMessage::new_encoded(id, HEARTBEAT_METHOD.to_string(), msg_type, b"{}".to_vec())
+            .into_heartbeat_frame()
+            .map_err(|e| {
+                NetworkError::Protocol(format!("Failed to encode heartbeat frame: {}", e))
+            })?;
+    write_typed_frame(send_stream, FRAME_TYPE_PROTOCOL, &frame.to_bytes())
+        .await
+        .map_err(|e| NetworkError::Protocol(format!("Failed to send heartbeat: {}", e)))
+}
+
+/// drop で task を abort する (= heartbeat task と pong reader の寿命を揃える)
+pub(crate) struct AbortOnDrop(pub(crate) tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_clamps_degenerate_values() {
+        let config = HeartbeatConfig::default()
+            .with_interval(Duration::ZERO)
+            .with_max_missed(0);
+        assert_eq!(config.interval, Duration::from_millis(1));
+        assert_eq!(config.max_missed, 1);
+        assert_eq!(
+            HeartbeatConfig::default().dead_after(),
+            Duration::from_secs(15)
+        );
+    }
+
+    #[test]
+    fn stats_track_rtt_and_misses() {
+        let mut stats = HeartbeatStats::default();
+        stats.record_miss();
+        stats.record_miss();
+        assert_eq!((stats.missed, stats.total_missed), (2, 2));
+
+        stats.record_rtt(Duration::from_millis(80));
+        assert_eq!(stats.rtt, Some(Duration::from_millis(80)));
+        assert_eq!(stats.smoothed_rtt, Some(Duration::from_millis(80)));
+        assert_eq!((stats.acked, stats.missed, stats.total_missed), (1, 0, 2));
+
+        stats.record_rtt(Duration::from_millis(160));
+        assert_eq!(stats.rtt, Some(Duration::from_millis(160)));
+        assert_eq!(stats.smoothed_rtt, Some(Duration::from_millis(90)));
+    }
+}
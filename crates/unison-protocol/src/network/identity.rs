@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::frame::CHANNEL_UPDATE_METHOD;
 use super::{MessageType, ProtocolMessage};
 
 /// サーバーの自己紹介情報
@@ -84,4 +85,42 @@ impl ServerIdentity {
     pub fn from_protocol_message(msg: &ProtocolMessage) -> Result<Self, serde_json::Error> {
         serde_json::from_slice(&msg.payload)
     }
+
+    /// チャネル更新を反映する（v1.0 で追加）
+    ///
+    /// `Added` は同名のチャネルを置き換える (= 重複して届いても 1 件)。 知らない
+    /// チャネルの `StatusChanged` は無視する。
+    pub fn apply(&mut self, update: &ChannelUpdate) {
+        match update {
+            ChannelUpdate::Added(info) => {
+                match self.channels.iter_mut().find(|c| c.name == info.name) {
+                    Some(existing) => *existing = info.clone(),
+                    None => self.channels.push(info.clone()),
+                }
+            }
+            ChannelUpdate::Removed(name) => self.channels.retain(|c| &c.name != name),
+            ChannelUpdate::StatusChanged { name, status } => {
+                if let Some(channel) = self.channels.iter_mut().find(|c| &c.name == name) {
+                    channel.status = status.clone();
+                }
+            }
+        }
+    }
+}
+
+impl ChannelUpdate {
+    /// ProtocolMessageに変換（Identity stream 送信用、 v1.0 で追加）
+    pub fn to_protocol_message(&self) -> ProtocolMessage {
+        ProtocolMessage {
+            id: 0,
+            method: CHANNEL_UPDATE_METHOD.to_string(),
+            msg_type: MessageType::Event,
+            payload: serde_json::to_vec(self).unwrap(),
+        }
+    }
+
+    /// ProtocolMessageから復元（v1.0 で追加）
+    pub fn from_protocol_message(msg: &ProtocolMessage) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(&msg.payload)
+    }
 }
@@ -0,0 +1,323 @@
+//! Interceptor: channel open と channel 上の message を横断的に扱うフック (v1.0 で追加)
+//!
+//! 認証・ログ・計測・rate limit を handler ごとに書かずに済むよう、
+//! `ProtocolServer::add_interceptor()` で登録した [`Interceptor`] が
+//! `__channel:` handshake と handler 起動の間、 および channel 上の全 message に挟まる。
+//!
+//! ```rust,ignore
+//! server.add_interceptor(TracingInterceptor::new()).await;
+//! server
+//!     .add_interceptor(TimingInterceptor::new().on_complete(|t| {
+//!         latency.record(&t.method, t.elapsed);
+//!     }))
+//!     .await;
+//! ```
+//!
+//! - 登録順に呼ばれ、 最初に `Err` を返した interceptor で打ち切る。
+//! - [`Interceptor::on_open`] の `Err(reason)` は channel open の nack になる
+//!   (= 開いた側は `reason` を含む `NetworkError::Protocol` を受け取る)。
+//! - [`Interceptor::on_inbound`] の `Err` は、 Request ならその `ProtocolError` を
+//!   Error として返し、 Event なら捨てる。 どちらも handler には届かない。
+//! - message フックはサーバーが受けた channel とサーバー発信の channel の両方に掛かる。
+//!   `__cancel` / `__stream_end` 等の `__` 付き制御 message もそのまま渡る。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::context::{ConnectionContext, ConnectionId};
+use super::{MessageType, ProtocolError, ProtocolMessage};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// interceptor に渡す channel の情報
+#[derive(Debug, Clone)]
+pub struct InterceptContext {
+    /// 接続単位のコンテキスト (= channel handler の `ctx` と同じ)
+    pub connection: Arc<ConnectionContext>,
+    /// channel 名
+    pub channel: String,
+    /// stream ID (= channel open request の message id)
+    pub stream_id: u64,
+}
+
+/// channel open / message のフック
+///
+/// 全メソッドにデフォルト実装があり、 必要なものだけ実装すればよい。
+/// message フックは送受信の経路上で同期的に呼ばれるので、 重い処理は避ける。
+pub trait Interceptor: Send + Sync + 'static {
+    /// channel open を検査する (= `Err(reason)` で nack)
+    ///
+    /// handler が登録済みの channel についてのみ、 `open_ack` を返す前に呼ばれる。
+    fn on_open<'a>(&'a self, _ctx: &'a InterceptContext) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// 受信した message を検査する
+    ///
+    /// Request に `Err` を返すとその Error を応答し、 Event に返すと捨てる。
+    fn on_inbound(
+        &self,
+        _ctx: &InterceptContext,
+        _msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        Ok(())
+    }
+
+    /// 送信する message を観測する
+    fn on_outbound(&self, _ctx: &InterceptContext, _msg: &ProtocolMessage) {}
+
+    /// channel の handler が終わった (= サーバーが受けた channel のみ)
+    fn on_close(&self, _ctx: &InterceptContext) {}
+}
+
+/// `Arc` で共有した interceptor (= 登録後も呼び出し側から状態を参照できる)
+impl<T: Interceptor + ?Sized> Interceptor for Arc<T> {
+    fn on_open<'a>(&'a self, ctx: &'a InterceptContext) -> BoxFuture<'a, Result<(), String>> {
+        (**self).on_open(ctx)
+    }
+
+    fn on_inbound(
+        &self,
+        ctx: &InterceptContext,
+        msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        (**self).on_inbound(ctx, msg)
+    }
+
+    fn on_outbound(&self, ctx: &InterceptContext, msg: &ProtocolMessage) {
+        (**self).on_outbound(ctx, msg)
+    }
+
+    fn on_close(&self, ctx: &InterceptContext) {
+        (**self).on_close(ctx)
+    }
+}
+
+/// 登録済み interceptor の列 (= 接続ごと / channel ごとに共有する snapshot)
+#[derive(Clone)]
+pub(crate) struct InterceptorChain(Arc<[Arc<dyn Interceptor>]>);
+
+impl InterceptorChain {
+    /// 空なら `None` (= フックの呼び出し自体を省く)
+    pub(crate) fn from_registered(interceptors: &[Arc<dyn Interceptor>]) -> Option<Self> {
+        if interceptors.is_empty() {
+            None
+        } else {
+            Some(Self(interceptors.into()))
+        }
+    }
+
+    pub(crate) async fn check_open(&self, ctx: &InterceptContext) -> Result<(), String> {
+        for interceptor in self.0.iter() {
+            interceptor.on_open(ctx).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn inbound(
+        &self,
+        ctx: &InterceptContext,
+        msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        self.0
+            .iter()
+            .try_for_each(|interceptor| interceptor.on_inbound(ctx, msg))
+    }
+
+    pub(crate) fn outbound(&self, ctx: &InterceptContext, msg: &ProtocolMessage) {
+        for interceptor in self.0.iter() {
+            interceptor.on_outbound(ctx, msg);
+        }
+    }
+
+    pub(crate) fn close(&self, ctx: &InterceptContext) {
+        for interceptor in self.0.iter() {
+            interceptor.on_close(ctx);
+        }
+    }
+}
+
+/// channel の開閉と message を `tracing` の debug level で記録する interceptor
+#[derive(Debug, Clone, Default)]
+pub struct TracingInterceptor;
+
+impl TracingInterceptor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Interceptor for TracingInterceptor {
+    fn on_open<'a>(&'a self, ctx: &'a InterceptContext) -> BoxFuture<'a, Result<(), String>> {
+        debug!(
+            connection_id = %ctx.connection.connection_id,
+            channel = %ctx.channel,
+            stream_id = ctx.stream_id,
+            "channel open"
+        );
+        Box::pin(async { Ok(()) })
+    }
+
+    fn on_inbound(
+        &self,
+        ctx: &InterceptContext,
+        msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        debug!(
+            connection_id = %ctx.connection.connection_id,
+            channel = %ctx.channel,
+            id = msg.id,
+            method = %msg.method,
+            msg_type = ?msg.msg_type,
+            "channel recv"
+        );
+        Ok(())
+    }
+
+    fn on_outbound(&self, ctx: &InterceptContext, msg: &ProtocolMessage) {
+        debug!(
+            connection_id = %ctx.connection.connection_id,
+            channel = %ctx.channel,
+            id = msg.id,
+            method = %msg.method,
+            msg_type = ?msg.msg_type,
+            "channel send"
+        );
+    }
+
+    fn on_close(&self, ctx: &InterceptContext) {
+        debug!(
+            connection_id = %ctx.connection.connection_id,
+            channel = %ctx.channel,
+            stream_id = ctx.stream_id,
+            "channel closed"
+        );
+    }
+}
+
+/// 1 Request の処理時間 ([`TimingInterceptor`] の計測結果)
+#[derive(Debug, Clone)]
+pub struct RequestTiming {
+    pub connection_id: ConnectionId,
+    pub channel: String,
+    pub method: String,
+    pub request_id: u64,
+    /// Request の受信から最初の応答 (= Response / Error) の送信まで
+    pub elapsed: Duration,
+    /// 最初の応答が Error だった
+    pub is_error: bool,
+}
+
+type TimingKey = (ConnectionId, u64, u64);
+type TimingSink = Arc<dyn Fn(&RequestTiming) + Send + Sync>;
+
+/// 受けた Request の処理時間を計る interceptor
+///
+/// Request の受信から同じ id の最初の応答までを計る (= server-streaming では最初の
+/// item まで)。 結果は `on_complete()` の callback に渡し、 未指定なら debug log に出す。
+/// 応答しないまま channel が閉じた Request は捨てる。
+pub struct TimingInterceptor {
+    inflight: Mutex<HashMap<TimingKey, (String, Instant)>>,
+    sink: Option<TimingSink>,
+}
+
+impl Default for TimingInterceptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for TimingInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimingInterceptor")
+            .field("inflight", &self.inflight_count())
+            .finish()
+    }
+}
+
+impl TimingInterceptor {
+    pub fn new() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+            sink: None,
+        }
+    }
+
+    /// 計測結果の受け口（ビルダーパターン）
+    pub fn on_complete<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&RequestTiming) + Send + Sync + 'static,
+    {
+        self.sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// 応答待ちの Request 数
+    pub fn inflight_count(&self) -> usize {
+        self.inflight.lock().expect("timing lock poisoned").len()
+    }
+
+    fn key(ctx: &InterceptContext, request_id: u64) -> TimingKey {
+        (ctx.connection.connection_id, ctx.stream_id, request_id)
+    }
+}
+
+impl Interceptor for TimingInterceptor {
+    fn on_inbound(
+        &self,
+        ctx: &InterceptContext,
+        msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        if msg.msg_type == MessageType::Request {
+            self.inflight
+                .lock()
+                .expect("timing lock poisoned")
+                .insert(Self::key(ctx, msg.id), (msg.method.clone(), Instant::now()));
+        }
+        Ok(())
+    }
+
+    fn on_outbound(&self, ctx: &InterceptContext, msg: &ProtocolMessage) {
+        if !matches!(msg.msg_type, MessageType::Response | MessageType::Error) {
+            return;
+        }
+        let Some((method, started)) = self
+            .inflight
+            .lock()
+            .expect("timing lock poisoned")
+            .remove(&Self::key(ctx, msg.id))
+        else {
+            return;
+        };
+        let timing = RequestTiming {
+            connection_id: ctx.connection.connection_id,
+            channel: ctx.channel.clone(),
+            method,
+            request_id: msg.id,
+            elapsed: started.elapsed(),
+            is_error: msg.msg_type == MessageType::Error,
+        };
+        match &self.sink {
+            Some(sink) => sink(&timing),
+            None => debug!(
+                channel = %timing.channel,
+                method = %timing.method,
+                elapsed_us = timing.elapsed.as_micros() as u64,
+                is_error = timing.is_error,
+                "request completed"
+            ),
+        }
+    }
+
+    fn on_close(&self, ctx: &InterceptContext) {
+        let connection_id = ctx.connection.connection_id;
+        self.inflight
+            .lock()
+            .expect("timing lock poisoned")
+            .retain(|(conn, stream, _), _| !(*conn == connection_id && *stream == ctx.stream_id));
+    }
+}
@@ -12,6 +12,10 @@
 //! same self-signed certificate, eliminating the need for the client to fall
 //! back to [`crate::network::trust::TrustAnchors::SkipVerification`].
 //!
+//! For mutual TLS, [`InternalMeshKeypair::client_auth`] and
+//! [`InternalMeshKeypair::client_cert_source`] reuse the same cert in the
+//! opposite direction, so the server can authenticate its mesh peers too.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -31,6 +35,7 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use super::cert::{CertSource, generate_self_signed_with_der};
+use super::client_auth::ClientAuth;
 use super::trust::TrustAnchors;
 
 /// Paired server cert + client trust anchor for internal mesh communication.
@@ -61,4 +66,23 @@ impl InternalMeshKeypair {
             client_trust_anchors: TrustAnchors::Custom(vec![cert_der]),
         })
     }
+
+    /// Server-side client certificate policy requiring peers to present this
+    /// mesh cert (mutual TLS, v1.0).
+    ///
+    /// Pair with [`Self::client_cert_source`] on the client so both sides of
+    /// the mesh authenticate with the same keypair.
+    pub fn client_auth(&self) -> ClientAuth {
+        match &self.client_trust_anchors {
+            TrustAnchors::Custom(certs) => ClientAuth::Required(certs.clone()),
+            // `generate` always builds `Custom`; if a caller replaced the public
+            // field, an empty CA set makes the server's `bind` fail loudly.
+            _ => ClientAuth::Required(Vec::new()),
+        }
+    }
+
+    /// Client-side cert to present to a server using [`Self::client_auth`] (v1.0).
+    pub fn client_cert_source(&self) -> CertSource {
+        self.server_cert_source.clone()
+    }
 }
@@ -3,37 +3,68 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::codec::{CodecError, Decodable, Encodable, JsonCodec};
-use crate::packet::{SerializationError, UnisonPacket};
+use crate::packet::{PacketFlags, PacketType, SerializationError, UnisonPacket};
 use crate::proto;
 
+pub mod auth;
 pub mod cert;
 pub mod channel;
+pub mod channel_config;
+pub mod channel_group;
 pub mod client;
+pub mod client_auth;
 pub mod conn;
 pub mod conn_quinn;
+pub mod connection_handle;
 pub mod context;
 pub mod datagram_channel;
 pub mod datagram_dispatcher;
 pub mod dispatch;
 pub mod frame;
+pub mod heartbeat;
 pub mod identity;
+pub mod interceptor;
 pub mod mesh;
+pub mod policy;
+pub mod pool;
 pub mod quic;
+pub mod reconnect;
+pub mod router;
 pub mod server;
 pub mod stream;
+pub mod transport_options;
 pub mod trust;
 pub mod webtransport;
 
+pub use auth::{
+    AuthRequest, Authenticator, Credentials, HmacAuthenticator, Principal, TokenAuthenticator,
+};
 pub use cert::CertSource;
-pub use channel::UnisonChannel;
+pub use channel::{ResponseStream, UnisonChannel};
+pub use channel_config::{ChannelConfig, ChannelStats, OverflowPolicy, QueueStats};
+pub use channel_group::{ChannelGroup, MemberId};
 pub use client::{ClientConnectionEvent, ClientConnectionEventReceiver, ProtocolClient};
+pub use client_auth::{ClientAuth, PeerCertificate};
 pub use conn::UnisonConn;
+pub use connection_handle::{ConnectionHandle, PeerInfo};
+pub use context::ConnectionId;
 pub use datagram_channel::DatagramChannel;
+pub use heartbeat::{HeartbeatConfig, HeartbeatStats};
+pub use interceptor::{
+    InterceptContext, Interceptor, RequestTiming, TimingInterceptor, TracingInterceptor,
+};
 pub use mesh::InternalMeshKeypair;
-pub use quic::{QuicClient, QuicServer, TypedFrame, UnisonStream};
+pub use policy::{ChannelPolicy, IpNet, PolicyViolation};
+pub use pool::{ClientPool, EndpointStatus, LoadBalance, PoolConfig};
+pub use quic::{EarlyDataState, QuicClient, QuicServer, TypedFrame, UnisonStream};
+pub use reconnect::ReconnectPolicy;
+pub use router::{ChannelRouter, RequestContext};
 pub use server::{ConnectionEvent, ConnectionEventReceiver, ProtocolServer, ServerHandle};
+pub use transport_options::{CongestionController, TransportOptions};
 pub use trust::TrustAnchors;
 pub use webtransport::WebTransportServer;
+// handler が `UnisonChannel::cancellation_token` で受け取る型 (v1.0 で追加)
+pub use tokio_util::sync::CancellationToken;
 
 /// グローバルなリクエストID生成（モジュール間で一意）
 pub(crate) fn generate_request_id() -> u64 {
@@ -101,6 +132,24 @@ pub enum NetworkError {
     NotConnected,
     #[error("Unsupported transport: {0}")]
     UnsupportedTransport(String),
+    /// peer の handler が返した構造化エラー (v1.0 で追加、 `send_error` 由来)
+    #[error("Remote error {}: {}", .0.code, .0.message)]
+    Remote(ProtocolError),
+    /// peer が request をキャンセルした (v1.0 で追加、 `__cancel` 受信時)
+    #[error("Request {request_id} cancelled by peer")]
+    Cancelled { request_id: u64 },
+    /// 受信キューが満杯になり `OverflowPolicy::CloseWithError` で閉じた (v1.0 で追加)
+    #[error("{queue} queue overflowed (capacity {capacity})")]
+    QueueOverflow { queue: String, capacity: usize },
+    /// サーバーが資格情報を拒否した (v1.0 で追加、 `__auth` の失敗)
+    #[error("Unauthenticated: {0}")]
+    Unauthenticated(String),
+    /// 応答待ちの間に接続が切れ、 client が再接続中 (v1.0 で追加、 `ReconnectPolicy` 設定時)
+    ///
+    /// request は peer に届いていない可能性も処理された可能性もある。 冪等な request は
+    /// `Reconnected` event の後に同じ channel で再送できる。
+    #[error("Connection lost, reconnecting")]
+    Reconnecting,
 }
 
 impl NetworkError {
@@ -109,8 +158,8 @@ impl NetworkError {
     /// `UnisonChannel::recv()` / `recv_raw()` / `request()` は内部の sender / oneshot が
     /// drop された時に 3 種類の Protocol error を生成する:
     ///
-    /// - `"Channel closed"` — `recv()` で mpsc receiver が None を返した
-    /// - `"Raw channel closed"` — `recv_raw()` で raw mpsc receiver が None を返した
+    /// - `"Channel closed"` — `recv()` で Event キューが終端した
+    /// - `"Raw channel closed"` — `recv_raw()` で Raw キューが終端した
     /// - `"Request cancelled: channel closed"` — `request()` 中に oneshot sender が drop した
     ///
     /// これらは sender 側が request/response 完了後に正常 close した end-of-stream であり、
@@ -135,20 +184,25 @@ impl NetworkError {
     /// retry 可否やログレベルを決められる。
     pub fn category(&self) -> ErrorCategory {
         match self {
-            // トランスポート層: QUIC / 接続 / トランスポート種別
+            // トランスポート層: QUIC / 接続 / トランスポート種別 / 再接続中
             NetworkError::Connection(_)
             | NetworkError::Quic(_)
             | NetworkError::NotConnected
-            | NetworkError::UnsupportedTransport(_) => ErrorCategory::Transport,
+            | NetworkError::UnsupportedTransport(_)
+            | NetworkError::Reconnecting => ErrorCategory::Transport,
             // プロトコル層: 不正パケット / スキーマ不整合 / チャネル状態 / シリアライズ
             NetworkError::Protocol(_)
             | NetworkError::Serialization(_)
             | NetworkError::Codec(_)
             | NetworkError::FrameSerialization(_) => ErrorCategory::Protocol,
-            // アプリケーション層: handler が見つからない (caller 指定ミス)
-            NetworkError::HandlerNotFound { .. } => ErrorCategory::Application,
-            // リソース層: timeout (quota / rate-limit もここに将来追加)
-            NetworkError::Timeout => ErrorCategory::Resource,
+            // アプリケーション層: handler が見つからない (caller 指定ミス) / handler の
+            // 構造化エラー / peer の cancel / 認証の拒否
+            NetworkError::HandlerNotFound { .. }
+            | NetworkError::Remote(_)
+            | NetworkError::Cancelled { .. }
+            | NetworkError::Unauthenticated(_) => ErrorCategory::Application,
+            // リソース層: timeout / 受信キュー溢れ (quota / rate-limit もここに将来追加)
+            NetworkError::Timeout | NetworkError::QueueOverflow { .. } => ErrorCategory::Resource,
         }
     }
 }
@@ -183,6 +237,31 @@ impl ProtocolMessage {
         UnisonPacket::new(payload_bytes)
     }
 
+    /// ProtocolMessage を制御フレームに変換 (v1.0 で追加)
+    ///
+    /// `into_frame()` と同じ wire layout で、 packet header の `packet_type` だけを
+    /// [`PacketType::Control`] にする。 `__cancel` などプロトコル内部の制御メッセージ用。
+    pub fn into_control_frame(self) -> Result<ProtocolFrame, SerializationError> {
+        let proto_msg = self.into_proto();
+        let payload_bytes = proto_msg.encode_to_vec();
+        UnisonPacket::builder()
+            .packet_type(PacketType::Control)
+            .build(payload_bytes)
+    }
+
+    /// ProtocolMessage を heartbeat フレームに変換 (v1.0 で追加)
+    ///
+    /// `into_frame()` と同じ wire layout で、 packet header を [`PacketType::Heartbeat`] +
+    /// [`PacketFlags::KEEPALIVE`] にする。 `__heartbeat` の ping / pong 用。
+    pub fn into_heartbeat_frame(self) -> Result<ProtocolFrame, SerializationError> {
+        let proto_msg = self.into_proto();
+        let payload_bytes = proto_msg.encode_to_vec();
+        UnisonPacket::builder()
+            .packet_type(PacketType::Heartbeat)
+            .with_flags(PacketFlags::from_bits(PacketFlags::KEEPALIVE))
+            .build(payload_bytes)
+    }
+
     /// フレームから ProtocolMessage を復元
     pub fn from_frame(frame: &ProtocolFrame) -> Result<Self, SerializationError> {
         let payload_bytes = frame.payload()?;
@@ -289,13 +368,59 @@ impl MessageType {
 }
 
 /// プロトコルエラー
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `UnisonChannel::send_error` で `Error` メッセージの payload (常に JSON) として
+/// 送られ、 受信側では [`NetworkError::Remote`] として復元される。 `code` の意味は
+/// アプリケーションが決める。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProtocolError {
     pub code: i32,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
 }
 
+impl ProtocolError {
+    /// payload を Codec で decode できなかった (v1.0 で追加、 JSON-RPC の invalid params 相当)
+    pub const INVALID_PAYLOAD: i32 = -32602;
+    /// 未登録の method (v1.0 で追加、 JSON-RPC の method not found 相当)
+    ///
+    /// `details` に `{"method": ...}` を持つ場合、 受信側は
+    /// [`NetworkError::HandlerNotFound`] として復元する。
+    pub const METHOD_NOT_FOUND: i32 = -32601;
+    /// handler 内部のエラー (v1.0 で追加、 JSON-RPC の internal error 相当)
+    pub const INTERNAL: i32 = -32603;
+    /// 資格情報が無い / 不正 (v1.0 で追加、 `__auth` の失敗応答)
+    pub const UNAUTHENTICATED: i32 = -32001;
+
+    /// code と message から作成（details なし）
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// details を付与（ビルダーパターン）
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// handler 内の `?` 用 (v1.0 で追加)
+///
+/// `Remote` はそのまま中継し、 それ以外は [`ProtocolError::INTERNAL`] にする。
+impl From<NetworkError> for ProtocolError {
+    fn from(err: NetworkError) -> Self {
+        match err {
+            NetworkError::Remote(err) => err,
+            other => ProtocolError::new(ProtocolError::INTERNAL, other.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +495,7 @@ mod tests {
             (NetworkError::Quic("x".into()), Transport),
             (NetworkError::NotConnected, Transport),
             (NetworkError::UnsupportedTransport("x".into()), Transport),
+            (NetworkError::Reconnecting, Transport),
             (NetworkError::Protocol("x".into()), Protocol),
             (
                 NetworkError::FrameSerialization(SerializationError::InvalidHeader),
@@ -379,13 +505,57 @@ mod tests {
                 NetworkError::HandlerNotFound { method: "x".into() },
                 Application,
             ),
+            (
+                NetworkError::Remote(ProtocolError::new(404, "not found")),
+                Application,
+            ),
+            (NetworkError::Cancelled { request_id: 1 }, Application),
             (NetworkError::Timeout, Resource),
+            (
+                NetworkError::QueueOverflow {
+                    queue: "event".into(),
+                    capacity: 1,
+                },
+                Resource,
+            ),
         ];
         for (err, expected) in cases {
             assert_eq!(err.category(), *expected, "{err:?}");
         }
     }
 
+    /// ProtocolError の JSON 形 (= TS / Ruby client と共有する wire shape)
+    #[test]
+    fn protocol_error_json_shape() {
+        let err = ProtocolError::new(404, "not found");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({"code": 404, "message": "not found"})
+        );
+
+        let with_details = err.with_details(serde_json::json!({"id": "m1"}));
+        let json = serde_json::to_value(&with_details).unwrap();
+        assert_eq!(json["details"]["id"], "m1");
+        let restored: ProtocolError = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, with_details);
+
+        // 旧来の `{"error": ...}` payload は ProtocolError として解釈されない
+        assert!(
+            serde_json::from_value::<ProtocolError>(serde_json::json!({"error": "x"})).is_err()
+        );
+    }
+
+    /// handler 内の `?` で NetworkError → ProtocolError に変換できること
+    #[test]
+    fn protocol_error_from_network_error() {
+        let relayed: ProtocolError = NetworkError::Remote(ProtocolError::new(409, "x")).into();
+        assert_eq!(relayed, ProtocolError::new(409, "x"));
+
+        let internal: ProtocolError = NetworkError::Timeout.into();
+        assert_eq!(internal.code, ProtocolError::INTERNAL);
+        assert_eq!(internal.message, "Timeout error");
+    }
+
     /// ErrorCategory の文字列表現が TS SDK の値と一致すること
     #[test]
     fn error_category_str_values() {
@@ -395,6 +565,43 @@ mod tests {
         assert_eq!(ErrorCategory::Resource.as_str(), "resource");
     }
 
+    /// 制御フレームは PacketType::Control で、 payload は通常フレームと同じく復元できる
+    #[test]
+    fn protocol_message_control_frame_round_trip() {
+        let original = ProtocolMessage::new_encoded(
+            7,
+            "__cancel".to_string(),
+            MessageType::Event,
+            b"{}".to_vec(),
+        );
+
+        let frame = original.clone().into_control_frame().unwrap();
+        assert_eq!(frame.header().unwrap().packet_type(), PacketType::Control);
+        let restored = ProtocolMessage::from_frame(&frame).unwrap();
+        assert_eq!(restored.id, 7);
+        assert_eq!(restored.method, "__cancel");
+        assert_eq!(restored.msg_type, MessageType::Event);
+    }
+
+    /// heartbeat フレームは PacketType::Heartbeat + KEEPALIVE で、 payload は復元できる
+    #[test]
+    fn protocol_message_heartbeat_frame_round_trip() {
+        let original = ProtocolMessage::new_encoded(
+            3,
+            "__heartbeat".to_string(),
+            MessageType::Request,
+            b"{}".to_vec(),
+        );
+
+        let frame = original.into_heartbeat_frame().unwrap();
+        let header = frame.header().unwrap();
+        assert_eq!(header.packet_type(), PacketType::Heartbeat);
+        assert!(header.flags().is_keepalive());
+        let restored = ProtocolMessage::from_frame(&frame).unwrap();
+        assert_eq!(restored.id, 3);
+        assert_eq!(restored.method, "__heartbeat");
+    }
+
     /// 各 MessageType variant が wire を通って同じ variant で戻ること
     #[test]
     fn message_type_proto_round_trip_all_variants() {
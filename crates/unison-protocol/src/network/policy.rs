@@ -0,0 +1,333 @@
+//! channel 単位のアクセスポリシー (v1.0 で追加)
+//!
+//! KDL schema の channel 定義に並べて書いた規則を、 サーバーが `__channel:` の open 時に
+//! [`ConnectionContext`] の属性 (= 接続元アドレス、 principal / アプリが付与した role) と
+//! 照合する。 満たさない open は `forbidden` で nack される。
+//!
+//! ```kdl
+//! channel "admin" from="client" lifetime="persistent" require-role="ops,sre" allow-from="10.0.0.0/8,::1" {
+//!     request "Drain" { returns "Ack" }
+//! }
+//! ```
+//!
+//! ```rust,ignore
+//! // schema の全 channel のポリシーを読み込む
+//! server.load_channel_policies(&schema).await?;
+//!
+//! // コードで直接設定する
+//! server
+//!     .set_channel_policy("admin", ChannelPolicy::new().require_role("ops"))
+//!     .await;
+//! ```
+//!
+//! - `require-role`: カンマ区切り。 いずれか 1 つを持てば通る (= any-of)。
+//! - `allow-from`: カンマ区切りの CIDR。 prefix を省略したアドレスは単一ホスト扱い。
+//!   IPv4-mapped IPv6 (`::ffff:10.0.0.1`) は IPv4 として照合する。
+//! - 両方あるときは両方を満たす必要がある (= `allow-from` を先に判定)。
+//! - 判定は server が受ける stream channel の open のみ。 datagram channel と
+//!   サーバー発信の channel には掛からない (= `unison schema-lint` が検出する)。
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use super::context::ConnectionContext;
+
+/// [`ChannelPolicy::check`] が拒否した理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// 満たさなかった規則 (= `"require-role"` / `"allow-from"`)
+    pub rule: &'static str,
+    /// 人が読むための説明 (= nack payload の `detail`)
+    pub detail: String,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.detail)
+    }
+}
+
+/// channel open の可否を決める規則
+///
+/// 空の規則は制限なし。 いずれも any-of で評価する。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelPolicy {
+    /// 必要な role (= いずれか 1 つ)
+    pub required_roles: Vec<String>,
+    /// 許可する接続元ネットワーク (= いずれか 1 つに含まれる)
+    pub allowed_sources: Vec<IpNet>,
+}
+
+impl ChannelPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 必要な role を追加（ビルダーパターン）
+    pub fn require_role(mut self, role: impl Into<String>) -> Self {
+        self.required_roles.push(role.into());
+        self
+    }
+
+    /// 許可する接続元ネットワークを追加（ビルダーパターン）
+    pub fn allow_from(mut self, net: IpNet) -> Self {
+        self.allowed_sources.push(net);
+        self
+    }
+
+    /// KDL の `require-role` / `allow-from` 属性値から組み立てる
+    ///
+    /// どちらも `None` なら `Ok(None)`。 空要素や解釈できない CIDR は `Err`。
+    pub fn from_attributes(
+        require_role: Option<&str>,
+        allow_from: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        if require_role.is_none() && allow_from.is_none() {
+            return Ok(None);
+        }
+        let mut policy = Self::new();
+        if let Some(roles) = require_role {
+            for role in split_list(roles, "require-role")? {
+                policy = policy.require_role(role);
+            }
+        }
+        if let Some(sources) = allow_from {
+            for source in split_list(sources, "allow-from")? {
+                policy = policy.allow_from(source.parse()?);
+            }
+        }
+        Ok(Some(policy))
+    }
+
+    /// 制限が無いか
+    pub fn is_empty(&self) -> bool {
+        self.required_roles.is_empty() && self.allowed_sources.is_empty()
+    }
+
+    /// 接続がこのポリシーを満たすか判定する
+    ///
+    /// `allow-from` は接続元アドレスが不明な接続 (= クライアント側の context) を拒否する。
+    pub fn check(&self, ctx: &ConnectionContext) -> Result<(), PolicyViolation> {
+        if !self.allowed_sources.is_empty() {
+            let Some(addr) = ctx.remote_addr() else {
+                return Err(PolicyViolation {
+                    rule: "allow-from",
+                    detail: "remote address unknown".into(),
+                });
+            };
+            if !self
+                .allowed_sources
+                .iter()
+                .any(|net| net.contains(addr.ip()))
+            {
+                return Err(PolicyViolation {
+                    rule: "allow-from",
+                    detail: format!("{} is not in an allowed network", addr.ip()),
+                });
+            }
+        }
+        if !self.required_roles.is_empty()
+            && !self.required_roles.iter().any(|role| ctx.has_role(role))
+        {
+            return Err(PolicyViolation {
+                rule: "require-role",
+                detail: format!("requires one of roles [{}]", self.required_roles.join(", ")),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ChannelPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.required_roles.is_empty() {
+            parts.push(format!("require-role={}", self.required_roles.join(",")));
+        }
+        if !self.allowed_sources.is_empty() {
+            let sources: Vec<String> = self.allowed_sources.iter().map(|n| n.to_string()).collect();
+            parts.push(format!("allow-from={}", sources.join(",")));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+fn split_list<'a>(value: &'a str, attr: &str) -> Result<Vec<&'a str>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .map(|item| {
+            if item.is_empty() {
+                Err(format!("{attr}=\"{value}\" has an empty entry"))
+            } else {
+                Ok(item)
+            }
+        })
+        .collect()
+}
+
+/// IP ネットワーク (= アドレスと prefix 長、 `"10.0.0.0/8"` / `"::1"` 形式)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNet {
+    /// `prefix_len` がアドレス長を超えると `Err`
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max {
+            return Err(format!(
+                "prefix length /{prefix_len} exceeds /{max} for {addr}"
+            ));
+        }
+        Ok(Self { addr, prefix_len })
+    }
+
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// `ip` がこのネットワークに含まれるか (= IPv4-mapped IPv6 は IPv4 として扱う)
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_matches(&net.octets(), &ip.octets(), self.prefix_len)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(&net.octets(), &ip.octets(), self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(net: &[u8], ip: &[u8], prefix_len: u8) -> bool {
+    let full = (prefix_len / 8) as usize;
+    if net[..full] != ip[..full] {
+        return false;
+    }
+    let rest = prefix_len % 8;
+    if rest == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - rest);
+    net[full] & mask == ip[full] & mask
+}
+
+impl FromStr for IpNet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, len)) => (
+                addr,
+                Some(
+                    len.parse::<u8>()
+                        .map_err(|_| format!("invalid prefix length in \"{s}\""))?,
+                ),
+            ),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("invalid IP address in \"{s}\""))?;
+        let addr = addr.to_canonical();
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        Self::new(addr, prefix_len.unwrap_or(max))
+    }
+}
+
+impl fmt::Display for IpNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::auth::Principal;
+
+    fn ctx_from(addr: &str) -> ConnectionContext {
+        let ctx = ConnectionContext::new();
+        ctx.set_remote_addr(addr.parse().unwrap());
+        ctx
+    }
+
+    #[test]
+    fn ip_net_parses_and_matches_prefixes() {
+        let net: IpNet = "10.0.0.0/8".parse().unwrap();
+        assert!(net.contains("10.200.1.1".parse().unwrap()));
+        assert!(net.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!net.contains("11.0.0.1".parse().unwrap()));
+        assert!(!net.contains("::1".parse().unwrap()));
+
+        let odd: IpNet = "192.168.0.0/20".parse().unwrap();
+        assert!(odd.contains("192.168.15.255".parse().unwrap()));
+        assert!(!odd.contains("192.168.16.0".parse().unwrap()));
+
+        let host: IpNet = "::1".parse().unwrap();
+        assert_eq!(host.prefix_len(), 128);
+        assert!(host.contains("::1".parse().unwrap()));
+        assert_eq!(host.to_string(), "::1/128");
+
+        assert!("10.0.0.0/33".parse::<IpNet>().is_err());
+        assert!("not-an-ip".parse::<IpNet>().is_err());
+        assert!("10.0.0.0/x".parse::<IpNet>().is_err());
+    }
+
+    #[test]
+    fn from_attributes_parses_lists() {
+        assert_eq!(ChannelPolicy::from_attributes(None, None), Ok(None));
+        let policy = ChannelPolicy::from_attributes(Some("ops, sre"), Some("10.0.0.0/8,::1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(policy.required_roles, vec!["ops", "sre"]);
+        assert_eq!(policy.allowed_sources.len(), 2);
+        assert_eq!(
+            policy.to_string(),
+            "require-role=ops,sre allow-from=10.0.0.0/8,::1/128"
+        );
+        assert!(ChannelPolicy::from_attributes(Some("ops,"), None).is_err());
+        assert!(ChannelPolicy::from_attributes(None, Some("10.0.0.0/99")).is_err());
+    }
+
+    #[test]
+    fn check_roles_from_principal_and_assignment() {
+        let policy = ChannelPolicy::new().require_role("ops").require_role("sre");
+
+        let ctx = ctx_from("[::1]:4000");
+        let violation = policy.check(&ctx).unwrap_err();
+        assert_eq!(violation.rule, "require-role");
+
+        ctx.assign_role("sre");
+        assert!(policy.check(&ctx).is_ok());
+
+        let ctx = ctx_from("[::1]:4000");
+        ctx.set_principal(Principal::new("svc").with_roles(["ops"]));
+        assert!(policy.check(&ctx).is_ok());
+    }
+
+    #[test]
+    fn check_allow_from() {
+        let policy = ChannelPolicy::new().allow_from("10.0.0.0/8".parse().unwrap());
+        assert!(policy.check(&ctx_from("10.1.2.3:9")).is_ok());
+
+        let violation = policy.check(&ctx_from("[::1]:9")).unwrap_err();
+        assert_eq!(violation.rule, "allow-from");
+        assert!(violation.detail.contains("::1"), "{}", violation.detail);
+
+        // アドレス不明の接続は拒否する
+        assert!(policy.check(&ConnectionContext::new()).is_err());
+    }
+}
@@ -0,0 +1,772 @@
+//! ClientPool: 複数 endpoint への接続プールと failover (v1.0 で追加)
+//!
+//! `ProtocolClient` は 1 つの `QuicClient` (= 1 接続) しか持たず、 hostname も最初に
+//! 解決した address にしか繋がない。 `ClientPool` は複数の endpoint (= hostname なら
+//! 解決した全 address) に計 `connections` 本の接続を張っておき、 `open_channel` を
+//! [`LoadBalance`] で振り分ける:
+//!
+//! - 接続が切れた / dial に失敗した endpoint は失敗回数を数え、 `max_failures` 回続くと
+//!   `ejection_time` の間 eject する (= その endpoint の接続を閉じ、 新しい接続も張らない)
+//! - `health_check_interval` ごとに切れた接続を外し、 eject されていない endpoint へ
+//!   張り直して `connections` 本を保つ (= 接続数の少ない endpoint から順に配る)
+//! - `open_channel` が接続の問題で失敗したら、 その接続を外して別の接続で開き直す
+//!   (= caller からは failover が見えない)
+//!
+//! ```rust,ignore
+//! let pool = ClientPool::new(move || {
+//!     let transport = QuicClient::builder().trust_anchors(trust.clone()).build()?;
+//!     Ok(ProtocolClient::new(transport).with_heartbeat(HeartbeatConfig::default()))
+//! })
+//! .with_config(PoolConfig::default().with_connections(4));
+//! pool.connect(["broker-a.internal:4510", "broker-b.internal:4510"]).await?;
+//! let channel = pool.open_channel("events").await?;
+//! ```
+//!
+//! 開いた後の channel は接続と運命を共にする (= 接続が切れたら閉じ、 別の接続へは
+//! 引き継がない)。 pool 自身が failover するため、 factory の `ProtocolClient` に
+//! `with_reconnect` は要らない (= 再接続中の接続も切れたものとして外す)。 応答しない
+//! サーバーを早く見つけるには factory で `with_heartbeat` を設定する。
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use super::NetworkError;
+use super::channel::{ChannelShared, UnisonChannel};
+use super::channel_config::ChannelConfig;
+use super::client::ProtocolClient;
+use super::quic::{host_name, resolve_socket_addrs};
+
+/// 接続の振り分け方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalance {
+    /// 接続を順番に使う
+    #[default]
+    RoundRobin,
+    /// pool 経由で開いた channel が最も少ない接続を使う (= 同数なら順番)
+    LeastLoaded,
+}
+
+/// [`ClientPool`] の設定
+///
+/// ```rust
+/// use std::time::Duration;
+/// use unison::network::{LoadBalance, PoolConfig};
+///
+/// let config = PoolConfig::default()
+///     .with_connections(4)
+///     .with_load_balance(LoadBalance::LeastLoaded)
+///     .with_max_failures(0);
+/// assert_eq!(config.connections, 4);
+/// assert_eq!(config.max_failures, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    /// 保つ接続の本数 (= endpoint 全体で)
+    pub connections: usize,
+    /// `open_channel` の振り分け方
+    pub load_balance: LoadBalance,
+    /// 切れた接続を外して張り直す間隔
+    pub health_check_interval: Duration,
+    /// endpoint を eject するまでの連続失敗回数
+    pub max_failures: u32,
+    /// eject した endpoint を再び使うまでの時間
+    pub ejection_time: Duration,
+    /// 1 回の dial (= QUIC handshake + Identity / `__auth`) に掛ける時間の上限
+    pub connect_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            connections: 2,
+            load_balance: LoadBalance::RoundRobin,
+            health_check_interval: Duration::from_secs(5),
+            max_failures: 2,
+            ejection_time: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 接続の本数を設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_connections(mut self, connections: usize) -> Self {
+        self.connections = connections.max(1);
+        self
+    }
+
+    /// 振り分け方を設定（ビルダーパターン）
+    pub fn with_load_balance(mut self, load_balance: LoadBalance) -> Self {
+        self.load_balance = load_balance;
+        self
+    }
+
+    /// health check の間隔を設定（ビルダーパターン）
+    pub fn with_health_check_interval(mut self, interval: Duration) -> Self {
+        self.health_check_interval = interval;
+        self
+    }
+
+    /// eject までの連続失敗回数を設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    /// eject の時間を設定（ビルダーパターン）
+    pub fn with_ejection_time(mut self, ejection_time: Duration) -> Self {
+        self.ejection_time = ejection_time;
+        self
+    }
+
+    /// dial の上限時間を設定（ビルダーパターン）
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+}
+
+/// endpoint の状態 (= [`ClientPool::endpoints`] が返すスナップショット)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// dial 先 (= hostname は解決済みの address)
+    pub url: String,
+    /// この endpoint への接続数
+    pub connections: usize,
+    /// 連続失敗回数
+    pub failures: u32,
+    /// eject 中か
+    pub ejected: bool,
+}
+
+struct Endpoint {
+    url: String,
+    /// TLS の server name (= 解決前の hostname、 IP リテラルなら `None`)
+    server_name: Option<String>,
+    failures: u32,
+    ejected_until: Option<Instant>,
+}
+
+impl Endpoint {
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.is_some_and(|until| until > now)
+    }
+}
+
+/// pool が持つ 1 本の接続
+struct Slot {
+    id: u64,
+    /// `PoolInner::endpoints` の index
+    endpoint: usize,
+    url: String,
+    client: Arc<ProtocolClient>,
+    /// この接続で開いた channel (= `LeastLoaded` の負荷)
+    channels: Mutex<Vec<Weak<ChannelShared>>>,
+}
+
+impl Slot {
+    /// 開いている channel の数
+    fn load(&self) -> usize {
+        let mut channels = self.channels.lock().expect("channels lock poisoned");
+        channels.retain(|weak| weak.upgrade().is_some_and(|c| !c.is_closed()));
+        channels.len()
+    }
+
+    fn track(&self, channel: &UnisonChannel) {
+        self.channels
+            .lock()
+            .expect("channels lock poisoned")
+            .push(Arc::downgrade(channel.shared()));
+    }
+}
+
+type ClientFactory = Box<dyn Fn() -> anyhow::Result<ProtocolClient> + Send + Sync>;
+
+/// health check task と共有する状態
+struct PoolInner {
+    factory: ClientFactory,
+    config: std::sync::RwLock<PoolConfig>,
+    endpoints: Mutex<Vec<Endpoint>>,
+    slots: Mutex<Vec<Arc<Slot>>>,
+    next_slot_id: AtomicU64,
+    /// round-robin の位置
+    cursor: AtomicUsize,
+    /// 張り直しを直列化する (= 同じ endpoint へ重ねて dial しない)
+    fill_lock: tokio::sync::Mutex<()>,
+    /// 接続の問題を見つけたら health check を前倒しする
+    wake: Notify,
+    /// `connect()` ごとの health check task を止める
+    maintenance: Mutex<CancellationToken>,
+}
+
+/// 複数 endpoint への `ProtocolClient` の pool
+pub struct ClientPool {
+    inner: Arc<PoolInner>,
+}
+
+impl std::fmt::Debug for ClientPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientPool")
+            .field("endpoints", &self.endpoints())
+            .finish()
+    }
+}
+
+impl ClientPool {
+    /// 接続ごとに `factory` で `ProtocolClient` を作る pool を構築する
+    ///
+    /// `factory` は dial のたびに呼ばれる (= trust anchors / 資格情報 / heartbeat 等は
+    /// ここで設定する)。
+    pub fn new<F>(factory: F) -> Self
+    where
+        F: Fn() -> anyhow::Result<ProtocolClient> + Send + Sync + 'static,
+    {
+        Self {
+            inner: Arc::new(PoolInner {
+                factory: Box::new(factory),
+                config: std::sync::RwLock::new(PoolConfig::default()),
+                endpoints: Mutex::new(Vec::new()),
+                slots: Mutex::new(Vec::new()),
+                next_slot_id: AtomicU64::new(1),
+                cursor: AtomicUsize::new(0),
+                fill_lock: tokio::sync::Mutex::new(()),
+                wake: Notify::new(),
+                maintenance: Mutex::new(CancellationToken::new()),
+            }),
+        }
+    }
+
+    /// pool の設定（ビルダーパターン）
+    pub fn with_config(self, config: PoolConfig) -> Self {
+        *self.inner.config.write().expect("config lock poisoned") = config;
+        self
+    }
+
+    /// endpoint 群に接続する
+    ///
+    /// 各 endpoint は `ProtocolClient::connect` と同じ形式で、 hostname は解決した全
+    /// address を別々の endpoint として扱う (= どの address でも証明書は hostname で
+    /// 検証する)。 `connections` 本まで張れなくても 1 本でも
+    /// 繋がれば `Ok` (= 残りは health check で張り直す)。 既に接続していれば、 今の
+    /// 接続を閉じてから endpoint 群を入れ替える。
+    pub async fn connect<I, S>(&self, endpoints: I) -> Result<(), NetworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut urls: Vec<(String, Option<String>)> = Vec::new();
+        let mut last_error = None;
+        for endpoint in endpoints {
+            let endpoint = endpoint.as_ref();
+            match resolve_socket_addrs(endpoint).await {
+                Ok(addrs) => {
+                    let server_name = host_name(endpoint).map(str::to_string);
+                    for addr in addrs {
+                        let url = addr.to_string();
+                        if !urls.iter().any(|(u, _)| *u == url) {
+                            urls.push((url, server_name.clone()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to resolve pool endpoint {}: {}", endpoint, e);
+                    last_error = Some(NetworkError::Connection(e.to_string()));
+                }
+            }
+        }
+        if urls.is_empty() {
+            return Err(last_error
+                .unwrap_or_else(|| NetworkError::Connection("no pool endpoints".to_string())));
+        }
+
+        self.stop_maintenance();
+        {
+            let _fill = self.inner.fill_lock.lock().await;
+            self.inner.close_slots().await;
+            *self
+                .inner
+                .endpoints
+                .lock()
+                .expect("endpoints lock poisoned") = urls
+                .into_iter()
+                .map(|(url, server_name)| Endpoint {
+                    url,
+                    server_name,
+                    failures: 0,
+                    ejected_until: None,
+                })
+                .collect();
+        }
+        let dial_error = self.inner.fill().await;
+        if self.connections() == 0 {
+            return Err(dial_error.unwrap_or(NetworkError::NotConnected));
+        }
+
+        let token = CancellationToken::new();
+        *self
+            .inner
+            .maintenance
+            .lock()
+            .expect("maintenance lock poisoned") = token.clone();
+        PoolInner::spawn_health_check(&self.inner, token);
+        Ok(())
+    }
+
+    /// 振り分け方に従って接続を選び、 チャネルを開く
+    pub async fn open_channel(&self, channel_name: &str) -> Result<UnisonChannel, NetworkError> {
+        self.open_channel_with_config(channel_name, ChannelConfig::default())
+            .await
+    }
+
+    /// 受信キュー設定を指定してチャネルを開く
+    ///
+    /// 選んだ接続が切れていたら外して次の接続で開き直し、 全て駄目なら 1 度だけ
+    /// 張り直してから試す。 サーバーが nack した等の接続以外のエラーはそのまま返す。
+    pub async fn open_channel_with_config(
+        &self,
+        channel_name: &str,
+        config: ChannelConfig,
+    ) -> Result<UnisonChannel, NetworkError> {
+        let mut tried = HashSet::new();
+        let mut last_error = None;
+        let mut refilled = false;
+        loop {
+            let Some(slot) = self.inner.pick(&tried) else {
+                if refilled {
+                    break;
+                }
+                refilled = true;
+                self.inner.fill().await;
+                continue;
+            };
+            tried.insert(slot.id);
+            match slot
+                .client
+                .open_channel_with_config(channel_name, config.clone())
+                .await
+            {
+                Ok(channel) => {
+                    slot.track(&channel);
+                    return Ok(channel);
+                }
+                // 読みかけで接続が切れると Protocol error になるため、 接続の状態でも判定する
+                Err(e) if is_connection_error(&e) || !slot.client.is_connected().await => {
+                    tracing::warn!(
+                        "Pool connection to {} failed to open channel '{}': {}",
+                        slot.url,
+                        channel_name,
+                        e
+                    );
+                    self.inner.discard(&slot).await;
+                    self.inner.wake.notify_one();
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or(NetworkError::NotConnected))
+    }
+
+    /// 振り分け方に従って接続中の client を 1 つ選ぶ
+    ///
+    /// request 以外の API (= datagram channel 等) を使う場合に。 この client で直接
+    /// 開いた channel は `LeastLoaded` の負荷に数えない。
+    pub async fn client(&self) -> Result<Arc<ProtocolClient>, NetworkError> {
+        let mut tried = HashSet::new();
+        while let Some(slot) = self.inner.pick(&tried) {
+            tried.insert(slot.id);
+            if slot.client.is_connected().await {
+                return Ok(Arc::clone(&slot.client));
+            }
+            self.inner.discard(&slot).await;
+            self.inner.wake.notify_one();
+        }
+        Err(NetworkError::NotConnected)
+    }
+
+    /// endpoint ごとの状態
+    pub fn endpoints(&self) -> Vec<EndpointStatus> {
+        let counts = self.inner.slot_counts();
+        let now = Instant::now();
+        self.inner
+            .endpoints
+            .lock()
+            .expect("endpoints lock poisoned")
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| EndpointStatus {
+                url: endpoint.url.clone(),
+                connections: counts.get(index).copied().unwrap_or(0),
+                failures: endpoint.failures,
+                ejected: endpoint.is_ejected(now),
+            })
+            .collect()
+    }
+
+    /// pool が持つ接続の本数 (= 切れたがまだ health check で外していない分を含む)
+    pub fn connections(&self) -> usize {
+        self.inner.slots.lock().expect("slots lock poisoned").len()
+    }
+
+    /// 全ての接続を閉じ、 health check を止める
+    ///
+    /// `connect()` で再び使える。
+    pub async fn close(&self) {
+        self.stop_maintenance();
+        let _fill = self.inner.fill_lock.lock().await;
+        self.inner.close_slots().await;
+    }
+
+    fn stop_maintenance(&self) {
+        self.inner
+            .maintenance
+            .lock()
+            .expect("maintenance lock poisoned")
+            .cancel();
+    }
+}
+
+impl Drop for ClientPool {
+    fn drop(&mut self) {
+        self.stop_maintenance();
+    }
+}
+
+impl PoolInner {
+    fn config(&self) -> PoolConfig {
+        self.config.read().expect("config lock poisoned").clone()
+    }
+
+    /// endpoint ごとの接続数 (= index は `endpoints` と同じ)
+    fn slot_counts(&self) -> Vec<usize> {
+        let mut counts = Vec::new();
+        for slot in self.slots.lock().expect("slots lock poisoned").iter() {
+            if counts.len() <= slot.endpoint {
+                counts.resize(slot.endpoint + 1, 0);
+            }
+            counts[slot.endpoint] += 1;
+        }
+        counts
+    }
+
+    /// `tried` 以外から振り分け方に従って接続を選ぶ
+    fn pick(&self, tried: &HashSet<u64>) -> Option<Arc<Slot>> {
+        let strategy = self
+            .config
+            .read()
+            .expect("config lock poisoned")
+            .load_balance;
+        let slots = self.slots.lock().expect("slots lock poisoned");
+        let candidates: Vec<&Arc<Slot>> = slots.iter().filter(|s| !tried.contains(&s.id)).collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let loads: Vec<usize> = match strategy {
+            LoadBalance::RoundRobin => vec![0; candidates.len()],
+            LoadBalance::LeastLoaded => candidates.iter().map(|s| s.load()).collect(),
+        };
+        let index = select(&loads, self.cursor.fetch_add(1, Ordering::Relaxed));
+        Some(Arc::clone(candidates[index]))
+    }
+
+    /// 次に dial する endpoint (= eject されておらず、 接続数が最も少ないもの)
+    ///
+    /// eject の期限が過ぎた endpoint は失敗回数を戻して再び候補にする。
+    fn next_endpoint(&self, skip: &HashSet<usize>) -> Option<(usize, String)> {
+        let counts = self.slot_counts();
+        let now = Instant::now();
+        let mut endpoints = self.endpoints.lock().expect("endpoints lock poisoned");
+        for endpoint in endpoints.iter_mut() {
+            if endpoint.ejected_until.is_some() && !endpoint.is_ejected(now) {
+                endpoint.ejected_until = None;
+                endpoint.failures = 0;
+            }
+        }
+        endpoints
+            .iter()
+            .enumerate()
+            .filter(|(index, endpoint)| !skip.contains(index) && !endpoint.is_ejected(now))
+            .min_by_key(|(index, _)| counts.get(*index).copied().unwrap_or(0))
+            .map(|(index, endpoint)| (index, endpoint.url.clone()))
+    }
+
+    /// endpoint の失敗を数える (= eject したら `true`)
+    fn record_failure(&self, index: usize) -> bool {
+        let config = self.config();
+        let mut endpoints = self.endpoints.lock().expect("endpoints lock poisoned");
+        let Some(endpoint) = endpoints.get_mut(index) else {
+            return false;
+        };
+        let now = Instant::now();
+        endpoint.failures += 1;
+        if endpoint.failures < config.max_failures || endpoint.is_ejected(now) {
+            return false;
+        }
+        endpoint.ejected_until = Some(now + config.ejection_time);
+        tracing::warn!(
+            "Ejecting pool endpoint {} for {:?} after {} failure(s)",
+            endpoint.url,
+            config.ejection_time,
+            endpoint.failures
+        );
+        true
+    }
+
+    fn record_success(&self, index: usize) {
+        if let Some(endpoint) = self
+            .endpoints
+            .lock()
+            .expect("endpoints lock poisoned")
+            .get_mut(index)
+        {
+            endpoint.failures = 0;
+        }
+    }
+
+    /// endpoint の失敗を数え、 eject したらその endpoint の接続を全て閉じる
+    async fn fail_endpoint(&self, index: usize) {
+        if !self.record_failure(index) {
+            return;
+        }
+        let ejected: Vec<Arc<Slot>> = {
+            let mut slots = self.slots.lock().expect("slots lock poisoned");
+            let (ejected, kept) = slots.drain(..).partition(|s| s.endpoint == index);
+            *slots = kept;
+            ejected
+        };
+        for slot in ejected {
+            let _ = slot.client.disconnect().await;
+        }
+    }
+
+    /// 切れた接続を pool から外し、 endpoint の失敗として数える
+    ///
+    /// 既に外されていれば何もしない (= 1 本の接続断を重ねて数えない)。
+    async fn discard(&self, slot: &Arc<Slot>) {
+        let removed = {
+            let mut slots = self.slots.lock().expect("slots lock poisoned");
+            let before = slots.len();
+            slots.retain(|s| s.id != slot.id);
+            slots.len() != before
+        };
+        if removed {
+            let _ = slot.client.disconnect().await;
+            self.fail_endpoint(slot.endpoint).await;
+        }
+    }
+
+    /// `connections` 本になるまで張り直す (= 最後の dial 失敗を返す)
+    ///
+    /// 1 回の呼び出しで各 endpoint の失敗は 1 度まで (= 失敗した endpoint は飛ばす)。
+    async fn fill(&self) -> Option<NetworkError> {
+        let _fill = self.fill_lock.lock().await;
+        let config = self.config();
+        let mut failed = HashSet::new();
+        let mut last_error = None;
+        while self.slots.lock().expect("slots lock poisoned").len() < config.connections {
+            let Some((index, url)) = self.next_endpoint(&failed) else {
+                break;
+            };
+            let server_name = self.endpoint_server_name(index);
+            match self.dial(&url, server_name.as_deref(), &config).await {
+                Ok(client) => {
+                    self.record_success(index);
+                    tracing::debug!("Pool connected to {}", url);
+                    self.slots
+                        .lock()
+                        .expect("slots lock poisoned")
+                        .push(Arc::new(Slot {
+                            id: self.next_slot_id.fetch_add(1, Ordering::Relaxed),
+                            endpoint: index,
+                            url,
+                            client: Arc::new(client),
+                            channels: Mutex::new(Vec::new()),
+                        }));
+                }
+                Err(e) => {
+                    tracing::warn!("Pool failed to connect to {}: {}", url, e);
+                    failed.insert(index);
+                    self.fail_endpoint(index).await;
+                    last_error = Some(e);
+                }
+            }
+        }
+        last_error
+    }
+
+    fn endpoint_server_name(&self, index: usize) -> Option<String> {
+        self.endpoints
+            .lock()
+            .expect("endpoints lock poisoned")
+            .get(index)
+            .and_then(|endpoint| endpoint.server_name.clone())
+    }
+
+    /// 1 本 dial する (= `connect_timeout` で打ち切る)
+    async fn dial(
+        &self,
+        url: &str,
+        server_name: Option<&str>,
+        config: &PoolConfig,
+    ) -> Result<ProtocolClient, NetworkError> {
+        let client = (self.factory)().map_err(|e| NetworkError::Connection(e.to_string()))?;
+        let connect = async {
+            match server_name {
+                Some(server_name) => client.connect_with_server_name(url, server_name).await,
+                None => client.connect(url).await,
+            }
+        };
+        match tokio::time::timeout(config.connect_timeout, connect).await {
+            Ok(Ok(())) => Ok(client),
+            Ok(Err(e)) => Err(e),
+            Err(_) => {
+                // 打ち切った試行の張りかけの接続も捨てる
+                let _ = client.disconnect().await;
+                Err(NetworkError::Timeout)
+            }
+        }
+    }
+
+    /// 切れた接続を外してから張り直す
+    async fn health_check(&self) {
+        let slots: Vec<Arc<Slot>> = self.slots.lock().expect("slots lock poisoned").clone();
+        for slot in slots {
+            if !slot.client.is_connected().await {
+                tracing::debug!("Pool connection to {} is gone", slot.url);
+                self.discard(&slot).await;
+            }
+        }
+        self.fill().await;
+    }
+
+    async fn close_slots(&self) {
+        let slots: Vec<Arc<Slot>> = self
+            .slots
+            .lock()
+            .expect("slots lock poisoned")
+            .drain(..)
+            .collect();
+        for slot in slots {
+            let _ = slot.client.disconnect().await;
+        }
+    }
+
+    /// `health_check_interval` ごと (と `wake` 時) に health check を回す task
+    ///
+    /// pool は弱参照で持ち、 drop されたら止まる。
+    fn spawn_health_check(this: &Arc<Self>, token: CancellationToken) {
+        let pool = Arc::downgrade(this);
+        tokio::spawn(async move {
+            loop {
+                let Some(inner) = pool.upgrade() else {
+                    return;
+                };
+                let interval = inner.config().health_check_interval;
+                tokio::select! {
+                    _ = token.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = inner.wake.notified() => {}
+                }
+                inner.health_check().await;
+            }
+        });
+    }
+}
+
+/// 接続を替えれば成功し得るエラーか
+fn is_connection_error(e: &NetworkError) -> bool {
+    matches!(
+        e,
+        NetworkError::NotConnected
+            | NetworkError::Connection(_)
+            | NetworkError::Quic(_)
+            | NetworkError::Timeout
+            | NetworkError::Reconnecting
+    )
+}
+
+/// `loads` が最小の index を `cursor` の位置から順に探す (= 全て同じなら round-robin)
+fn select(loads: &[usize], cursor: usize) -> usize {
+    let len = loads.len();
+    (0..len)
+        .map(|i| (cursor + i) % len)
+        .min_by_key(|&i| loads[i])
+        .expect("select() needs at least one candidate")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_with_endpoints(config: PoolConfig, urls: &[&str]) -> ClientPool {
+        let pool = ClientPool::new(ProtocolClient::new_default).with_config(config);
+        *pool.inner.endpoints.lock().unwrap() = urls
+            .iter()
+            .map(|url| Endpoint {
+                url: url.to_string(),
+                server_name: None,
+                failures: 0,
+                ejected_until: None,
+            })
+            .collect();
+        pool
+    }
+
+    #[test]
+    fn select_rotates_on_ties_and_prefers_least_loaded() {
+        let even = [0, 0, 0];
+        let picks: Vec<usize> = (0..4).map(|cursor| select(&even, cursor)).collect();
+        assert_eq!(picks, vec![0, 1, 2, 0]);
+
+        let loads = [3, 1, 2, 1];
+        assert_eq!(select(&loads, 0), 1);
+        assert_eq!(select(&loads, 2), 3);
+    }
+
+    #[test]
+    fn endpoint_is_ejected_after_max_failures() {
+        let pool = pool_with_endpoints(
+            PoolConfig::default()
+                .with_max_failures(2)
+                .with_ejection_time(Duration::from_secs(3600)),
+            &["[::1]:1", "[::1]:2"],
+        );
+        assert!(!pool.inner.record_failure(0));
+        assert!(pool.inner.record_failure(0));
+
+        let status = pool.endpoints();
+        assert!(status[0].ejected);
+        assert_eq!(status[0].failures, 2);
+        assert!(!status[1].ejected);
+        assert_eq!(
+            pool.inner.next_endpoint(&HashSet::new()),
+            Some((1, "[::1]:2".to_string()))
+        );
+        assert_eq!(pool.inner.next_endpoint(&HashSet::from([1])), None);
+    }
+
+    #[test]
+    fn ejected_endpoint_is_readmitted_after_ejection_time() {
+        let pool = pool_with_endpoints(
+            PoolConfig::default()
+                .with_max_failures(1)
+                .with_ejection_time(Duration::ZERO),
+            &["[::1]:1"],
+        );
+        assert!(pool.inner.record_failure(0));
+        assert_eq!(
+            pool.inner.next_endpoint(&HashSet::new()),
+            Some((0, "[::1]:1".to_string()))
+        );
+        assert_eq!(pool.endpoints()[0].failures, 0);
+    }
+}
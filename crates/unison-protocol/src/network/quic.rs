@@ -9,13 +9,17 @@ use anyhow::{Context, Result};
 use quinn::{ClientConfig, Connection, Endpoint, ServerConfig};
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use rustls::{ClientConfig as RustlsClientConfig, ServerConfig as RustlsServerConfig};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock, mpsc, oneshot};
+use tokio::sync::{Mutex, RwLock, broadcast, mpsc, oneshot, watch};
 use tracing::{error, info, warn};
 
 use super::conn::UnisonConn;
 use super::dispatch::{client_accept_bi_loop, handle_connection};
+use super::identity::ChannelUpdate;
+use super::server::ChannelHandlerMap;
+use super::transport_options::TransportOptions;
 use super::{ProtocolMessage, context::ConnectionContext, server::ProtocolServer};
 
 // 後方互換: typed-frame wire I/O と handler-facing stream 型は専用モジュールへ
@@ -33,6 +37,29 @@ pub const DEFAULT_KEY_PATH: &str = "assets/certs/private_key.der";
 /// Default port for QUIC connections
 const DEFAULT_PORT: u16 = 8080;
 
+/// `QuicClient::connect` 全体 (= 全候補の handshake) の既定の上限 (v1.0 で追加)
+pub const DEFAULT_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// happy-eyeballs で次の候補の dial を始めるまでの既定の間隔 (v1.0 で追加、 RFC 8305 の推奨値)
+pub const DEFAULT_CONNECTION_ATTEMPT_DELAY: std::time::Duration =
+    std::time::Duration::from_millis(250);
+
+/// client が提示する cert chain (= TLS 設定の使い回しの key)
+type ClientCertChain = Option<Vec<CertificateDer<'static>>>;
+
+/// 現在の接続の 0-RTT の状態 (v1.0 で追加、 [`QuicClient::early_data`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarlyDataState {
+    /// 0-RTT を使っていない (= 無効、 resumption できる session が無い、 未接続)
+    NotUsed,
+    /// 0-RTT で送り始めたが handshake が終わっていない (= 送ったデータは replay され得る)
+    Pending,
+    /// handshake が終わり、 サーバーが 0-RTT のデータを受け入れた
+    Accepted,
+    /// handshake が終わり、 サーバーが 0-RTT のデータを捨てた (= 0-RTT で開いた stream は失敗する)
+    Rejected,
+}
+
 /// アドレス文字列を SocketAddr に解決する共通関数。
 ///
 /// IPv6 / IPv4 リテラル + DNS hostname を受け付け、必要に応じて DNS 解決する。
@@ -47,25 +74,38 @@ const DEFAULT_PORT: u16 = 8080;
 /// - `https://host:port` / `http://host:port` / `quic://host:port` — scheme prefix を strip
 ///
 /// DNS 解決時は最初の resolved address を返す (IPv4/IPv6 どちらでも、リゾルバの順)。
+/// 全ての address が必要な場合は [`resolve_socket_addrs`] を使う。
 async fn resolve_socket_addr(addr: &str) -> Result<SocketAddr> {
+    let addrs = resolve_socket_addrs(addr).await?;
+    addrs
+        .into_iter()
+        .next()
+        .with_context(|| format!("アドレスを解決できませんでした: {}", addr))
+}
+
+/// アドレス文字列を全ての SocketAddr に解決する (v1.0 で追加、 `ClientPool` 用)
+///
+/// 対応形式は [`resolve_socket_addr`] と同じ。 リテラルは 1 件、 DNS hostname は
+/// リゾルバが返した全 address (= 重複は除く、 リゾルバの順) を返す。
+pub(crate) async fn resolve_socket_addrs(addr: &str) -> Result<Vec<SocketAddr>> {
     // URL scheme 剥がし
     let addr = strip_scheme(addr);
 
     // 1. IPv4/IPv6 リテラル + port を直接 parse
     if let Ok(socket_addr) = addr.parse::<SocketAddr>() {
-        return Ok(socket_addr);
+        return Ok(vec![socket_addr]);
     }
 
     // 2. port のみ ("8080") → IPv6 ループバック (後方互換)
     if let Ok(port) = addr.parse::<u16>() {
-        return Ok(SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], port)));
+        return Ok(vec![SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], port))]);
     }
 
     // 3. IPv6 リテラル、port なし ("::1")
     if addr.contains(':') && !addr.contains('[') && !addr.contains('.') {
         let with_port = format!("[{}]:{}", addr, DEFAULT_PORT);
         if let Ok(sa) = with_port.parse::<SocketAddr>() {
-            return Ok(sa);
+            return Ok(vec![sa]);
         }
     }
 
@@ -89,7 +129,7 @@ async fn resolve_socket_addr(addr: &str) -> Result<SocketAddr> {
         } else {
             DEFAULT_PORT
         };
-        return Ok(SocketAddr::from((ipv6, port)));
+        return Ok(vec![SocketAddr::from((ipv6, port))]);
     }
 
     // 5. DNS hostname (host or host:port)
@@ -98,11 +138,127 @@ async fn resolve_socket_addr(addr: &str) -> Result<SocketAddr> {
     } else {
         format!("{}:{}", addr, DEFAULT_PORT)
     };
-    let mut iter = tokio::net::lookup_host(&lookup_target)
+    let mut addrs = Vec::new();
+    for sa in tokio::net::lookup_host(&lookup_target)
         .await
-        .with_context(|| format!("DNS lookup 失敗: {}", lookup_target))?;
-    iter.next()
-        .with_context(|| format!("アドレスを解決できませんでした: {}", lookup_target))
+        .with_context(|| format!("DNS lookup 失敗: {}", lookup_target))?
+    {
+        if !addrs.contains(&sa) {
+            addrs.push(sa);
+        }
+    }
+    if addrs.is_empty() {
+        anyhow::bail!("アドレスを解決できませんでした: {}", lookup_target);
+    }
+    Ok(addrs)
+}
+
+/// 候補 address の family を交互に並べる (RFC 8305 §4)
+///
+/// 先頭の family (= リゾルバが最も優先した address の family) から始め、 各 family
+/// 内の順序は保つ。 重複は除く。
+fn interleave_families(addrs: &[SocketAddr]) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else {
+        return Vec::new();
+    };
+    let (mut preferred, mut other): (Vec<SocketAddr>, Vec<SocketAddr>) = (Vec::new(), Vec::new());
+    for addr in addrs {
+        let family = if addr.is_ipv4() == first.is_ipv4() {
+            &mut preferred
+        } else {
+            &mut other
+        };
+        if !family.contains(addr) {
+            family.push(*addr);
+        }
+    }
+    let mut preferred = preferred.into_iter();
+    let mut other = other.into_iter();
+    let mut ordered = Vec::new();
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => return ordered,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+/// 候補へ `attempt_delay` ずつずらして並行に dial し、 最初に確立した接続を返す
+///
+/// 試行が失敗したら待たずに次の候補を始める。 全ての試行が失敗したら最後のエラーを返す
+/// (= `candidates` は空でないこと)。
+/// 負けた試行は future ごと drop する (= handshake 中の接続と endpoint を捨てる)。
+///
+/// 各候補は TLS の server name (= SNI と証明書検証に使う名前) と組にして渡す。
+/// `zero_rtt` なら resumption できる候補は handshake を待たずに 0-RTT の接続として返し、
+/// handshake の結果を待つ [`quinn::ZeroRttAccepted`] を添える。 0-RTT の接続は相手が
+/// 応答する前に「確立」するので、 呼び出し側は候補が 1 つのときだけ `zero_rtt` にする
+/// (= 応答しない候補が race に勝たないように)。
+async fn race_connect(
+    candidates: &[(SocketAddr, String)],
+    client_config: ClientConfig,
+    attempt_delay: std::time::Duration,
+    zero_rtt: bool,
+) -> Result<(Endpoint, Connection, Option<quinn::ZeroRttAccepted>)> {
+    use futures_util::stream::{FuturesUnordered, StreamExt};
+
+    let attempt = |(addr, server_name): &(SocketAddr, String)| {
+        let (addr, server_name) = (*addr, server_name.clone());
+        let client_config = client_config.clone();
+        async move {
+            // bind addr は target family に揃える (IPv4 target には 0.0.0.0、IPv6 target には [::])
+            let bind_addr: SocketAddr = match addr {
+                SocketAddr::V4(_) => "0.0.0.0:0".parse().unwrap(),
+                SocketAddr::V6(_) => "[::]:0".parse().unwrap(),
+            };
+            let mut endpoint = Endpoint::client(bind_addr)?;
+            endpoint.set_default_client_config(client_config);
+            let connecting = endpoint.connect(addr, &server_name)?;
+            let connecting = if zero_rtt {
+                match connecting.into_0rtt() {
+                    Ok((connection, accepted)) => {
+                        return anyhow::Ok((endpoint, connection, Some(accepted)));
+                    }
+                    // resumption できる session が無い
+                    Err(connecting) => connecting,
+                }
+            } else {
+                connecting
+            };
+            let connection = connecting
+                .await
+                .with_context(|| format!("Failed to establish QUIC connection to {}", addr))?;
+            anyhow::Ok((endpoint, connection, None))
+        }
+    };
+
+    let mut pending = candidates.iter();
+    let mut attempts = FuturesUnordered::new();
+    if let Some(addr) = pending.next() {
+        attempts.push(attempt(addr));
+    }
+    loop {
+        let has_pending = pending.len() > 0;
+        tokio::select! {
+            Some(result) = attempts.next() => match result {
+                Ok(won) => return Ok(won),
+                Err(e) => {
+                    match pending.next() {
+                        Some(addr) => attempts.push(attempt(addr)),
+                        // 最後の試行の失敗をそのまま返す
+                        None if attempts.is_empty() => return Err(e),
+                        None => {}
+                    }
+                    warn!("QUIC connection attempt failed: {:#}", e);
+                }
+            },
+            _ = tokio::time::sleep(attempt_delay), if has_pending => {
+                if let Some(addr) = pending.next() {
+                    attempts.push(attempt(addr));
+                }
+            }
+        }
+    }
 }
 
 /// `https://` / `http://` / `quic://` 前置詞を取り除く
@@ -129,6 +285,22 @@ fn has_port(addr: &str) -> bool {
     false
 }
 
+/// アドレス文字列の hostname 部分 (= IP リテラルや port のみなら `None`)
+pub(crate) fn host_name(addr: &str) -> Option<&str> {
+    let addr = strip_scheme(addr);
+    if addr.starts_with('[') || addr.parse::<u16>().is_ok() {
+        return None;
+    }
+    let host = match addr.rfind(':') {
+        Some(colon) if has_port(addr) => &addr[..colon],
+        _ => addr,
+    };
+    if host.is_empty() || host.parse::<std::net::IpAddr>().is_ok() {
+        return None;
+    }
+    Some(host)
+}
+
 /// QUIC client implementation
 pub struct QuicClient {
     endpoint: Mutex<Option<Endpoint>>,
@@ -141,12 +313,38 @@ pub struct QuicClient {
     identity_tx: Arc<Mutex<Option<oneshot::Sender<ProtocolMessage>>>>,
     /// レスポンス受信タスクのハンドルを管理
     response_tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+    /// サーバー発信 channel の handler（v1.0 で追加、 name → handler）
+    channel_handlers: ChannelHandlerMap,
+    /// 接続コンテキスト（サーバー発信 channel の handler に渡す）
+    context: Arc<ConnectionContext>,
+    /// サーバーの `__goaway` 通知（v1.0 で追加、 値は drain の deadline）
+    goaway_tx: broadcast::Sender<std::time::Duration>,
+    /// サーバーから push されたチャネル更新（v1.0 で追加）
+    channel_update_tx: broadcast::Sender<ChannelUpdate>,
     /// Trust anchors used when verifying the server's certificate during connect.
     ///
     /// v0.8.0: explicit per-instance trust selection. Defaults to
     /// `TrustAnchors::SkipVerification` for backward compatibility with
     /// `QuicClient::new()` callers (will be tightened in v0.9.0).
     trust_anchors: super::trust::TrustAnchors,
+    /// Certificate presented to servers that request one (mutual TLS, v1.0).
+    client_cert: Option<super::cert::CertSource>,
+    /// QUIC transport parameters (keep-alive, idle timeout, limits, v1.0).
+    transport: TransportOptions,
+    /// Upper bound for the whole happy-eyeballs connect (v1.0).
+    connect_timeout: std::time::Duration,
+    /// Stagger between happy-eyeballs connection attempts (v1.0).
+    connection_attempt_delay: std::time::Duration,
+    /// TLS config reused across `connect` calls, keyed by the client cert
+    /// chain it presents (v1.0).
+    ///
+    /// rustls only resumes a session with the config that stored it, so
+    /// reusing it keeps the session cache valid across reconnects.
+    tls_config: std::sync::Mutex<Option<(ClientCertChain, Arc<RustlsClientConfig>)>>,
+    /// Send 0-RTT data when a resumable session exists (v1.0).
+    zero_rtt: bool,
+    /// 0-RTT state of the current connection (v1.0).
+    early_data: watch::Sender<EarlyDataState>,
 }
 
 /// Builder for [`QuicClient`] (v0.8.0+).
@@ -154,6 +352,11 @@ pub struct QuicClient {
 /// Use [`QuicClient::builder`] to construct.
 pub struct QuicClientBuilder {
     trust_anchors: Option<super::trust::TrustAnchors>,
+    client_cert: Option<super::cert::CertSource>,
+    transport: TransportOptions,
+    connect_timeout: std::time::Duration,
+    connection_attempt_delay: std::time::Duration,
+    zero_rtt: bool,
 }
 
 impl QuicClientBuilder {
@@ -163,10 +366,62 @@ impl QuicClientBuilder {
         self
     }
 
+    /// Present this certificate when the server requests one (v1.0, mutual
+    /// TLS — see [`super::client_auth::ClientAuth`]).
+    pub fn client_cert(mut self, cert: super::cert::CertSource) -> Self {
+        self.client_cert = Some(cert);
+        self
+    }
+
+    /// Set the QUIC transport parameters (v1.0). Defaults to
+    /// [`TransportOptions::default`]; validated by [`Self::build`].
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport = options;
+        self
+    }
+
+    /// Set the upper bound for [`QuicClient::connect`] across all address
+    /// candidates (v1.0). Defaults to [`DEFAULT_CONNECT_TIMEOUT`]; exceeding it
+    /// fails with [`super::NetworkError::Timeout`].
+    pub fn connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set how long a happy-eyeballs attempt may run before the next address
+    /// candidate is dialled in parallel (v1.0, RFC 8305 "Connection Attempt
+    /// Delay"). Defaults to [`DEFAULT_CONNECTION_ATTEMPT_DELAY`].
+    pub fn connection_attempt_delay(mut self, delay: std::time::Duration) -> Self {
+        self.connection_attempt_delay = delay;
+        self
+    }
+
+    /// Send 0-RTT data on reconnects (v1.0, default `false`).
+    ///
+    /// TLS sessions are always cached across `connect` calls, so reconnects
+    /// resume instead of running a full handshake. With 0-RTT enabled, a
+    /// resumed connection is usable before the handshake completes; data sent
+    /// in that window can be replayed by an attacker, so `ProtocolClient` only
+    /// opens channels listed as replay-safe early (see
+    /// `ProtocolClient::with_zero_rtt_channels`). The server must opt in too
+    /// ([`QuicServerBuilder::enable_0rtt`]). Only used when the address has a
+    /// single candidate: a 0-RTT attempt is "connected" before the peer
+    /// answers, so with several candidates the race waits for a handshake.
+    pub fn enable_0rtt(mut self, enabled: bool) -> Self {
+        self.zero_rtt = enabled;
+        self
+    }
+
     /// Build the [`QuicClient`]. If `trust_anchors` is not set, defaults to
     /// [`super::trust::TrustAnchors::SkipVerification`] for backward
     /// compatibility — a `tracing::warn!` is emitted at connect time.
+    ///
+    /// Fails if the transport options are inconsistent (see
+    /// [`TransportOptions::validate`]).
     pub fn build(self) -> Result<QuicClient> {
+        self.transport
+            .validate()
+            .map_err(|e| anyhow::anyhow!("invalid transport options: {e}"))?;
         let trust_anchors = self
             .trust_anchors
             .unwrap_or(super::trust::TrustAnchors::SkipVerification);
@@ -179,7 +434,18 @@ impl QuicClientBuilder {
             identity_rx: Arc::new(Mutex::new(None)),
             identity_tx: Arc::new(Mutex::new(None)),
             response_tasks: Arc::new(Mutex::new(Vec::new())),
+            channel_handlers: Arc::new(RwLock::new(HashMap::new())),
+            context: Arc::new(ConnectionContext::new()),
+            goaway_tx: broadcast::channel(4).0,
+            channel_update_tx: broadcast::channel(64).0,
             trust_anchors,
+            client_cert: self.client_cert,
+            transport: self.transport,
+            connect_timeout: self.connect_timeout,
+            connection_attempt_delay: self.connection_attempt_delay,
+            tls_config: std::sync::Mutex::new(None),
+            zero_rtt: self.zero_rtt,
+            early_data: watch::channel(EarlyDataState::NotUsed).0,
         })
     }
 }
@@ -189,6 +455,11 @@ impl QuicClient {
     pub fn builder() -> QuicClientBuilder {
         QuicClientBuilder {
             trust_anchors: None,
+            client_cert: None,
+            transport: TransportOptions::default(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            connection_attempt_delay: DEFAULT_CONNECTION_ATTEMPT_DELAY,
+            zero_rtt: false,
         }
     }
 
@@ -217,7 +488,18 @@ impl QuicClient {
             identity_rx: Arc::new(Mutex::new(None)),
             identity_tx: Arc::new(Mutex::new(None)),
             response_tasks: Arc::new(Mutex::new(Vec::new())),
+            channel_handlers: Arc::new(RwLock::new(HashMap::new())),
+            context: Arc::new(ConnectionContext::new()),
+            goaway_tx: broadcast::channel(4).0,
+            channel_update_tx: broadcast::channel(64).0,
             trust_anchors: super::trust::TrustAnchors::SkipVerification,
+            client_cert: None,
+            transport: TransportOptions::default(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            connection_attempt_delay: DEFAULT_CONNECTION_ATTEMPT_DELAY,
+            tls_config: std::sync::Mutex::new(None),
+            zero_rtt: false,
+            early_data: watch::channel(EarlyDataState::NotUsed).0,
         })
     }
 
@@ -226,25 +508,46 @@ impl QuicClient {
     /// v0.7.0+: operator must explicitly choose how server certs are verified.
     /// See [`crate::network::trust::TrustAnchors`] for variants.
     pub async fn configure_client_with(trust: super::trust::TrustAnchors) -> Result<ClientConfig> {
-        let rustls_client_config = trust.build_client_config()?;
-        // ClientConfig is Arc<rustls::ClientConfig> — extract and rewrap for quinn
-        let client_crypto_config: RustlsClientConfig = (*rustls_client_config).clone();
+        Self::configure_client_with_cert(trust, None).await
+    }
+
+    /// [`Self::configure_client_with`] に加え、 サーバーに提示する client cert を指定する
+    /// (v1.0 で追加、 mutual TLS)。
+    pub async fn configure_client_with_cert(
+        trust: super::trust::TrustAnchors,
+        client_cert: Option<super::cert::CertSource>,
+    ) -> Result<ClientConfig> {
+        Self::configure_client_with_transport(trust, client_cert, &TransportOptions::default())
+            .await
+    }
+
+    /// [`Self::configure_client_with_cert`] に加え、 transport 設定 (keep-alive /
+    /// idle timeout / stream 数など) を指定する (v1.0 で追加)。
+    pub async fn configure_client_with_transport(
+        trust: super::trust::TrustAnchors,
+        client_cert: Option<super::cert::CertSource>,
+        transport: &TransportOptions,
+    ) -> Result<ClientConfig> {
+        let client_cert = client_cert.map(|cert| cert.resolve()).transpose()?;
+        let rustls_client_config = trust.build_client_config_with_cert(client_cert)?;
+        Self::quic_client_config(rustls_client_config, transport)
+    }
+
+    /// rustls の設定を quinn の client 設定で包む
+    fn quic_client_config(
+        client_crypto_config: Arc<RustlsClientConfig>,
+        transport: &TransportOptions,
+    ) -> Result<ClientConfig> {
         let crypto = quinn::crypto::rustls::QuicClientConfig::try_from(client_crypto_config)?;
         let mut client_config = ClientConfig::new(Arc::new(crypto));
 
-        let mut transport_config = quinn::TransportConfig::default();
-        transport_config
-            .max_idle_timeout(Some(std::time::Duration::from_secs(60).try_into().unwrap()));
-        transport_config.keep_alive_interval(Some(std::time::Duration::from_secs(10)));
-        transport_config.max_concurrent_uni_streams(0u32.into());
-        transport_config.max_concurrent_bidi_streams(1000u32.into());
-        transport_config.initial_rtt(std::time::Duration::from_millis(100));
-        // v0.9.0: enable QUIC datagrams (= unreliable / unordered, ≤MTU). Used by
+        // v0.9.0: QUIC datagrams are enabled by the default buffers. Used by
         // [`QuicClient::send_datagram`] / [`QuicClient::recv_datagram`] for high-
         // frequency low-overhead broadcasts (e.g. 3DCG transform sync). 1300B is
         // the safe MTU upper bound (= 1500 - IP/UDP/QUIC header).
-        transport_config.datagram_receive_buffer_size(Some(1024 * 1024));
-        transport_config.datagram_send_buffer_size(1024 * 1024);
+        let transport_config = transport
+            .to_transport_config(false)
+            .map_err(|e| anyhow::anyhow!("invalid transport options: {e}"))?;
         client_config.transport_config(Arc::new(transport_config));
 
         Ok(client_config)
@@ -256,12 +559,105 @@ impl QuicClient {
     pub fn connection(&self) -> &Arc<RwLock<Option<Connection>>> {
         &self.connection
     }
+
+    /// 接続コンテキストを取得（v1.0 で追加）
+    ///
+    /// `ProtocolClient` はこの context を共有し、 サーバー発信 channel の handler にも
+    /// 同じものが渡る。
+    pub fn context(&self) -> &Arc<ConnectionContext> {
+        &self.context
+    }
+
+    /// サーバー発信 channel の handler registry（v1.0 で追加）
+    pub(crate) fn channel_handlers(&self) -> &ChannelHandlerMap {
+        &self.channel_handlers
+    }
+
+    /// サーバーの `__goaway` (= drain 開始) 通知を subscribe する（v1.0 で追加）
+    pub(crate) fn subscribe_goaway(&self) -> broadcast::Receiver<std::time::Duration> {
+        self.goaway_tx.subscribe()
+    }
+
+    /// サーバーから push されたチャネル更新を subscribe する（v1.0 で追加）
+    pub(crate) fn subscribe_channel_updates(&self) -> broadcast::Receiver<ChannelUpdate> {
+        self.channel_update_tx.subscribe()
+    }
+
+    /// TLS 設定を組み立てて使い回す (= session cache を保つ)
+    ///
+    /// client cert は connect のたびに解決し、 提示する chain が変わったとき
+    /// (= reload 等) だけ組み立て直す。
+    fn tls_config(&self) -> Result<Arc<RustlsClientConfig>> {
+        let client_cert = self
+            .client_cert
+            .clone()
+            .map(|cert| cert.resolve())
+            .transpose()?;
+        let chain = client_cert.as_ref().map(|key| key.cert.clone());
+        let mut cached = self.tls_config.lock().expect("tls_config lock poisoned");
+        if let Some((cached_chain, config)) = cached.as_ref()
+            && *cached_chain == chain
+        {
+            return Ok(Arc::clone(config));
+        }
+        let built = self
+            .trust_anchors
+            .clone()
+            .build_client_config_with_cert(client_cert)?;
+        // ClientConfig is Arc<rustls::ClientConfig> — extract and rewrap
+        let mut config: RustlsClientConfig = (*built).clone();
+        config.enable_early_data = self.zero_rtt;
+        let config = Arc::new(config);
+        *cached = Some((chain, Arc::clone(&config)));
+        Ok(config)
+    }
+
+    /// 現在の接続の 0-RTT の状態（v1.0 で追加）
+    pub fn early_data(&self) -> EarlyDataState {
+        *self.early_data.borrow()
+    }
+
+    /// 0-RTT で張った接続の handshake が終わるまで待つ（v1.0 で追加）
+    ///
+    /// 0-RTT を使っていなければすぐに返る。 返り値は [`EarlyDataState::Pending`] 以外。
+    pub async fn wait_handshake(&self) -> EarlyDataState {
+        let mut rx = self.early_data.subscribe();
+        match rx.wait_for(|state| *state != EarlyDataState::Pending).await {
+            Ok(state) => *state,
+            Err(_) => EarlyDataState::NotUsed,
+        }
+    }
+
+    /// 新しい接続の 0-RTT の状態を記録し、 handshake の結果を待つ task を起動する
+    fn track_early_data(&self, zero_rtt_accepted: Option<quinn::ZeroRttAccepted>) {
+        let Some(accepted) = zero_rtt_accepted else {
+            self.early_data.send_replace(EarlyDataState::NotUsed);
+            return;
+        };
+        self.early_data.send_replace(EarlyDataState::Pending);
+        let early_data = self.early_data.clone();
+        tokio::spawn(async move {
+            let state = if accepted.await {
+                EarlyDataState::Accepted
+            } else {
+                EarlyDataState::Rejected
+            };
+            // 待つ間に次の接続へ替わっていれば上書きしない
+            early_data.send_if_modified(|current| {
+                let pending = *current == EarlyDataState::Pending;
+                if pending {
+                    *current = state;
+                }
+                pending
+            });
+        });
+    }
 }
 
 impl QuicClient {
-    /// サーバーアドレスを解析 (IPv4 / IPv6 / DNS hostname 対応)
-    async fn parse_server_address(addr: &str) -> Result<SocketAddr> {
-        resolve_socket_addr(addr).await
+    /// サーバーアドレスを全ての候補に解析 (IPv4 / IPv6 / DNS hostname 対応)
+    async fn parse_server_addresses(addr: &str) -> Result<Vec<SocketAddr>> {
+        resolve_socket_addrs(addr).await
     }
 
     pub async fn receive(&self) -> Result<ProtocolMessage> {
@@ -293,45 +689,114 @@ impl QuicClient {
             .map_err(|_| anyhow::anyhow!("Identity sender dropped without sending"))
     }
 
+    /// サーバーへ接続する
+    ///
+    /// `url` を全ての address に解決し、 [`Self::connect_addrs`] で happy-eyeballs
+    /// (RFC 8305) 方式に dial する。 `url` が hostname なら、 それを TLS の server name
+    /// (= SNI と証明書検証に使う名前) にする。
     pub async fn connect(&self, url: &str) -> Result<()> {
         // URL を解決 (IPv4 / IPv6 / DNS hostname)
-        let addr = Self::parse_server_address(url).await?;
+        let addrs = Self::parse_server_addresses(url).await?;
+        self.connect_candidates(&addrs, host_name(url))
+            .await
+            .with_context(|| format!("Failed to connect to {}", url))
+    }
+
+    /// TLS の server name を指定してサーバーへ接続する（v1.0 で追加）
+    ///
+    /// [`Self::connect`] と同じだが、 `url` の host ではなく `server_name` で
+    /// サーバーの証明書を検証する (= 解決済みの address に dial する `ClientPool` 用)。
+    pub async fn connect_with_server_name(&self, url: &str, server_name: &str) -> Result<()> {
+        let addrs = Self::parse_server_addresses(url).await?;
+        self.connect_candidates(&addrs, Some(server_name))
+            .await
+            .with_context(|| format!("Failed to connect to {} as {}", url, server_name))
+    }
+
+    /// 候補 address 群へ happy-eyeballs (RFC 8305) 方式で接続する（v1.0 で追加）
+    ///
+    /// 候補は family を交互に並べ (= 先頭の候補の family から)、
+    /// `connection_attempt_delay` ずつずらして並行に handshake を始める (= 前の試行が
+    /// 失敗したら待たずに次を始める)。 最初に確立した接続を使い、 残りの試行は捨てる。
+    /// 全体が `connect_timeout` を超えたら [`NetworkError::Timeout`](super::NetworkError::Timeout)
+    /// (= `anyhow::Error` の中身) で失敗する。
+    ///
+    /// hostname を持たないので、 各候補の IP address で証明書を検証する
+    /// (= SkipVerification / PinnedSha256 は名前を見ないので `localhost`)。
+    pub async fn connect_addrs(&self, addrs: &[SocketAddr]) -> Result<()> {
+        self.connect_candidates(addrs, None).await
+    }
+
+    /// TLS の server name (= `host` が無ければ address から決める)
+    ///
+    /// 証明書の名前を見ない SkipVerification / PinnedSha256 では、 IP リテラルに
+    /// 従来どおり `localhost` を使う。
+    fn server_name(&self, host: Option<&str>, addr: &SocketAddr) -> String {
+        use super::trust::TrustAnchors;
+
+        match (host, &self.trust_anchors) {
+            (Some(host), _) => host.to_string(),
+            (None, TrustAnchors::SkipVerification | TrustAnchors::PinnedSha256(_)) => {
+                "localhost".to_string()
+            }
+            (None, _) => addr.ip().to_string(),
+        }
+    }
+
+    /// [`Self::connect_addrs`] の本体 (= `host` は TLS の server name)
+    async fn connect_candidates(&self, addrs: &[SocketAddr], host: Option<&str>) -> Result<()> {
+        let candidates = interleave_families(addrs);
+        if candidates.is_empty() {
+            return Err(anyhow::anyhow!("No address to connect to"));
+        }
 
         // SkipVerification は loopback 接続にのみ許可する (TS 側 `enforceTrustGate`
         // と対称)。 任意のホストに対する証明書検証スキップを防ぐ。
         if matches!(
             self.trust_anchors,
             super::trust::TrustAnchors::SkipVerification
-        ) && !addr.ip().is_loopback()
+        ) && let Some(addr) = candidates.iter().find(|a| !a.ip().is_loopback())
         {
             return Err(anyhow::anyhow!(
-                "SkipVerification is restricted to loopback; got {} (resolved from {}). \
+                "SkipVerification is restricted to loopback; got {}. \
                  Use QuicClient::builder() with an explicit TrustAnchors to connect to \
                  non-loopback hosts.",
-                addr,
-                url
+                addr
             ));
         }
 
         // v0.8.0+: builder で設定された trust_anchors を使う (default = SkipVerification、
         // builder 経由で TrustAnchors::System 等に明示変更可能)
-        let client_config = Self::configure_client_with(self.trust_anchors.clone()).await?;
-
-        // bind addr は target family に揃える (IPv4 target には 0.0.0.0、IPv6 target には [::])
-        let bind_addr: SocketAddr = match addr {
-            SocketAddr::V4(_) => "0.0.0.0:0".parse().unwrap(),
-            SocketAddr::V6(_) => "[::]:0".parse().unwrap(),
-        };
-
-        let mut endpoint = Endpoint::client(bind_addr)?;
-        endpoint.set_default_client_config(client_config);
-
-        let connection = endpoint
-            .connect(addr, "localhost")?
-            .await
-            .context("Failed to establish QUIC connection")?;
-
-        info!("Connected to QUIC server at {}", addr);
+        let client_config = Self::quic_client_config(self.tls_config()?, &self.transport)?;
+        let named: Vec<(SocketAddr, String)> = candidates
+            .iter()
+            .map(|addr| (*addr, self.server_name(host, addr)))
+            .collect();
+
+        let (endpoint, connection, zero_rtt_accepted) = tokio::time::timeout(
+            self.connect_timeout,
+            race_connect(
+                &named,
+                client_config,
+                self.connection_attempt_delay,
+                // 複数の候補は handshake の完了で勝者を決める (= happy eyeballs を優先)
+                self.zero_rtt && named.len() == 1,
+            ),
+        )
+        .await
+        .map_err(|_| anyhow::Error::new(super::NetworkError::Timeout))??;
+
+        info!(
+            "Connected to QUIC server at {} ({} candidate(s){})",
+            connection.remote_address(),
+            candidates.len(),
+            if zero_rtt_accepted.is_some() {
+                ", 0-RTT"
+            } else {
+                ""
+            }
+        );
+        self.track_early_data(zero_rtt_accepted);
 
         // Endpoint を保存（drop されると UDP ソケットが閉じて接続が切れる）
         *self.endpoint.lock().await = Some(endpoint);
@@ -348,8 +813,21 @@ impl QuicClient {
         // サーバー発信ストリームを受け付けるバックグラウンドタスクを起動
         let tx = self.tx.clone();
         let identity_tx = self.identity_tx.clone();
+        let goaway_tx = self.goaway_tx.clone();
+        let channel_update_tx = self.channel_update_tx.clone();
+        let handlers = Arc::clone(&self.channel_handlers);
+        let ctx = Arc::clone(&self.context);
         let task = tokio::spawn(async move {
-            client_accept_bi_loop(connection_for_loop, tx, identity_tx).await;
+            client_accept_bi_loop(
+                connection_for_loop,
+                tx,
+                identity_tx,
+                goaway_tx,
+                channel_update_tx,
+                handlers,
+                ctx,
+            )
+            .await;
         });
         self.response_tasks.lock().await.push(task);
 
@@ -368,6 +846,7 @@ impl QuicClient {
         if let Some(connection) = connection_guard.take() {
             connection.close(quinn::VarInt::from_u32(0), b"client disconnect");
         }
+        self.early_data.send_replace(EarlyDataState::NotUsed);
 
         // Endpoint をクリーンアップ
         self.endpoint.lock().await.take();
@@ -448,6 +927,13 @@ pub struct QuicServer {
     /// Defaults to [`super::cert::CertSource::dev_localhost`] for backward
     /// compatibility with `QuicServer::new()`.
     cert_source: super::cert::CertSource,
+    /// Client certificate policy (v1.0, mutual TLS). Defaults to
+    /// [`ClientAuth::None`](super::client_auth::ClientAuth::None).
+    client_auth: super::client_auth::ClientAuth,
+    /// QUIC transport parameters (keep-alive, idle timeout, limits, v1.0).
+    transport: TransportOptions,
+    /// Accept 0-RTT data from resuming clients (v1.0).
+    zero_rtt: bool,
 }
 
 /// Builder for [`QuicServer`] (v0.8.0+).
@@ -456,6 +942,9 @@ pub struct QuicServer {
 pub struct QuicServerBuilder {
     server: Arc<ProtocolServer>,
     cert_source: Option<super::cert::CertSource>,
+    client_auth: super::client_auth::ClientAuth,
+    transport: TransportOptions,
+    zero_rtt: bool,
 }
 
 impl QuicServerBuilder {
@@ -465,6 +954,38 @@ impl QuicServerBuilder {
         self
     }
 
+    /// Set whether clients must present a certificate, and which CAs may issue
+    /// it (v1.0, mutual TLS). The verified certificate is exposed as
+    /// `ConnectionContext::peer_certificate()`.
+    pub fn client_auth(mut self, client_auth: super::client_auth::ClientAuth) -> Self {
+        self.client_auth = client_auth;
+        self
+    }
+
+    /// Set the QUIC transport parameters (v1.0). Defaults to
+    /// [`TransportOptions::default`]; validated at `bind` time.
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport = options;
+        self
+    }
+
+    /// Accept 0-RTT data from clients resuming a TLS session (v1.0, default
+    /// `false`).
+    ///
+    /// 0-RTT data can be replayed by an attacker; only requests marked
+    /// `idempotent=#true` in the schema should be reachable before the
+    /// handshake completes. Channels not listed with
+    /// `ProtocolServer::set_zero_rtt_channels` (or loaded with
+    /// `ProtocolServer::load_zero_rtt_channels`) are nacked with `early-data`
+    /// when opened before then. Session tickets are single-use, which rejects
+    /// replays against this server instance. Ignored unless `client_auth` is
+    /// [`ClientAuth::None`](super::client_auth::ClientAuth::None), since early
+    /// data arrives before the client certificate is verified.
+    pub fn enable_0rtt(mut self, enabled: bool) -> Self {
+        self.zero_rtt = enabled;
+        self
+    }
+
     /// Build the [`QuicServer`]. If `cert_source` is not set, defaults to
     /// [`super::cert::CertSource::dev_localhost`] (DEV ONLY).
     pub fn build(self) -> QuicServer {
@@ -474,6 +995,9 @@ impl QuicServerBuilder {
             cert_source: self
                 .cert_source
                 .unwrap_or_else(super::cert::CertSource::dev_localhost),
+            client_auth: self.client_auth,
+            transport: self.transport,
+            zero_rtt: self.zero_rtt,
         }
     }
 }
@@ -484,6 +1008,9 @@ impl QuicServer {
         QuicServerBuilder {
             server,
             cert_source: None,
+            client_auth: super::client_auth::ClientAuth::None,
+            transport: TransportOptions::default(),
+            zero_rtt: false,
         }
     }
 
@@ -492,6 +1019,9 @@ impl QuicServer {
             server,
             endpoint: None,
             cert_source: super::cert::CertSource::dev_localhost(),
+            client_auth: super::client_auth::ClientAuth::None,
+            transport: TransportOptions::default(),
+            zero_rtt: false,
         }
     }
 
@@ -542,28 +1072,69 @@ impl QuicServer {
     pub async fn configure_server_with(
         cert_source: super::cert::CertSource,
     ) -> Result<ServerConfig> {
-        let certified_key = cert_source.resolve()?;
+        Self::configure_server_with_client_auth(cert_source, super::client_auth::ClientAuth::None)
+            .await
+    }
+
+    /// [`Self::configure_server_with`] に加え、 client cert の要否と CA を指定する
+    /// (v1.0 で追加、 mutual TLS)。
+    pub async fn configure_server_with_client_auth(
+        cert_source: super::cert::CertSource,
+        client_auth: super::client_auth::ClientAuth,
+    ) -> Result<ServerConfig> {
+        Self::configure_server_with_transport(
+            cert_source,
+            client_auth,
+            &TransportOptions::default(),
+        )
+        .await
+    }
+
+    /// [`Self::configure_server_with_client_auth`] に加え、 transport 設定
+    /// (keep-alive / idle timeout / stream 数など) を指定する (v1.0 で追加)。
+    pub async fn configure_server_with_transport(
+        cert_source: super::cert::CertSource,
+        client_auth: super::client_auth::ClientAuth,
+        transport: &TransportOptions,
+    ) -> Result<ServerConfig> {
+        Self::server_config(cert_source, client_auth, transport, false)
+    }
 
+    /// `zero_rtt` なら resumption した client の 0-RTT データを受け入れる
+    fn server_config(
+        cert_source: super::cert::CertSource,
+        client_auth: super::client_auth::ClientAuth,
+        transport: &TransportOptions,
+        zero_rtt: bool,
+    ) -> Result<ServerConfig> {
         // CertifiedKey holds both cert chain and signing key in a single Arc,
         // avoiding any clone_key() of the private key (zeroize-friendlier).
-        let rustls_server_config = RustlsServerConfig::builder()
-            .with_no_client_auth()
-            .with_cert_resolver(Arc::new(SingleCertResolver(certified_key)));
+        // v1.0: a reloadable source is itself the resolver, so rotations reach
+        // new handshakes without rebuilding the endpoint.
+        let resolver: Arc<dyn rustls::server::ResolvesServerCert> = match cert_source {
+            super::cert::CertSource::Reloadable(reloader) => reloader,
+            other => Arc::new(SingleCertResolver(other.resolve()?)),
+        };
+        let builder = RustlsServerConfig::builder();
+        let builder = match client_auth.build_verifier()? {
+            Some(verifier) => builder.with_client_cert_verifier(verifier),
+            None => builder.with_no_client_auth(),
+        };
+        let mut rustls_server_config = builder.with_cert_resolver(resolver);
+        if zero_rtt {
+            // quinn は 0 か u32::MAX しか受け付けない
+            rustls_server_config.max_early_data_size = u32::MAX;
+            rustls_server_config.send_half_rtt_data = true;
+        }
 
         let crypto = quinn::crypto::rustls::QuicServerConfig::try_from(rustls_server_config)?;
         let mut server_config = ServerConfig::with_crypto(Arc::new(crypto));
 
-        let mut transport_config = quinn::TransportConfig::default();
-        transport_config
-            .max_idle_timeout(Some(std::time::Duration::from_secs(60).try_into().unwrap()));
-        transport_config.keep_alive_interval(Some(std::time::Duration::from_secs(10)));
-        transport_config.max_concurrent_uni_streams(0u32.into());
-        transport_config.max_concurrent_bidi_streams(1000u32.into());
-        transport_config.initial_rtt(std::time::Duration::from_millis(100));
-        // v0.9.0: enable QUIC datagrams (= same as client side、 server-initiated
+        // v0.9.0: QUIC datagrams (= same as client side、 server-initiated
         // broadcast 用 e.g. 3DCG transform sync from server)
-        transport_config.datagram_receive_buffer_size(Some(1024 * 1024));
-        transport_config.datagram_send_buffer_size(1024 * 1024);
+        let transport_config = transport
+            .to_transport_config(false)
+            .map_err(|e| anyhow::anyhow!("invalid transport options: {e}"))?;
         server_config.transport_config(Arc::new(transport_config));
 
         Ok(server_config)
@@ -575,7 +1146,12 @@ impl QuicServer {
 
         // v0.8.0+: builder で設定された cert_source を使う (default = dev_localhost、
         // builder 経由で Provided / FromFile / internal_mesh に明示変更可能)
-        let server_config = Self::configure_server_with(self.cert_source.clone()).await?;
+        let server_config = Self::server_config(
+            self.cert_source.clone(),
+            self.client_auth.clone(),
+            &self.transport,
+            self.accepts_0rtt(),
+        )?;
         let endpoint = Endpoint::server(server_config, socket_addr)?;
 
         info!("QUIC server bound to {}", socket_addr);
@@ -588,6 +1164,26 @@ impl QuicServer {
         resolve_socket_addr(addr).await
     }
 
+    /// 0-RTT を受け入れるか (= client cert の検証より先にデータを受けるので mTLS では無効)
+    fn accepts_0rtt(&self) -> bool {
+        self.zero_rtt && matches!(self.client_auth, super::client_auth::ClientAuth::None)
+    }
+
+    /// handshake を終えて接続を受け入れる。 0-RTT が有効なら handshake を待たずに返し、
+    /// handshake の完了通知を添える
+    async fn accept_incoming(
+        &self,
+        incoming: quinn::Incoming,
+    ) -> Result<(Connection, Option<quinn::ZeroRttAccepted>)> {
+        if !self.accepts_0rtt() {
+            return Ok((incoming.await?, None));
+        }
+        match incoming.accept()?.into_0rtt() {
+            Ok((connection, accepted)) => Ok((connection, Some(accepted))),
+            Err(connecting) => Ok((connecting.await?, None)),
+        }
+    }
+
     /// バインド済みのローカルアドレスを取得
     pub fn local_addr(&self) -> Option<SocketAddr> {
         self.endpoint.as_ref().and_then(|ep| ep.local_addr().ok())
@@ -602,12 +1198,28 @@ impl QuicServer {
         info!("QUIC server listening for connections");
 
         while let Some(connecting) = endpoint.accept().await {
-            let connection = connecting.await?;
+            // v1.0: graceful shutdown 中は新規接続を拒否 (= 他の ingress の drain も含む)
+            if self.server.is_draining() {
+                info!(
+                    "Refusing connection from {} (server draining)",
+                    connecting.remote_address()
+                );
+                connecting.refuse();
+                continue;
+            }
+            // handshake の失敗 (= client cert の拒否等) は 1 接続だけの問題
+            let (connection, early_data) = match self.accept_incoming(connecting).await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("QUIC handshake failed: {}", e);
+                    continue;
+                }
+            };
             let remote_addr = connection.remote_address();
             info!("New QUIC connection from: {}", remote_addr);
 
             let server = Arc::clone(&self.server);
-            let ctx = Arc::new(ConnectionContext::new());
+            let ctx = connection_context(&connection, early_data);
             let conn: Arc<dyn UnisonConn> = Arc::new(connection);
             tokio::spawn(async move {
                 if let Err(e) = handle_connection(conn, server, ctx).await {
@@ -636,12 +1248,27 @@ impl QuicServer {
                 connecting = endpoint.accept() => {
                     match connecting {
                         Some(connecting) => {
-                            let connection = connecting.await?;
+                            // v1.0: graceful shutdown 中は新規接続を拒否
+                            if self.server.is_draining() {
+                                info!(
+                                    "Refusing connection from {} (server draining)",
+                                    connecting.remote_address()
+                                );
+                                connecting.refuse();
+                                continue;
+                            }
+                            let (connection, early_data) = match self.accept_incoming(connecting).await {
+                                Ok(accepted) => accepted,
+                                Err(e) => {
+                                    warn!("QUIC handshake failed: {}", e);
+                                    continue;
+                                }
+                            };
                             let remote_addr = connection.remote_address();
                             info!("New QUIC connection from: {}", remote_addr);
 
                             let server = Arc::clone(&self.server);
-                            let ctx = Arc::new(ConnectionContext::new());
+                            let ctx = connection_context(&connection, early_data);
                             let conn: Arc<dyn UnisonConn> = Arc::new(connection);
                             tokio::spawn(async move {
                                 if let Err(e) = handle_connection(conn, server, ctx).await {
@@ -667,6 +1294,31 @@ impl QuicServer {
     }
 }
 
+/// 受け付けた接続のコンテキストを作る (= 検証済みの client cert と 0-RTT の
+/// handshake 完了通知があれば記録、 v1.0 で追加)
+fn connection_context(
+    connection: &Connection,
+    early_data: Option<quinn::ZeroRttAccepted>,
+) -> Arc<ConnectionContext> {
+    let ctx = ConnectionContext::new();
+    if let Some(accepted) = early_data {
+        ctx.set_early_data(accepted);
+    }
+    let leaf = connection
+        .peer_identity()
+        .and_then(|identity| identity.downcast::<Vec<CertificateDer<'static>>>().ok())
+        .and_then(|chain| chain.into_iter().next());
+    if let Some(der) = leaf {
+        match super::client_auth::PeerCertificate::from_der(der) {
+            Ok(cert) => {
+                ctx.set_peer_certificate(cert);
+            }
+            Err(e) => warn!("Failed to parse client certificate: {}", e),
+        }
+    }
+    Arc::new(ctx)
+}
+
 /// Server-side cert resolver that always returns the same [`rustls::sign::CertifiedKey`].
 ///
 /// Holds the key behind a single `Arc` so the private key material exists in
@@ -851,6 +1503,15 @@ mod tests {
         assert_eq!(sa.port(), 8080);
     }
 
+    #[tokio::test]
+    async fn resolve_all_returns_every_dns_address() {
+        let addrs = resolve_socket_addrs("localhost:8080").await.unwrap();
+        assert!(!addrs.is_empty());
+        assert!(addrs.iter().all(|sa| sa.port() == 8080));
+        let literal = resolve_socket_addrs("[::1]:8080").await.unwrap();
+        assert_eq!(literal, vec!["[::1]:8080".parse::<SocketAddr>().unwrap()]);
+    }
+
     #[tokio::test]
     async fn resolve_strips_https_scheme() {
         let sa = resolve_socket_addr("https://[::1]:4510").await.unwrap();
@@ -876,6 +1537,36 @@ mod tests {
         assert!(res.is_err(), "unresolvable hostname should error");
     }
 
+    #[test]
+    fn interleave_families_alternates_starting_with_first_family() {
+        let addrs: Vec<SocketAddr> = [
+            "[2001:db8::1]:443",
+            "[2001:db8::2]:443",
+            "192.0.2.1:443",
+            "[2001:db8::1]:443",
+            "192.0.2.2:443",
+            "192.0.2.3:443",
+        ]
+        .iter()
+        .map(|a| a.parse().unwrap())
+        .collect();
+        let ordered: Vec<String> = interleave_families(&addrs)
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert_eq!(
+            ordered,
+            vec![
+                "[2001:db8::1]:443",
+                "192.0.2.1:443",
+                "[2001:db8::2]:443",
+                "192.0.2.2:443",
+                "192.0.2.3:443",
+            ]
+        );
+        assert!(interleave_families(&[]).is_empty());
+    }
+
     #[test]
     fn has_port_recognizes_ipv4_with_port() {
         assert!(has_port("127.0.0.1:8080"));
@@ -901,6 +1592,17 @@ mod tests {
         assert!(!has_port("localhost"));
     }
 
+    #[test]
+    fn host_name_keeps_hostnames_and_skips_ip_literals() {
+        assert_eq!(host_name("broker.internal:4510"), Some("broker.internal"));
+        assert_eq!(host_name("quic://broker.internal"), Some("broker.internal"));
+        assert_eq!(host_name("localhost"), Some("localhost"));
+        assert_eq!(host_name("[::1]:8080"), None);
+        assert_eq!(host_name("::1"), None);
+        assert_eq!(host_name("127.0.0.1:8080"), None);
+        assert_eq!(host_name("8080"), None);
+    }
+
     #[test]
     fn strip_scheme_removes_known_prefixes() {
         assert_eq!(strip_scheme("https://example.com:443"), "example.com:443");
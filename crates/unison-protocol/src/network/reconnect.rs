@@ -0,0 +1,193 @@
+//! `ProtocolClient` の自動再接続 (v1.0 で追加)
+//!
+//! [`ReconnectPolicy`] を `ProtocolClient::with_reconnect()` で設定すると、 client は
+//! 明示 `disconnect()` 以外の接続断を検知して同じ URL へ再接続する:
+//!
+//! 1. `Reconnecting { attempt, delay }` を fire し、 `delay` だけ待って dial する
+//!    (= `attempt_timeout` 以内に 2. まで終わらなければ失敗として数える)
+//! 2. Identity handshake と (資格情報があれば) `__auth` をやり直す
+//! 3. 接続断の時点で開いていた `UnisonChannel` を同じ名前で開き直す
+//! 4. `Reconnected { .. }` を fire する
+//!
+//! 待ち時間は `initial_backoff * multiplier^(attempt-1)` を `max_backoff` で頭打ちにし、
+//! `±jitter` の割合でばらす。 `max_attempts` 回失敗すると `ReconnectFailed` を fire して
+//! channel を閉じる。
+//!
+//! ```rust,ignore
+//! let client = ProtocolClient::new(transport)
+//!     .with_reconnect(ReconnectPolicy::default().with_max_attempts(5));
+//! client.connect("[::1]:8080").await?;
+//! let channel = client.open_channel("events").await?;
+//! // 接続断の間の request は `NetworkError::Reconnecting` で失敗し、
+//! // 再接続後は同じ `channel` をそのまま使える
+//! ```
+//!
+//! 開き直すのは stream channel のみ。 datagram channel とサーバー発信の channel は
+//! 引き継がない。 接続断の時点で応答待ちだった request は
+//! [`NetworkError::Reconnecting`](super::NetworkError::Reconnecting) で失敗する。
+
+use std::time::Duration;
+
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// 再接続の backoff 設定
+///
+/// ```rust
+/// use std::time::Duration;
+/// use unison::network::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy::default()
+///     .with_initial_backoff(Duration::from_millis(100))
+///     .with_jitter(0.0);
+/// assert_eq!(policy.backoff(1), Duration::from_millis(100));
+/// assert_eq!(policy.backoff(3), Duration::from_millis(400));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    /// 1 回目の試行までの待ち時間
+    pub initial_backoff: Duration,
+    /// 待ち時間の上限
+    pub max_backoff: Duration,
+    /// 試行ごとに待ち時間へ掛ける倍率
+    pub multiplier: f64,
+    /// 待ち時間をばらす割合 (= `0.2` なら ±20%、 `0.0..=1.0`)
+    pub jitter: f64,
+    /// 諦めるまでの試行回数 (= `None` は無制限)
+    pub max_attempts: Option<u32>,
+    /// 1 回の試行 (= dial + Identity / `__auth`) に掛ける時間の上限
+    ///
+    /// 応答の無い相手への QUIC handshake は idle timeout (= 60 秒) まで待つため、
+    /// それより短く打ち切って次の試行に進む。
+    pub attempt_timeout: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: Some(10),
+            attempt_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 1 回目の待ち時間を設定（ビルダーパターン）
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// 待ち時間の上限を設定（ビルダーパターン）
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// 倍率を設定（ビルダーパターン、1.0 未満は 1.0 に丸める）
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// jitter の割合を設定（ビルダーパターン、`0.0..=1.0` に丸める）
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 試行回数の上限を設定（ビルダーパターン）
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// 試行回数を無制限にする（ビルダーパターン）
+    pub fn unlimited(mut self) -> Self {
+        self.max_attempts = None;
+        self
+    }
+
+    /// 1 回の試行に掛ける時間の上限を設定（ビルダーパターン）
+    pub fn with_attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = timeout;
+        self
+    }
+
+    /// `attempt` 回目 (1 始まり) の試行前の待ち時間 (= jitter を掛ける前)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let secs = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let max = self.max_backoff.as_secs_f64();
+        Duration::from_secs_f64(if secs.is_finite() { secs.min(max) } else { max })
+    }
+
+    /// `attempt` 回目の試行前に実際に待つ時間 (= `backoff()` に jitter を掛けたもの)
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let base = self.backoff(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return base;
+        }
+        let mut bytes = [0u8; 4];
+        if SystemRandom::new().fill(&mut bytes).is_err() {
+            return base;
+        }
+        // [0, 1) の一様乱数を [-jitter, +jitter) に写す
+        let unit = u32::from_le_bytes(bytes) as f64 / (u32::MAX as f64 + 1.0);
+        base.mul_f64(1.0 + jitter * (2.0 * unit - 1.0))
+    }
+
+    /// `attempts` 回失敗した後にまだ試行するか
+    pub(crate) fn allows(&self, attempts: u32) -> bool {
+        self.max_attempts.is_none_or(|max| attempts < max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let policy = ReconnectPolicy::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_secs(1));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn delay_stays_within_jitter() {
+        let policy = ReconnectPolicy::default()
+            .with_initial_backoff(Duration::from_millis(1000))
+            .with_jitter(0.25);
+        for _ in 0..100 {
+            let delay = policy.delay(1);
+            assert!(delay >= Duration::from_millis(750), "{delay:?}");
+            assert!(delay < Duration::from_millis(1250), "{delay:?}");
+        }
+        assert_eq!(
+            policy.clone().with_jitter(0.0).delay(1),
+            Duration::from_millis(1000)
+        );
+    }
+
+    #[test]
+    fn max_attempts_limits_retries() {
+        let policy = ReconnectPolicy::default().with_max_attempts(3);
+        assert!(policy.allows(2));
+        assert!(!policy.allows(3));
+        assert!(policy.unlimited().allows(u32::MAX));
+    }
+}
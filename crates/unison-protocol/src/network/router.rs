@@ -0,0 +1,275 @@
+//! ChannelRouter: method 単位の型付き handler ルーター (v1.0 で追加)
+//!
+//! `register_channel` の handler は従来 `loop { channel.recv() … match msg.method }`
+//! を手書きし、 `send_response` を自前で呼んでいた。 `ChannelRouter` は method 名 →
+//! 型付き handler の表を持ち、 recv ループ・Codec での decode / encode・応答送信を
+//! 肩代わりする。
+//!
+//! ```rust,ignore
+//! let router = ChannelRouter::<JsonCodec>::new()
+//!     .on_request::<PingReq, PingResp, _, _>("Ping", |_ctx, req| async move {
+//!         Ok(PingResp { echo: req.text })
+//!     })
+//!     .on_event::<Heartbeat, _, _>("Heartbeat", |_ctx, hb| async move {
+//!         tracing::debug!(?hb, "heartbeat");
+//!     })
+//!     .with_max_concurrency(16);
+//! server.register_channel_router("ping", router).await;
+//! ```
+//!
+//! - Request は handler ごとに task を spawn して並行に処理する。 同時実行数は
+//!   `with_max_concurrency()` で制限し、 上限に達すると空きが出るまで次の受信を待つ。
+//!   その間に届いた Request は channel の Event キューに溜まり、 キューも埋まると
+//!   channel の recv ループが空きを待つ (= 受信 Request は `OverflowPolicy` で捨てない
+//!   ので、 QUIC の flow control でクライアントの送信が止まり、 channel は閉じない)。
+//! - Event は受信順を保つため recv ループ上で 1 つずつ処理する。
+//! - 未登録 method の Request には [`ProtocolError::METHOD_NOT_FOUND`] の Error を返す
+//!   (= クライアント側は [`NetworkError::HandlerNotFound`])。 未登録 Event は捨てる。
+//! - payload の decode 失敗は [`ProtocolError::INVALID_PAYLOAD`] の Error を返す。
+//! - handler の panic はその Request への [`ProtocolError::INTERNAL`] の Error になり、
+//!   channel はそのまま使える (Event handler の panic はログのみ)。
+
+use futures_util::FutureExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+use crate::codec::{Codec, Decodable, Encodable, JsonCodec};
+
+use super::channel::UnisonChannel;
+use super::channel_config::ChannelConfig;
+use super::context::ConnectionContext;
+use super::quic::UnisonStream;
+use super::{MessageType, NetworkError, ProtocolError, ProtocolMessage};
+
+/// デフォルトの Request 同時実行数（1 channel あたり）
+pub const DEFAULT_MAX_CONCURRENCY: usize = 64;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// 型消去済み Request handler (= decode → handler → 応答送信まで)
+type RequestHandler<C> = Arc<
+    dyn Fn(Arc<UnisonChannel<C>>, RequestContext, ProtocolMessage) -> BoxFuture<()> + Send + Sync,
+>;
+
+/// 型消去済み Event handler
+type EventHandler =
+    Arc<dyn Fn(Arc<ConnectionContext>, ProtocolMessage) -> BoxFuture<()> + Send + Sync>;
+
+/// Request handler に渡すコンテキスト
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// 接続単位のコンテキスト (= `register_channel` handler の `ctx` と同じ)
+    pub connection: Arc<ConnectionContext>,
+    /// 受信した Request の message id
+    pub request_id: u64,
+    /// 受信した Request の method 名
+    pub method: String,
+    /// peer の `__cancel` / 接続断で cancel される token
+    pub cancellation: CancellationToken,
+}
+
+/// method 単位の型付き handler ルーター
+pub struct ChannelRouter<C: Codec = JsonCodec> {
+    requests: HashMap<String, RequestHandler<C>>,
+    events: HashMap<String, EventHandler>,
+    max_concurrency: usize,
+    config: ChannelConfig,
+}
+
+impl<C: Codec> Default for ChannelRouter<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Codec> ChannelRouter<C> {
+    /// 空のルーターを作成（同時実行数 64、 `ChannelConfig::default()`）
+    pub fn new() -> Self {
+        Self {
+            requests: HashMap::new(),
+            events: HashMap::new(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            config: ChannelConfig::default(),
+        }
+    }
+
+    /// Request handler を登録（ビルダーパターン）
+    ///
+    /// handler の `Err(ProtocolError)` はそのまま `send_error` で返る。
+    /// `NetworkError` は `?` で [`ProtocolError::INTERNAL`] に変換できる。
+    pub fn on_request<Req, Resp, F, Fut>(mut self, method: &str, handler: F) -> Self
+    where
+        Req: Decodable<C> + Send + 'static,
+        Resp: Encodable<C> + Send + Sync + 'static,
+        F: Fn(RequestContext, Req) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Resp, ProtocolError>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let erased: RequestHandler<C> = Arc::new(
+            move |channel: Arc<UnisonChannel<C>>, ctx: RequestContext, msg: ProtocolMessage| {
+                let handler = Arc::clone(&handler);
+                Box::pin(async move {
+                    let result = match msg.decode_payload::<Req, C>() {
+                        // panic は request 単位で INTERNAL にする (= channel は生かす)
+                        Ok(req) => AssertUnwindSafe(async move { handler(ctx, req).await })
+                            .catch_unwind()
+                            .await
+                            .unwrap_or_else(|_| {
+                                warn!("Request handler for '{}' panicked", msg.method);
+                                Err(ProtocolError::new(
+                                    ProtocolError::INTERNAL,
+                                    format!("Handler for '{}' panicked", msg.method),
+                                ))
+                            }),
+                        Err(e) => Err(ProtocolError::new(
+                            ProtocolError::INVALID_PAYLOAD,
+                            format!("Invalid payload for '{}': {}", msg.method, e),
+                        )),
+                    };
+                    let sent = match result {
+                        Ok(resp) => channel.send_response(msg.id, &msg.method, &resp).await,
+                        Err(err) => channel.send_error(msg.id, err).await,
+                    };
+                    if let Err(e) = sent {
+                        debug!("Failed to reply to '{}' ({}): {}", msg.method, msg.id, e);
+                    }
+                }) as BoxFuture<()>
+            },
+        );
+        self.requests.insert(method.to_string(), erased);
+        self
+    }
+
+    /// Event handler を登録（ビルダーパターン）
+    ///
+    /// decode に失敗した Event はログを出して捨てる。
+    pub fn on_event<E, F, Fut>(mut self, method: &str, handler: F) -> Self
+    where
+        E: Decodable<C> + Send + 'static,
+        F: Fn(Arc<ConnectionContext>, E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let erased: EventHandler =
+            Arc::new(move |ctx: Arc<ConnectionContext>, msg: ProtocolMessage| {
+                let handler = Arc::clone(&handler);
+                Box::pin(async move {
+                    match msg.decode_payload::<E, C>() {
+                        Ok(event) => {
+                            let run = AssertUnwindSafe(async move { handler(ctx, event).await });
+                            if run.catch_unwind().await.is_err() {
+                                warn!("Event handler for '{}' panicked", msg.method);
+                            }
+                        }
+                        Err(e) => warn!("Dropping undecodable event '{}': {}", msg.method, e),
+                    }
+                }) as BoxFuture<()>
+            });
+        self.events.insert(method.to_string(), erased);
+        self
+    }
+
+    /// Request の同時実行数を設定（ビルダーパターン、0 は 1 に丸める）
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
+    /// 内部で構築する `UnisonChannel` の設定（ビルダーパターン）
+    pub fn with_channel_config(mut self, config: ChannelConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 登録済みの Request method 名
+    pub fn request_methods(&self) -> impl Iterator<Item = &str> {
+        self.requests.keys().map(String::as_str)
+    }
+
+    /// 登録済みの Event method 名
+    pub fn event_methods(&self) -> impl Iterator<Item = &str> {
+        self.events.keys().map(String::as_str)
+    }
+
+    /// stream 上で recv ループを回す (= `register_channel` handler の本体)
+    ///
+    /// peer が channel を閉じるまで戻らない。 処理中の Request handler の完了を
+    /// 待ってから返る (= エラーで終わる場合も handler を途中で打ち切らない)。
+    pub async fn serve(
+        &self,
+        ctx: Arc<ConnectionContext>,
+        stream: UnisonStream,
+    ) -> Result<(), NetworkError> {
+        let channel = Arc::new(UnisonChannel::<C>::with_config(stream, self.config.clone()));
+        self.serve_channel(ctx, channel).await
+    }
+
+    /// 構築済みの `UnisonChannel` 上で recv ループを回す
+    pub async fn serve_channel(
+        &self,
+        ctx: Arc<ConnectionContext>,
+        channel: Arc<UnisonChannel<C>>,
+    ) -> Result<(), NetworkError> {
+        let limit = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut tasks = JoinSet::new();
+
+        let outcome = loop {
+            let msg = match channel.recv().await {
+                Ok(msg) => msg,
+                Err(e) if e.is_normal_close() => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            // 完了済み task を回収 (= JoinSet の肥大化防止)
+            while tasks.try_join_next().is_some() {}
+
+            match msg.msg_type {
+                MessageType::Request => {
+                    let Some(handler) = self.requests.get(&msg.method) else {
+                        let err = ProtocolError::new(
+                            ProtocolError::METHOD_NOT_FOUND,
+                            format!("Handler not found for method: {}", msg.method),
+                        )
+                        .with_details(serde_json::json!({ "method": msg.method }));
+                        // `?` で抜けると JoinSet の drop で処理中の handler が abort される
+                        if let Err(e) = channel.send_error(msg.id, err).await {
+                            break Err(e);
+                        }
+                        continue;
+                    };
+                    let Ok(permit) = Arc::clone(&limit).acquire_owned().await else {
+                        break Ok(());
+                    };
+                    let request_ctx = RequestContext {
+                        connection: Arc::clone(&ctx),
+                        request_id: msg.id,
+                        method: msg.method.clone(),
+                        cancellation: channel.cancellation_token(msg.id).await.unwrap_or_default(),
+                    };
+                    let fut = handler(Arc::clone(&channel), request_ctx, msg);
+                    tasks.spawn(async move {
+                        fut.await;
+                        drop(permit);
+                    });
+                }
+                MessageType::Event => match self.events.get(&msg.method) {
+                    Some(handler) => handler(Arc::clone(&ctx), msg).await,
+                    None => debug!("No event handler for '{}', dropping", msg.method),
+                },
+                // 応答待ちの無い Response / Error (= 相手の誤送信) は無視
+                MessageType::Response | MessageType::Error => {
+                    debug!("Ignoring unsolicited {:?} '{}'", msg.msg_type, msg.method);
+                }
+            }
+        };
+
+        while tasks.join_next().await.is_some() {}
+        outcome
+    }
+}
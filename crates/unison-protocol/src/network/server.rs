@@ -1,19 +1,37 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::{RwLock, watch};
 use tokio::task::JoinHandle;
 
 use crate::codec::{Codec, Encodable, JsonCodec};
+use crate::parser::ParsedSchema;
 
 use super::NetworkError;
+use super::auth::Authenticator;
+use super::channel::UnisonChannel;
+use super::channel_config::ChannelConfig;
+use super::channel_group::ChannelGroup;
+use super::connection_handle::ConnectionHandle;
+use super::context::ConnectionId;
 use super::datagram_channel::{DatagramChannel, encode_varint};
-use super::identity::{ChannelDirection, ChannelInfo, ChannelStatus, ServerIdentity};
+use super::dispatch::HandlerOutcome;
+use super::heartbeat::HeartbeatConfig;
+use super::identity::{
+    ChannelDirection, ChannelInfo, ChannelStatus, ChannelUpdate, ServerIdentity,
+};
+use super::interceptor::{Interceptor, InterceptorChain};
+use super::policy::ChannelPolicy;
+use super::router::ChannelRouter;
 
 /// 接続イベント通知
+///
+/// v1.0 で variant を追加したため `#[non_exhaustive]` (= `match` には `_` の arm が要る)。
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum ConnectionEvent {
     /// 新しい接続が確立された
     Connected {
@@ -21,7 +39,34 @@ pub enum ConnectionEvent {
         context: Arc<super::context::ConnectionContext>,
     },
     /// 接続が切断された
-    Disconnected { remote_addr: SocketAddr },
+    Disconnected {
+        /// 切断した接続の ID（v1.0 で追加、 `Connected` の `context.connection_id` と同じ）
+        connection_id: ConnectionId,
+        remote_addr: SocketAddr,
+        /// 切断理由（v1.0 で追加、 free text。 heartbeat の dead 判定なら `"heartbeat timeout"`）
+        reason: String,
+    },
+    /// client の path migration で接続のアドレスが変わった（v1.0 で追加）
+    ///
+    /// NAT rebinding や client のネットワーク切り替えで、 同じ接続が別の address から
+    /// 届くようになったときに出る。 `connection_id` と接続の状態はそのまま引き継がれ、
+    /// `ConnectionHandle::remote_address()` / `ConnectionContext::remote_addr()` は `new` を返す。
+    PathChanged {
+        connection_id: ConnectionId,
+        old: SocketAddr,
+        new: SocketAddr,
+    },
+    /// channel handler が `Err` を返した / panic した（v1.0 で追加）
+    ///
+    /// peer の close による end-of-stream は含まない。 panic の場合 `error` は panic
+    /// message で、 peer には `__channel_error` と stream reset が届いている。
+    HandlerFailed {
+        connection_id: ConnectionId,
+        /// handler を登録した channel 名
+        channel: String,
+        error: String,
+        panicked: bool,
+    },
 }
 
 /// [`ProtocolServer::subscribe_connection_events()`] が返す接続イベントレシーバー
@@ -104,6 +149,9 @@ pub type ChannelHandler = Arc<
         + Sync,
 >;
 
+/// name → [`ChannelHandler`] の registry (= サーバー / クライアント共通、 v1.0 で型名を付与)
+pub(crate) type ChannelHandlerMap = Arc<RwLock<HashMap<String, ChannelHandler>>>;
+
 /// Datagram channel handler 型 (v0.10.0 で追加)
 ///
 /// 接続ごとに一度だけ invoke される。 `DatagramChannel<JsonCodec>` を受け取り、
@@ -114,6 +162,35 @@ pub type DatagramChannelHandler = Arc<
         + Sync,
 >;
 
+/// datagram channel event を `[varint channel_id][encoded event]` に詰める
+///
+/// `broadcast` / `ConnectionHandle::send_datagram` 共通。 `channel_name` が未登録なら
+/// `HandlerNotFound`。
+pub(crate) async fn encode_datagram<T, C>(
+    handlers: &RwLock<HashMap<String, DatagramHandlerEntry>>,
+    channel_name: &str,
+    event: &T,
+) -> Result<Vec<u8>, NetworkError>
+where
+    T: Encodable<C>,
+    C: Codec,
+{
+    let channel_id = handlers
+        .read()
+        .await
+        .get(channel_name)
+        .map(|entry| entry.channel_id)
+        .ok_or_else(|| NetworkError::HandlerNotFound {
+            method: format!("datagram channel: {}", channel_name),
+        })?;
+
+    let encoded = event.encode().map_err(NetworkError::Codec)?;
+    let mut payload = Vec::with_capacity(super::datagram_channel::VARINT_MAX_LEN + encoded.len());
+    encode_varint(channel_id, &mut payload);
+    payload.extend_from_slice(&encoded);
+    Ok(payload)
+}
+
 /// Datagram channel の registry エントリ (= name に紐づく channel_id + handler)
 pub(crate) struct DatagramHandlerEntry {
     pub(crate) channel_id: u64,
@@ -127,10 +204,17 @@ pub struct ServerHandle {
     join_handle: JoinHandle<Result<(), NetworkError>>,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
     local_addr: SocketAddr,
+    server: Arc<ProtocolServer>,
 }
 
+/// force close 後、 各接続の `Disconnected` event が出揃うのを待つ上限
+const CLOSE_GRACE: Duration = Duration::from_secs(1);
+
 impl ServerHandle {
-    /// サーバーをグレースフルにシャットダウンし、完了を待つ
+    /// endpoint を即座に閉じてシャットダウンし、完了を待つ
+    ///
+    /// 処理中の request も含めて接続を切る。 drain したい場合は
+    /// [`shutdown_graceful`](Self::shutdown_graceful) を使う。
     pub async fn shutdown(mut self) -> Result<(), NetworkError> {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(());
@@ -140,6 +224,53 @@ impl ServerHandle {
             .map_err(|e| NetworkError::Quic(format!("Server task panicked: {}", e)))?
     }
 
+    /// drain してからシャットダウンする（v1.0 で追加）
+    ///
+    /// 1. 新規接続を拒否し、 新規 channel open を `server-draining` で nack する
+    /// 2. 全接続に `__goaway` (= `deadline` 付きの drain 通知) を送る
+    /// 3. 実行中の channel handler の終了を `deadline` まで待つ
+    /// 4. 残った接続を閉じ (= 接続ごとに [`ConnectionEvent::Disconnected`])、 endpoint を閉じる
+    ///
+    /// drain は `ProtocolServer` 単位 (= 同じ server を共有する他の ingress の接続も閉じる)。
+    pub async fn shutdown_graceful(self, deadline: Duration) -> Result<(), NetworkError> {
+        let until = tokio::time::Instant::now() + deadline;
+        let server = Arc::clone(&self.server);
+        server.draining.store(true, Ordering::SeqCst);
+        tracing::info!(
+            "Draining server on {} (deadline {:?})",
+            self.local_addr,
+            deadline
+        );
+
+        let _ = tokio::time::timeout_at(until, server.announce_drain(deadline)).await;
+        let drained = tokio::time::timeout_at(until, server.wait_handlers_idle())
+            .await
+            .is_ok();
+        let reason = if drained {
+            "server shutdown"
+        } else {
+            tracing::warn!(
+                "Drain deadline exceeded with {} channel handler(s) running, force-closing",
+                server.active_handler_count()
+            );
+            "drain deadline exceeded"
+        };
+        for connection in server.connections().await {
+            connection.close(reason);
+        }
+        // handle_connection が Disconnected を emit して map から外すのを待つ
+        let _ = tokio::time::timeout(CLOSE_GRACE, async {
+            while !server.active_connections.read().await.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+
+        let result = self.shutdown().await;
+        server.draining.store(false, Ordering::SeqCst);
+        result
+    }
+
     /// サーバータスクが終了済みかどうか
     pub fn is_finished(&self) -> bool {
         self.join_handle.is_finished()
@@ -159,16 +290,62 @@ pub struct ProtocolServer {
     server_version: String,
     server_namespace: String,
     /// チャネルハンドラー（チャネル名 → ハンドラー関数）
-    channel_handlers: Arc<RwLock<HashMap<String, ChannelHandler>>>,
+    channel_handlers: ChannelHandlerMap,
     /// Datagram channel handlers (v0.10.0 で追加、 name → channel_id + handler)
     datagram_channel_handlers: Arc<RwLock<HashMap<String, DatagramHandlerEntry>>>,
-    /// Active connections (= broadcast 配信先、 remote_addr → Connection)
+    /// Active connections (= broadcast 配信先、 connection_id → handle)
     ///
     /// transport 非依存。 raw QUIC / WebTransport どちらの接続も
     /// [`UnisonConn`](super::conn::UnisonConn) trait object として保持する。
-    active_connections: Arc<RwLock<HashMap<SocketAddr, Arc<dyn super::conn::UnisonConn>>>>,
+    /// v1.0 で key を `SocketAddr` から [`ConnectionId`] に変更 (= NAT rebinding /
+    /// 再接続でアドレスが衝突しても別接続として扱える)。
+    active_connections: Arc<RwLock<HashMap<ConnectionId, ConnectionHandle>>>,
     /// 接続イベント broadcast チャネル（複数サブスクライバ対応）
     connection_event_tx: tokio::sync::broadcast::Sender<ConnectionEvent>,
+    /// graceful shutdown 中 (= 新規接続 / channel を受け付けない、 v1.0 で追加)
+    draining: Arc<AtomicBool>,
+    /// 実行中の channel handler 数 (= drain の完了判定、 v1.0 で追加)
+    active_handlers: Arc<watch::Sender<usize>>,
+    /// `Err` で終わった channel handler の累計 (v1.0 で追加)
+    handler_failures: Arc<AtomicU64>,
+    /// panic した channel handler の累計 (v1.0 で追加)
+    handler_panics: Arc<AtomicU64>,
+    /// channel open / message のフック (= 登録順、 v1.0 で追加)
+    interceptors: Interceptors,
+    /// 接続認証 (= 設定時は `__auth` が済むまで channel open を拒否、 v1.0 で追加)
+    authenticator: Arc<RwLock<Option<Arc<dyn Authenticator>>>>,
+    /// channel 単位のアクセスポリシー (= チャネル名 → 規則、 v1.0 で追加)
+    channel_policies: Arc<RwLock<HashMap<String, ChannelPolicy>>>,
+    /// handshake の完了前 (= 0-RTT) に open を受け付ける channel (v1.0 で追加)
+    zero_rtt_channels: Arc<RwLock<HashSet<String>>>,
+    /// 接続ごとの heartbeat (= 設定時は各接続へ ping を送る、 v1.0 で追加)
+    heartbeat: Arc<RwLock<Option<HeartbeatConfig>>>,
+    /// `Available` 以外に設定したチャネルの状態 (= チャネル名 → 状態、 v1.0 で追加)
+    channel_status: Arc<RwLock<HashMap<String, ChannelStatus>>>,
+    /// 接続中のクライアントへ push するチャネル更新 (v1.0 で追加)
+    channel_update_tx: tokio::sync::broadcast::Sender<ChannelUpdate>,
+}
+
+/// 登録済み interceptor (= サーバーと `ConnectionHandle` で共有)
+pub(crate) type Interceptors = Arc<RwLock<Vec<Arc<dyn Interceptor>>>>;
+
+/// 実行中の channel handler を数えるガード (= drop で減算)
+pub(crate) struct HandlerGuard(Arc<watch::Sender<usize>>);
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n -= 1);
+    }
+}
+
+/// Identity で広告するチャネル情報 (= サーバーのチャネルは双方向・persistent)
+fn channel_info(name: &str, status: ChannelStatus) -> ChannelInfo {
+    ChannelInfo {
+        name: name.to_string(),
+        direction: ChannelDirection::Bidirectional,
+        lifetime: "persistent".to_string(),
+        status,
+    }
 }
 
 impl ProtocolServer {
@@ -186,6 +363,19 @@ impl ProtocolServer {
             datagram_channel_handlers: Arc::new(RwLock::new(HashMap::new())),
             active_connections: Arc::new(RwLock::new(HashMap::new())),
             connection_event_tx: tx,
+            draining: Arc::new(AtomicBool::new(false)),
+            active_handlers: Arc::new(watch::Sender::new(0)),
+            handler_failures: Arc::new(AtomicU64::new(0)),
+            handler_panics: Arc::new(AtomicU64::new(0)),
+            interceptors: Arc::new(RwLock::new(Vec::new())),
+            authenticator: Arc::new(RwLock::new(None)),
+            channel_policies: Arc::new(RwLock::new(HashMap::new())),
+            zero_rtt_channels: Arc::new(RwLock::new(HashSet::new())),
+            heartbeat: Arc::new(RwLock::new(None)),
+            channel_status: Arc::new(RwLock::new(HashMap::new())),
+            // capacity 64: チャネルの登録 / 状態変更は運用操作の頻度。 溢れた接続には
+            // Identity を送り直すので取りこぼしは起きない
+            channel_update_tx: tokio::sync::broadcast::channel(64).0,
         }
     }
 
@@ -204,6 +394,80 @@ impl ProtocolServer {
         self.running.load(Ordering::SeqCst)
     }
 
+    /// graceful shutdown の drain 中かどうか（v1.0 で追加）
+    ///
+    /// 長く走る channel handler はこれを見て早めに切り上げられる。
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// 実行中の channel handler 数（v1.0 で追加）
+    pub fn active_handler_count(&self) -> usize {
+        *self.active_handlers.borrow()
+    }
+
+    /// `Err` で終わった channel handler の累計（v1.0 で追加、 panic は含まない）
+    pub fn handler_failure_count(&self) -> u64 {
+        self.handler_failures.load(Ordering::Relaxed)
+    }
+
+    /// panic した channel handler の累計（v1.0 で追加）
+    pub fn handler_panic_count(&self) -> u64 {
+        self.handler_panics.load(Ordering::Relaxed)
+    }
+
+    /// channel handler の終わり方を集計し、 失敗なら [`ConnectionEvent::HandlerFailed`] を出す
+    pub(crate) fn record_handler_outcome(
+        &self,
+        connection_id: ConnectionId,
+        channel: &str,
+        outcome: HandlerOutcome,
+    ) {
+        let (error, panicked) = match outcome {
+            HandlerOutcome::NotStarted | HandlerOutcome::Completed => return,
+            HandlerOutcome::Failed(e) => {
+                self.handler_failures.fetch_add(1, Ordering::Relaxed);
+                (e.to_string(), false)
+            }
+            HandlerOutcome::Panicked(reason) => {
+                self.handler_panics.fetch_add(1, Ordering::Relaxed);
+                (reason, true)
+            }
+        };
+        self.emit_connection_event(ConnectionEvent::HandlerFailed {
+            connection_id,
+            channel: channel.to_string(),
+            error,
+            panicked,
+        });
+    }
+
+    /// channel handler の開始を記録する (= guard の drop で終了)
+    pub(crate) fn track_handler(&self) -> HandlerGuard {
+        self.active_handlers.send_modify(|n| *n += 1);
+        HandlerGuard(Arc::clone(&self.active_handlers))
+    }
+
+    /// 実行中の channel handler が 0 になるまで待つ
+    async fn wait_handlers_idle(&self) {
+        let mut rx = self.active_handlers.subscribe();
+        let _ = rx.wait_for(|n| *n == 0).await;
+    }
+
+    /// 全接続に `__goaway` を送る (= 失敗は接続ごとに無視)
+    async fn announce_drain(&self, deadline: Duration) {
+        let connections = self.connections().await;
+        let sends = connections.iter().map(|c| c.send_goaway(deadline));
+        for (connection, result) in connections
+            .iter()
+            .zip(futures_util::future::join_all(sends).await)
+        {
+            if let Err(e) = result {
+                tracing::debug!("Failed to send goaway to {}: {}", connection.id(), e);
+            }
+        }
+    }
+
     /// 登録済みチャネルからServerIdentityを構築
     pub async fn build_identity(&self) -> ServerIdentity {
         let mut identity = ServerIdentity::new(
@@ -214,18 +478,31 @@ impl ProtocolServer {
 
         // チャネルハンドラーからChannelInfoを構築
         let handlers = self.channel_handlers.read().await;
+        let status = self.channel_status.read().await;
         for channel_name in handlers.keys() {
-            identity.add_channel(ChannelInfo {
-                name: channel_name.clone(),
-                direction: ChannelDirection::Bidirectional,
-                lifetime: "persistent".to_string(),
-                status: ChannelStatus::Available,
-            });
+            let status = status
+                .get(channel_name)
+                .cloned()
+                .unwrap_or(ChannelStatus::Available);
+            identity.add_channel(channel_info(channel_name, status));
         }
 
         identity
     }
 
+    /// 接続中の全クライアントにチャネル更新を push する
+    fn push_channel_update(&self, update: ChannelUpdate) {
+        // 受信者 (= 接続) が無ければ Err、 無視してよい
+        let _ = self.channel_update_tx.send(update);
+    }
+
+    /// チャネル更新を購読する (= `handle_connection` が Identity stream へ流す、 内部 API)
+    pub(crate) fn subscribe_channel_updates(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<ChannelUpdate> {
+        self.channel_update_tx.subscribe()
+    }
+
     /// チャネルハンドラーを登録
     pub async fn register_channel<F, Fut>(&self, name: &str, handler: F)
     where
@@ -244,7 +521,229 @@ impl ProtocolServer {
         );
 
         let mut handlers = self.channel_handlers.write().await;
-        handlers.insert(name.to_string(), handler);
+        let added = handlers.insert(name.to_string(), handler).is_none();
+        drop(handlers);
+
+        // v1.0: 新しいチャネルは接続中のクライアントへ `ChannelUpdate::Added` で通知
+        if added {
+            let status = self
+                .channel_status
+                .read()
+                .await
+                .get(name)
+                .cloned()
+                .unwrap_or(ChannelStatus::Available);
+            self.push_channel_update(ChannelUpdate::Added(channel_info(name, status)));
+        }
+    }
+
+    /// チャネルハンドラーの登録を解除する（v1.0 で追加）
+    ///
+    /// 以降の open は channel-not-found で拒否され、 接続中のクライアントには
+    /// `ChannelUpdate::Removed` が届く。 既に開いている channel の handler はそのまま走る。
+    /// 登録されていなければ `false`。
+    pub async fn unregister_channel(&self, name: &str) -> bool {
+        let removed = self.channel_handlers.write().await.remove(name).is_some();
+        self.channel_status.write().await.remove(name);
+        if removed {
+            self.push_channel_update(ChannelUpdate::Removed(name.to_string()));
+        }
+        removed
+    }
+
+    /// チャネルの状態を設定する（v1.0 で追加）
+    ///
+    /// Identity で広告する状態を変え、 変わった場合は接続中のクライアントに
+    /// `ChannelUpdate::StatusChanged` を push する。 状態は広告のみで、 `Unavailable`
+    /// でも open は拒否しない。 登録されていないチャネルなら `false`。
+    pub async fn set_channel_status(&self, name: &str, status: ChannelStatus) -> bool {
+        if !self.channel_handlers.read().await.contains_key(name) {
+            return false;
+        }
+        let mut statuses = self.channel_status.write().await;
+        let previous = statuses
+            .get(name)
+            .cloned()
+            .unwrap_or(ChannelStatus::Available);
+        if previous == status {
+            return true;
+        }
+        if status == ChannelStatus::Available {
+            statuses.remove(name);
+        } else {
+            statuses.insert(name.to_string(), status.clone());
+        }
+        drop(statuses);
+        self.push_channel_update(ChannelUpdate::StatusChanged {
+            name: name.to_string(),
+            status,
+        });
+        true
+    }
+
+    /// チャネルの現在の状態（v1.0 で追加、 登録されていなければ `None`）
+    pub async fn channel_status(&self, name: &str) -> Option<ChannelStatus> {
+        if !self.channel_handlers.read().await.contains_key(name) {
+            return None;
+        }
+        Some(
+            self.channel_status
+                .read()
+                .await
+                .get(name)
+                .cloned()
+                .unwrap_or(ChannelStatus::Available),
+        )
+    }
+
+    /// interceptor を追加（v1.0 で追加）
+    ///
+    /// 登録順に呼ばれる。 追加後に開かれた channel から有効 (= 開いている channel には
+    /// 掛からない)。
+    pub async fn add_interceptor(&self, interceptor: impl Interceptor) {
+        self.interceptors.write().await.push(Arc::new(interceptor));
+    }
+
+    /// 登録済み interceptor の snapshot (= 無ければ `None`)
+    pub(crate) async fn interceptor_chain(&self) -> Option<InterceptorChain> {
+        InterceptorChain::from_registered(&self.interceptors.read().await)
+    }
+
+    /// 接続認証を設定（v1.0 で追加）
+    ///
+    /// 設定後に確立した接続は、 クライアントが `__auth` で認証するまで channel open を
+    /// `unauthenticated` で nack され、 [`AUTH_TIMEOUT`](super::auth::AUTH_TIMEOUT) 以内に
+    /// 認証しなければ閉じられる。 認証された principal は
+    /// `ConnectionContext::principal()` で参照できる。
+    pub async fn set_authenticator(&self, authenticator: impl Authenticator) {
+        *self.authenticator.write().await = Some(Arc::new(authenticator));
+    }
+
+    /// 設定済みの接続認証 (= 無ければ `None`)
+    pub(crate) async fn authenticator(&self) -> Option<Arc<dyn Authenticator>> {
+        self.authenticator.read().await.clone()
+    }
+
+    /// 接続ごとの heartbeat を設定（v1.0 で追加）
+    ///
+    /// 設定後に確立した接続へ `config.interval` ごとに `__heartbeat` の ping を送り、
+    /// RTT を `ConnectionHandle::heartbeat_stats()` に記録する。 `max_missed` 回続けて
+    /// pong が届かなければ接続を閉じ、 `Disconnected { reason: "heartbeat timeout" }` を
+    /// 出す。 pong を返すのは `__heartbeat` に対応した peer (= Rust の `ProtocolClient`)
+    /// のみなので、 他の client が混ざる場合は設定しないこと。 詳細は [`super::heartbeat`]。
+    pub async fn set_heartbeat(&self, config: HeartbeatConfig) {
+        *self.heartbeat.write().await = Some(config);
+    }
+
+    /// 設定済みの heartbeat (= 無ければ `None`)
+    pub(crate) async fn heartbeat(&self) -> Option<HeartbeatConfig> {
+        self.heartbeat.read().await.clone()
+    }
+
+    /// channel のアクセスポリシーを設定（v1.0 で追加）
+    ///
+    /// 以降の `__channel:{name}` の open は [`ChannelPolicy::check`] を満たさなければ
+    /// `forbidden` で nack される。 空のポリシーは設定を外す。
+    pub async fn set_channel_policy(&self, name: &str, policy: ChannelPolicy) {
+        let mut policies = self.channel_policies.write().await;
+        if policy.is_empty() {
+            policies.remove(name);
+        } else {
+            policies.insert(name.to_string(), policy);
+        }
+    }
+
+    /// schema の channel 定義の `require-role` / `allow-from` を読み込む（v1.0 で追加）
+    ///
+    /// ポリシーを持つ channel の数を返す。 ポリシーの無い channel の既存設定は変えない。
+    pub async fn load_channel_policies(
+        &self,
+        schema: &ParsedSchema,
+    ) -> Result<usize, NetworkError> {
+        let Some(protocol) = &schema.protocol else {
+            return Ok(0);
+        };
+        let mut loaded = Vec::new();
+        for channel in &protocol.channels {
+            if let Some(policy) = channel.policy().map_err(NetworkError::Protocol)? {
+                loaded.push((channel.name.clone(), policy));
+            }
+        }
+        let count = loaded.len();
+        let mut policies = self.channel_policies.write().await;
+        policies.extend(loaded);
+        Ok(count)
+    }
+
+    /// channel に設定されたアクセスポリシー (= 無ければ `None`)
+    pub async fn channel_policy(&self, name: &str) -> Option<ChannelPolicy> {
+        self.channel_policies.read().await.get(name).cloned()
+    }
+
+    /// handshake の完了前 (= 0-RTT) に open を受け付ける channel を追加する（v1.0 で追加）
+    ///
+    /// [`QuicServerBuilder::enable_0rtt`](super::quic::QuicServerBuilder::enable_0rtt) の
+    /// サーバーが 0-RTT のまま受け入れた接続で、 ここに無い channel の open は handshake が
+    /// 完了するまで `early-data` で nack される (= replay されると困る request を 0-RTT の
+    /// データで処理しない)。 通常は schema から [`Self::load_zero_rtt_channels`] で読み込む。
+    pub async fn set_zero_rtt_channels<I, S>(&self, names: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.zero_rtt_channels
+            .write()
+            .await
+            .extend(names.into_iter().map(Into::into));
+    }
+
+    /// schema の replay-safe な channel を 0-RTT で受け付ける channel として読み込む（v1.0 で追加）
+    ///
+    /// 全ての request が `idempotent=#true` の stream channel
+    /// ([`Channel::is_replay_safe`](crate::parser::Channel::is_replay_safe)) を追加し、
+    /// 追加した数を返す。
+    pub async fn load_zero_rtt_channels(&self, schema: &ParsedSchema) -> usize {
+        let Some(protocol) = &schema.protocol else {
+            return 0;
+        };
+        let mut names = self.zero_rtt_channels.write().await;
+        let mut count = 0;
+        for channel in protocol.channels.iter().filter(|c| c.is_replay_safe()) {
+            names.insert(channel.name.clone());
+            count += 1;
+        }
+        count
+    }
+
+    /// handshake の完了前に `name` の open を受け付けるか
+    pub(crate) async fn accepts_early_open(&self, name: &str) -> bool {
+        self.zero_rtt_channels.read().await.contains(name)
+    }
+
+    /// `ChannelRouter` をチャネルハンドラーとして登録（v1.0 で追加）
+    ///
+    /// 接続ごとに `router.serve()` を起動する。 method ごとの handler 表は全接続で共有。
+    pub async fn register_channel_router<C: Codec>(&self, name: &str, router: ChannelRouter<C>) {
+        let router = Arc::new(router);
+        self.register_channel(name, move |ctx, stream| {
+            let router = Arc::clone(&router);
+            async move { router.serve(ctx, stream).await }
+        })
+        .await;
+    }
+
+    /// `ChannelGroup` を `group.name()` のチャネルハンドラーとして登録（v1.0 で追加）
+    ///
+    /// 接続ごとに `group.serve()` を起動する (= open された channel は自動で group に
+    /// 加わり、 閉じると外れる)。 Request も受けたい場合は `register_channel` 内で
+    /// `group.serve_with_router()` を呼ぶ。
+    pub async fn register_channel_group<C: Codec>(&self, group: ChannelGroup<C>) {
+        let name = group.name().to_string();
+        self.register_channel(&name, move |ctx, stream| {
+            let group = group.clone();
+            async move { group.serve(ctx, stream).await }
+        })
+        .await;
     }
 
     /// Datagram channel handler を登録 (v0.10.0 で追加)
@@ -288,31 +787,36 @@ impl ProtocolServer {
         T: Encodable<C>,
         C: Codec,
     {
-        let channel_id = {
-            let handlers = self.datagram_channel_handlers.read().await;
-            handlers
-                .get(channel_name)
-                .map(|entry| entry.channel_id)
-                .ok_or_else(|| NetworkError::HandlerNotFound {
-                    method: format!("datagram channel: {}", channel_name),
-                })?
-        };
+        self.broadcast_filtered::<T, C, _>(channel_name, event, |_| true)
+            .await
+    }
 
-        let encoded = event.encode().map_err(NetworkError::Codec)?;
-        let mut payload =
-            Vec::with_capacity(super::datagram_channel::VARINT_MAX_LEN + encoded.len());
-        encode_varint(channel_id, &mut payload);
-        payload.extend_from_slice(&encoded);
+    /// `predicate` が true を返す connection にだけ datagram channel event を送る (v1.0 で追加)
+    ///
+    /// 戻り値・失敗時の扱いは [`Self::broadcast`] と同じ。
+    pub async fn broadcast_filtered<T, C, F>(
+        &self,
+        channel_name: &str,
+        event: &T,
+        predicate: F,
+    ) -> Result<usize, NetworkError>
+    where
+        T: Encodable<C>,
+        C: Codec,
+        F: Fn(&ConnectionHandle) -> bool,
+    {
+        let payload =
+            encode_datagram::<T, C>(&self.datagram_channel_handlers, channel_name, event).await?;
 
         let connections = self.active_connections.read().await;
         let mut success = 0usize;
-        for connection in connections.values() {
-            match connection.send_datagram(payload.clone().into()) {
+        for handle in connections.values().filter(|handle| predicate(handle)) {
+            match handle.send_raw_datagram(payload.clone().into()) {
                 Ok(()) => success += 1,
                 Err(e) => {
                     tracing::debug!(
                         "Broadcast to {}: send_datagram failed: {} (continuing)",
-                        connection.remote_address(),
+                        handle.remote_address(),
                         e
                     );
                 }
@@ -336,18 +840,78 @@ impl ProtocolServer {
     /// Active connection を登録 (= quic.rs::handle_connection 用、 内部 API)
     pub(crate) async fn add_active_connection(
         &self,
-        remote_addr: SocketAddr,
         connection: Arc<dyn super::conn::UnisonConn>,
-    ) {
+        context: Arc<super::context::ConnectionContext>,
+    ) -> ConnectionHandle {
+        let handle = ConnectionHandle::new(
+            connection,
+            context,
+            Arc::clone(&self.datagram_channel_handlers),
+            Arc::clone(&self.interceptors),
+        );
         self.active_connections
             .write()
             .await
-            .insert(remote_addr, connection);
+            .insert(handle.id(), handle.clone());
+        handle
     }
 
     /// Active connection を解除 (= quic.rs::handle_connection 用、 内部 API)
-    pub(crate) async fn remove_active_connection(&self, remote_addr: SocketAddr) {
-        self.active_connections.write().await.remove(&remote_addr);
+    pub(crate) async fn remove_active_connection(&self, connection_id: ConnectionId) {
+        self.active_connections.write().await.remove(&connection_id);
+    }
+
+    /// 接続 ID からハンドルを取得 (v1.0 で追加)
+    ///
+    /// ID は [`ConnectionEvent::Connected`] の `context.connection_id`。 切断済みなら `None`。
+    pub async fn connection(&self, connection_id: ConnectionId) -> Option<ConnectionHandle> {
+        self.active_connections
+            .read()
+            .await
+            .get(&connection_id)
+            .cloned()
+    }
+
+    /// 全 active connection のハンドル (v1.0 で追加)
+    pub async fn connections(&self) -> Vec<ConnectionHandle> {
+        self.active_connections
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// 特定の接続に対してサーバー発信の channel を開く（v1.0 で追加）
+    ///
+    /// `connection_id` は [`ConnectionEvent::Connected`] の `context.connection_id`。
+    /// [`ConnectionHandle::open_channel`] の短縮形。 クライアントが
+    /// `ProtocolClient::register_channel(name, ..)` で handler を登録していれば
+    /// `open_ack` が返り、 以降は通常の `UnisonChannel` として request / event を送れる
+    /// (= サーバーがクライアント agent にジョブを依頼する push 型ワークフロー)。
+    /// 未登録なら [`NetworkError::Protocol`] で reject、 接続が無ければ
+    /// [`NetworkError::NotConnected`]。
+    pub async fn open_channel(
+        &self,
+        connection_id: ConnectionId,
+        channel_name: &str,
+    ) -> Result<UnisonChannel, NetworkError> {
+        self.open_channel_with_config(connection_id, channel_name, ChannelConfig::default())
+            .await
+    }
+
+    /// 受信キュー設定を指定してサーバー発信の channel を開く（v1.0 で追加）
+    pub async fn open_channel_with_config(
+        &self,
+        connection_id: ConnectionId,
+        channel_name: &str,
+        config: ChannelConfig,
+    ) -> Result<UnisonChannel, NetworkError> {
+        self.connection(connection_id)
+            .await
+            .ok_or(NetworkError::NotConnected)?
+            .open_channel_with_config(channel_name, config)
+            .await
     }
 
     /// Active connection 数 (= 主に test / debug 用)
@@ -487,6 +1051,7 @@ impl ProtocolServer {
             join_handle,
             shutdown_tx: Some(shutdown_tx),
             local_addr,
+            server: protocol_server,
         })
     }
 
@@ -583,6 +1148,7 @@ impl ProtocolServer {
             join_handle,
             shutdown_tx: Some(shutdown_tx),
             local_addr,
+            server: protocol_server,
         })
     }
 }
@@ -624,11 +1190,15 @@ mod tests {
         let mut rx = server.subscribe_connection_events();
 
         let addr: SocketAddr = "127.0.0.1:9999".parse().unwrap();
-        server.emit_connection_event(ConnectionEvent::Disconnected { remote_addr: addr });
+        server.emit_connection_event(ConnectionEvent::Disconnected {
+            connection_id: ConnectionId::nil(),
+            remote_addr: addr,
+            reason: "test".to_string(),
+        });
 
         let event = rx.recv_skip_lagged().await.unwrap();
         match event {
-            ConnectionEvent::Disconnected { remote_addr } => {
+            ConnectionEvent::Disconnected { remote_addr, .. } => {
                 assert_eq!(remote_addr, addr);
             }
             _ => panic!("Expected Disconnected event"),
@@ -648,13 +1218,17 @@ mod tests {
 
         // capacity(2) を超える 4 件を送信 → subscriber は Lagged になる
         for _ in 0..4 {
-            let _ = tx.send(ConnectionEvent::Disconnected { remote_addr: addr });
+            let _ = tx.send(ConnectionEvent::Disconnected {
+                connection_id: ConnectionId::nil(),
+                remote_addr: addr,
+                reason: "test".to_string(),
+            });
         }
 
         // recv_skip_lagged は Lagged をスキップして最新のイベントを返す
         let event = rx.recv_skip_lagged().await.unwrap();
         match event {
-            ConnectionEvent::Disconnected { remote_addr } => {
+            ConnectionEvent::Disconnected { remote_addr, .. } => {
                 assert_eq!(remote_addr, addr);
             }
             _ => panic!("Expected Disconnected event"),
@@ -694,7 +1268,11 @@ mod tests {
 
         // capacity を超える送信
         for _ in 0..4 {
-            let _ = tx.send(ConnectionEvent::Disconnected { remote_addr: addr });
+            let _ = tx.send(ConnectionEvent::Disconnected {
+                connection_id: ConnectionId::nil(),
+                remote_addr: addr,
+                reason: "test".to_string(),
+            });
         }
 
         // recv() は Lagged をそのまま返す
@@ -715,12 +1293,16 @@ mod tests {
         let mut rx = server.subscribe_connection_events();
 
         let addr: SocketAddr = "127.0.0.1:7001".parse().unwrap();
-        server.emit_connection_event(ConnectionEvent::Disconnected { remote_addr: addr });
+        server.emit_connection_event(ConnectionEvent::Disconnected {
+            connection_id: ConnectionId::nil(),
+            remote_addr: addr,
+            reason: "test".to_string(),
+        });
 
         // inner() で内部の broadcast::Receiver を取得し、直接 recv() する
         let event = rx.inner().recv().await.unwrap();
         match event {
-            ConnectionEvent::Disconnected { remote_addr } => {
+            ConnectionEvent::Disconnected { remote_addr, .. } => {
                 assert_eq!(remote_addr, addr);
             }
             _ => panic!("Expected Disconnected event"),
@@ -740,13 +1322,17 @@ mod tests {
 
         // capacity(2) を超える 4 件を送信 → subscriber は Lagged になる
         for _ in 0..4 {
-            let _ = tx.send(ConnectionEvent::Disconnected { remote_addr: addr });
+            let _ = tx.send(ConnectionEvent::Disconnected {
+                connection_id: ConnectionId::nil(),
+                remote_addr: addr,
+                reason: "test".to_string(),
+            });
         }
 
         // recv_skip_lagged で Lagged をスキップして最新イベントを受信
         let event = rx.recv_skip_lagged().await.unwrap();
         match &event {
-            ConnectionEvent::Disconnected { remote_addr } => {
+            ConnectionEvent::Disconnected { remote_addr, .. } => {
                 assert_eq!(*remote_addr, addr);
             }
             _ => panic!("Expected Disconnected event after lagged skip"),
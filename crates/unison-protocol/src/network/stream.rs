@@ -10,12 +10,14 @@ use std::sync::{
     Arc,
     atomic::{AtomicBool, AtomicU64, Ordering},
 };
+use std::time::Duration;
 use tokio::sync::Mutex;
-use tracing::info;
+use tracing::{debug, info};
 
 use super::conn::{BoxUnisonRecv, BoxUnisonSend, UnisonConn};
 use super::frame::{FRAME_TYPE_PROTOCOL, FRAME_TYPE_RAW, read_typed_frame, write_typed_frame};
-use super::{NetworkError, ProtocolFrame, ProtocolMessage};
+use super::interceptor::{InterceptContext, InterceptorChain};
+use super::{MessageType, NetworkError, ProtocolError, ProtocolFrame, ProtocolMessage};
 
 /// Unison Stream — transport 非依存の双方向ストリーム実装。
 ///
@@ -26,11 +28,12 @@ use super::{NetworkError, ProtocolFrame, ProtocolMessage};
 pub struct UnisonStream {
     stream_id: u64,
     method: String,
-    #[allow(dead_code)]
     connection: Arc<dyn UnisonConn>,
     send_stream: Arc<Mutex<Option<BoxUnisonSend>>>,
     recv_stream: Arc<Mutex<Option<BoxUnisonRecv>>>,
     is_active: Arc<AtomicBool>,
+    /// 送受信する ProtocolMessage に掛ける interceptor (v1.0 で追加、 サーバー側のみ)
+    intercept: Option<(InterceptorChain, InterceptContext)>,
 }
 
 impl UnisonStream {
@@ -56,6 +59,7 @@ impl UnisonStream {
             send_stream: Arc::new(Mutex::new(Some(send_stream))),
             recv_stream: Arc::new(Mutex::new(Some(recv_stream))),
             is_active: Arc::new(AtomicBool::new(true)),
+            intercept: None,
         })
     }
 
@@ -74,13 +78,82 @@ impl UnisonStream {
             send_stream: Arc::new(Mutex::new(Some(send_stream))),
             recv_stream: Arc::new(Mutex::new(Some(recv_stream))),
             is_active: Arc::new(AtomicBool::new(true)),
+            intercept: None,
         }
     }
 
+    /// 送受信する ProtocolMessage に interceptor を掛ける（v1.0 で追加）
+    pub(crate) fn with_interceptors(
+        mut self,
+        chain: InterceptorChain,
+        ctx: InterceptContext,
+    ) -> Self {
+        self.intercept = Some((chain, ctx));
+        self
+    }
+
+    /// ストリーム ID（channel open 時は open request の message id）
+    pub fn stream_id(&self) -> u64 {
+        self.stream_id
+    }
+
     /// ストリーム稼働状態の確認
     pub fn is_active(&self) -> bool {
         self.is_active.load(Ordering::SeqCst)
     }
+
+    /// このストリームの接続が閉じているか（v1.0 で追加）
+    pub(crate) fn is_connection_closed(&self) -> bool {
+        self.connection.is_closed()
+    }
+
+    /// handler の外から送信側を打ち切るための handle（v1.0 で追加）
+    ///
+    /// stream 本体は handler に move されるので、 panic 時の後始末用に送信側だけ
+    /// 共有しておく。
+    pub(crate) fn abort_handle(&self) -> StreamAbortHandle {
+        StreamAbortHandle {
+            send_stream: Arc::clone(&self.send_stream),
+            is_active: Arc::clone(&self.is_active),
+        }
+    }
+}
+
+/// 送信側を最後の 1 frame + reset で打ち切る handle (= [`UnisonStream::abort_handle`])
+pub(crate) struct StreamAbortHandle {
+    send_stream: Arc<Mutex<Option<BoxUnisonSend>>>,
+    is_active: Arc<AtomicBool>,
+}
+
+impl StreamAbortHandle {
+    /// reset 前に最後の frame を peer へ届けるための猶予
+    ///
+    /// 受信側の QUIC 実装は RESET_STREAM を受けると未読のデータを捨てるため、
+    /// frame を書いた直後に reset すると frame ごと失われうる。
+    const RESET_GRACE: Duration = Duration::from_millis(100);
+
+    /// `msg` を送ってから送信側を `code` で reset する
+    ///
+    /// `msg` の到達は best-effort (= 失敗しても reset は行う)。 既に閉じていれば何もしない。
+    pub(crate) async fn abort(&self, msg: &ProtocolMessage, code: u32) {
+        self.is_active.store(false, Ordering::SeqCst);
+        let Some(mut send_stream) = self.send_stream.lock().await.take() else {
+            return;
+        };
+        let written = match msg.clone().into_frame() {
+            Ok(frame) => tokio::time::timeout(
+                Self::RESET_GRACE,
+                write_typed_frame(&mut send_stream, FRAME_TYPE_PROTOCOL, &frame.to_bytes()),
+            )
+            .await
+            .is_ok_and(|r| r.is_ok()),
+            Err(_) => false,
+        };
+        if written {
+            tokio::time::sleep(Self::RESET_GRACE).await;
+        }
+        let _ = send_stream.reset(code);
+    }
 }
 
 /// Typed フレーム受信結果
@@ -100,8 +173,29 @@ impl UnisonStream {
         if !self.is_active() {
             return Err(NetworkError::Connection("Stream is not active".to_string()));
         }
-
+        if let Some((chain, ctx)) = &self.intercept {
+            chain.outbound(ctx, msg);
+        }
         let frame = msg.clone().into_frame()?;
+        self.write_protocol_frame(&frame).await
+    }
+
+    /// ProtocolMessage を制御フレームとして送信（type tag 0x00、v1.0 で追加）
+    ///
+    /// `send_frame()` と同じ経路で、 packet header の `packet_type` を
+    /// `PacketType::Control` にする。 `__cancel` 等のプロトコル内部メッセージで使用。
+    pub async fn send_control_frame(&self, msg: &ProtocolMessage) -> Result<(), NetworkError> {
+        if !self.is_active() {
+            return Err(NetworkError::Connection("Stream is not active".to_string()));
+        }
+        if let Some((chain, ctx)) = &self.intercept {
+            chain.outbound(ctx, msg);
+        }
+        let frame = msg.clone().into_control_frame()?;
+        self.write_protocol_frame(&frame).await
+    }
+
+    async fn write_protocol_frame(&self, frame: &ProtocolFrame) -> Result<(), NetworkError> {
         let frame_bytes = frame.to_bytes();
 
         let mut send_guard = self.send_stream.lock().await;
@@ -181,16 +275,28 @@ impl UnisonStream {
         }
 
         let mut recv_guard = self.recv_stream.lock().await;
-        if let Some(recv_stream) = recv_guard.as_mut() {
+        let Some(recv_stream) = recv_guard.as_mut() else {
+            return Err(NetworkError::Connection(
+                "Receive stream is closed".to_string(),
+            ));
+        };
+        loop {
             let (frame_type, payload) = read_typed_frame(recv_stream).await.map_err(|e| {
                 self.is_active.store(false, Ordering::SeqCst);
                 NetworkError::Quic(format!("Failed to read frame: {}", e))
             })?;
 
-            match frame_type {
+            return match frame_type {
                 FRAME_TYPE_PROTOCOL => {
                     let frame = ProtocolFrame::from_bytes(&payload)?;
                     let message = ProtocolMessage::from_frame(&frame)?;
+                    // v1.0: interceptor が拒否した message は呼び出し側に渡さない
+                    if let Some((chain, ctx)) = &self.intercept
+                        && let Err(err) = chain.inbound(ctx, &message)
+                    {
+                        self.reject_inbound(&message, err).await?;
+                        continue;
+                    }
                     Ok(TypedFrame::Protocol(message))
                 }
                 FRAME_TYPE_RAW => Ok(TypedFrame::Raw(payload.to_vec())),
@@ -198,11 +304,29 @@ impl UnisonStream {
                     "Unknown frame type tag: 0x{:02x}",
                     frame_type
                 ))),
-            }
-        } else {
-            Err(NetworkError::Connection(
-                "Receive stream is closed".to_string(),
-            ))
+            };
+        }
+    }
+
+    /// interceptor が拒否した受信 message の後始末 (= Request には Error を返す)
+    async fn reject_inbound(
+        &self,
+        message: &ProtocolMessage,
+        error: ProtocolError,
+    ) -> Result<(), NetworkError> {
+        if message.msg_type != MessageType::Request {
+            debug!(
+                "Interceptor dropped {:?} '{}' ({})",
+                message.msg_type, message.method, error.message
+            );
+            return Ok(());
         }
+        let reply = ProtocolMessage::new_with_json(
+            message.id,
+            "error".to_string(),
+            MessageType::Error,
+            serde_json::to_value(error)?,
+        )?;
+        self.send_frame(&reply).await
     }
 }
@@ -0,0 +1,228 @@
+//! QUIC の transport 設定 (v1.0 で追加)
+//!
+//! keep-alive / idle timeout / stream 数 / datagram バッファ / 輻輳制御を
+//! [`TransportOptions`] にまとめ、 `QuicClientBuilder` / `QuicServerBuilder` /
+//! `WebTransportServer` の `transport_options()` で渡す。 未指定なら旧実装の固定値
+//! (= keep-alive 10 秒、 idle timeout 60 秒) と同じ。
+//!
+//! NAT の UDP mapping は無通信だと 30 秒前後で消えることが多い。 長時間 idle になる
+//! agent 接続では keep-alive をそれより短くする:
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use unison::network::TransportOptions;
+//!
+//! let options = TransportOptions::default()
+//!     .with_keep_alive_interval(Some(Duration::from_secs(5)))
+//!     .with_max_idle_timeout(Some(Duration::from_secs(120)));
+//! assert!(options.validate().is_ok());
+//! ```
+//!
+//! idle timeout は両端の小さい方が使われる (= QUIC の仕様)。 keep-alive は idle
+//! timeout より短くないと接続を保てないため、 [`TransportOptions::validate`] が拒否する。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 輻輳制御アルゴリズム
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CongestionController {
+    /// CUBIC (= quinn の既定)
+    #[default]
+    Cubic,
+    /// NewReno
+    NewReno,
+    /// BBR (= quinn の実装は experimental)
+    Bbr,
+}
+
+/// QUIC 接続の transport 設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportOptions {
+    /// 無通信時に PING を送る間隔 (= `None` は送らない)
+    pub keep_alive_interval: Option<Duration>,
+    /// 無通信で接続を閉じるまでの時間 (= `None` は無期限、 相手の値が短ければそちら)
+    pub max_idle_timeout: Option<Duration>,
+    /// 相手が同時に開ける双方向 stream の数 (= 同時に開ける channel の上限)
+    pub max_concurrent_bidi_streams: u32,
+    /// 初期 RTT の見積もり
+    pub initial_rtt: Duration,
+    /// datagram の受信バッファ (byte、 `None` は datagram を受け付けない)
+    pub datagram_receive_buffer_size: Option<usize>,
+    /// datagram の送信バッファ (byte)
+    pub datagram_send_buffer_size: usize,
+    /// 輻輳制御
+    pub congestion_controller: CongestionController,
+}
+
+impl Default for TransportOptions {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval: Some(Duration::from_secs(10)),
+            max_idle_timeout: Some(Duration::from_secs(60)),
+            max_concurrent_bidi_streams: 1000,
+            initial_rtt: Duration::from_millis(100),
+            // v0.9.0: QUIC datagram (= unreliable / unordered, ≤MTU) 用
+            datagram_receive_buffer_size: Some(1024 * 1024),
+            datagram_send_buffer_size: 1024 * 1024,
+            congestion_controller: CongestionController::Cubic,
+        }
+    }
+}
+
+impl TransportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// keep-alive の間隔を設定（ビルダーパターン）
+    pub fn with_keep_alive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.keep_alive_interval = interval;
+        self
+    }
+
+    /// idle timeout を設定（ビルダーパターン）
+    pub fn with_max_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.max_idle_timeout = timeout;
+        self
+    }
+
+    /// 同時双方向 stream 数を設定（ビルダーパターン）
+    pub fn with_max_concurrent_bidi_streams(mut self, streams: u32) -> Self {
+        self.max_concurrent_bidi_streams = streams;
+        self
+    }
+
+    /// 初期 RTT の見積もりを設定（ビルダーパターン）
+    pub fn with_initial_rtt(mut self, rtt: Duration) -> Self {
+        self.initial_rtt = rtt;
+        self
+    }
+
+    /// datagram の受信 / 送信バッファを設定（ビルダーパターン）
+    pub fn with_datagram_buffers(mut self, receive: Option<usize>, send: usize) -> Self {
+        self.datagram_receive_buffer_size = receive;
+        self.datagram_send_buffer_size = send;
+        self
+    }
+
+    /// 輻輳制御を設定（ビルダーパターン）
+    pub fn with_congestion_controller(mut self, controller: CongestionController) -> Self {
+        self.congestion_controller = controller;
+        self
+    }
+
+    /// 設定の整合性を確かめる
+    ///
+    /// - keep-alive と idle timeout は 0 にできない
+    /// - keep-alive は idle timeout より短くなければならない
+    /// - idle timeout は QUIC の varint (= 2^62 ms 未満) に収まらなければならない
+    /// - 双方向 stream は 1 本以上 (= channel を開けない)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.keep_alive_interval == Some(Duration::ZERO) {
+            return Err("keep_alive_interval must be non-zero (use None to disable)".into());
+        }
+        if let Some(idle) = self.max_idle_timeout {
+            if idle.is_zero() {
+                return Err("max_idle_timeout must be non-zero (use None for no timeout)".into());
+            }
+            if quinn::IdleTimeout::try_from(idle).is_err() {
+                return Err(format!("max_idle_timeout {idle:?} is out of range"));
+            }
+            if let Some(interval) = self.keep_alive_interval
+                && interval >= idle
+            {
+                return Err(format!(
+                    "keep_alive_interval {interval:?} must be shorter than max_idle_timeout {idle:?}"
+                ));
+            }
+        }
+        if self.max_concurrent_bidi_streams == 0 {
+            return Err("max_concurrent_bidi_streams must be at least 1".into());
+        }
+        Ok(())
+    }
+
+    /// quinn の `TransportConfig` を組み立てる (= `validate()` 済みであること)
+    ///
+    /// 単方向 stream は raw QUIC では使わないため 0 (= `uni_streams` が `false`)。
+    /// WebTransport は HTTP/3 の制御 stream に単方向 stream を使うので quinn の既定値のまま。
+    pub(crate) fn to_transport_config(
+        &self,
+        uni_streams: bool,
+    ) -> Result<quinn::TransportConfig, String> {
+        self.validate()?;
+        let mut config = quinn::TransportConfig::default();
+        config
+            .max_idle_timeout(
+                self.max_idle_timeout
+                    .map(quinn::IdleTimeout::try_from)
+                    .transpose()
+                    .map_err(|e| e.to_string())?,
+            )
+            .keep_alive_interval(self.keep_alive_interval)
+            .max_concurrent_bidi_streams(self.max_concurrent_bidi_streams.into())
+            .initial_rtt(self.initial_rtt)
+            .datagram_receive_buffer_size(self.datagram_receive_buffer_size)
+            .datagram_send_buffer_size(self.datagram_send_buffer_size);
+        if !uni_streams {
+            config.max_concurrent_uni_streams(0u32.into());
+        }
+        match self.congestion_controller {
+            CongestionController::Cubic => {
+                config.congestion_controller_factory(Arc::new(
+                    quinn::congestion::CubicConfig::default(),
+                ));
+            }
+            CongestionController::NewReno => {
+                config.congestion_controller_factory(Arc::new(
+                    quinn::congestion::NewRenoConfig::default(),
+                ));
+            }
+            CongestionController::Bbr => {
+                config.congestion_controller_factory(Arc::new(
+                    quinn::congestion::BbrConfig::default(),
+                ));
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        let options = TransportOptions::default();
+        assert!(options.validate().is_ok());
+        assert!(options.to_transport_config(false).is_ok());
+        assert!(
+            TransportOptions::default()
+                .with_keep_alive_interval(None)
+                .with_max_idle_timeout(None)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_rejects_inconsistent_options() {
+        let keep_alive_too_long = TransportOptions::default()
+            .with_keep_alive_interval(Some(Duration::from_secs(60)))
+            .with_max_idle_timeout(Some(Duration::from_secs(30)));
+        let err = keep_alive_too_long.validate().unwrap_err();
+        assert!(err.contains("shorter than"), "{err}");
+
+        for options in [
+            TransportOptions::default().with_keep_alive_interval(Some(Duration::ZERO)),
+            TransportOptions::default().with_max_idle_timeout(Some(Duration::ZERO)),
+            TransportOptions::default().with_max_idle_timeout(Some(Duration::MAX)),
+            TransportOptions::default().with_max_concurrent_bidi_streams(0),
+        ] {
+            assert!(options.validate().is_err(), "{options:?}");
+            assert!(options.to_transport_config(false).is_err());
+        }
+    }
+}
@@ -10,26 +10,55 @@
 //!
 //! | Scenario | Variant |
 //! |----------|---------|
-//! | Connect to public server (CA chain) | [`TrustAnchors::System`] |
+//! | Connect to public server (CA chain) | [`TrustAnchors::System`] or [`TrustAnchors::OsNative`] |
+//! | Internal CA (PEM bundle on disk) | [`TrustAnchors::FromPemFile`] / [`TrustAnchors::FromDir`] |
 //! | Internal mesh (pinned CA) | [`TrustAnchors::Custom`] (via [`super::mesh::InternalMeshKeypair`]) |
+//! | Self-signed / ephemeral server with a known hash (e.g. WebTransport `certificate_hash_hex`) | [`TrustAnchors::PinnedSha256`] |
 //! | Dev against `dev_localhost()` server | [`TrustAnchors::SkipVerification`] |
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use rustls::client::ResolvesClientCert;
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::WebPkiSupportedAlgorithms;
 use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
-use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use rustls::sign::CertifiedKey;
+use rustls::{CertificateError, DigitallySignedStruct, RootCertStore, SignatureScheme};
 
 /// Trust anchors used by the client to verify server certificates.
 #[derive(Clone)]
 pub enum TrustAnchors {
-    /// Trust the OS-native CA bundle (via `webpki-roots`'s Mozilla bundled set).
+    /// Trust the Mozilla root set compiled in via `webpki-roots`.
     ///
     /// Suitable for connecting to public servers whose certs come from a
-    /// well-known CA chain.
+    /// well-known CA chain. Use [`Self::OsNative`] to honour the platform's
+    /// own trust store (including locally installed CAs) instead.
     System,
 
+    /// Trust the platform's certificate store (v1.0).
+    ///
+    /// Loaded at client build time via `rustls-native-certs` (the system
+    /// bundle / keychain / Windows store; `SSL_CERT_FILE` / `SSL_CERT_DIR`
+    /// are honoured). Certificates that fail to parse are skipped with a
+    /// warning; building fails if none load.
+    OsNative,
+
+    /// Trust every certificate in this PEM bundle (v1.0).
+    ///
+    /// Read at client build time, mirroring
+    /// [`super::cert::CertSource::FromFile`] on the server side. Suitable
+    /// for an internal CA distributed as a file.
+    FromPemFile(PathBuf),
+
+    /// Trust every certificate in the PEM files of this directory (v1.0).
+    ///
+    /// Regular files are read in name order (not recursively); files without
+    /// a PEM certificate are ignored. Building fails if the directory yields
+    /// none.
+    FromDir(PathBuf),
+
     /// Trust only the certs in this list (pinned CAs or self-issued certs).
     ///
     /// Suitable for internal mesh — pair this with
@@ -37,6 +66,17 @@ pub enum TrustAnchors {
     /// [`super::mesh::InternalMeshKeypair::generate`] returns both halves.
     Custom(Vec<CertificateDer<'static>>),
 
+    /// Trust a server whose leaf certificate, or its SubjectPublicKeyInfo,
+    /// has one of these SHA-256 hashes (v1.0).
+    ///
+    /// Suitable for self-signed or short-lived certs whose hash is published
+    /// out of band — e.g. `WebTransportServer::certificate_hash_hex` or
+    /// `CertReloader::fingerprint`. Pinning the SPKI survives re-issuing a
+    /// cert with the same key. The certificate must be within its validity
+    /// period and the handshake signature is verified; the server name is
+    /// not checked (the pin is the identity).
+    PinnedSha256(Vec<[u8; 32]>),
+
     /// **DEV ONLY** — skip all server certificate verification.
     ///
     /// Suitable for dev quickstart against
@@ -49,41 +89,237 @@ pub enum TrustAnchors {
 }
 
 impl TrustAnchors {
+    /// [`Self::PinnedSha256`] from hex strings (64 hex digits each, `:`
+    /// separators allowed).
+    pub fn pinned_sha256_hex<I, S>(pins: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let pins = pins
+            .into_iter()
+            .map(|pin| parse_sha256_hex(pin.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::PinnedSha256(pins))
+    }
+
     /// Build the underlying [`rustls::ClientConfig`] for this trust mode.
     pub fn build_client_config(self) -> Result<Arc<rustls::ClientConfig>> {
-        match self {
+        self.build_client_config_with_cert(None)
+    }
+
+    /// Like [`Self::build_client_config`], additionally presenting
+    /// `client_cert` to servers that request one (mutual TLS, see
+    /// [`super::client_auth::ClientAuth`]).
+    pub fn build_client_config_with_cert(
+        self,
+        client_cert: Option<Arc<CertifiedKey>>,
+    ) -> Result<Arc<rustls::ClientConfig>> {
+        let builder = match self {
             Self::System => {
                 let mut roots = RootCertStore::empty();
                 roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-                let config = rustls::ClientConfig::builder()
-                    .with_root_certificates(roots)
-                    .with_no_client_auth();
-                Ok(Arc::new(config))
+                rustls::ClientConfig::builder().with_root_certificates(roots)
             }
             Self::Custom(certs) => {
+                rustls::ClientConfig::builder().with_root_certificates(root_store(certs)?)
+            }
+            Self::OsNative => {
+                let loaded = rustls_native_certs::load_native_certs();
+                for error in &loaded.errors {
+                    tracing::warn!("failed to load some OS trust store certificates: {}", error);
+                }
                 let mut roots = RootCertStore::empty();
-                for cert in certs {
-                    roots
-                        .add(cert)
-                        .context("failed to add custom CA certificate to root store")?;
+                let (added, ignored) = roots.add_parsable_certificates(loaded.certs);
+                if ignored > 0 {
+                    tracing::warn!("ignored {} unparsable OS trust store certificates", ignored);
+                }
+                if added == 0 {
+                    anyhow::bail!("no certificates found in the OS trust store");
+                }
+                rustls::ClientConfig::builder().with_root_certificates(roots)
+            }
+            Self::FromPemFile(path) => {
+                let certs = load_pem_certs(&path)?;
+                if certs.is_empty() {
+                    anyhow::bail!("no certificates found in {}", path.display());
                 }
-                let config = rustls::ClientConfig::builder()
-                    .with_root_certificates(roots)
-                    .with_no_client_auth();
-                Ok(Arc::new(config))
+                rustls::ClientConfig::builder().with_root_certificates(root_store(certs)?)
+            }
+            Self::FromDir(dir) => {
+                let certs = load_pem_dir(&dir)?;
+                if certs.is_empty() {
+                    anyhow::bail!("no PEM certificates found in {}", dir.display());
+                }
+                rustls::ClientConfig::builder().with_root_certificates(root_store(certs)?)
+            }
+            Self::PinnedSha256(pins) => {
+                if pins.is_empty() {
+                    anyhow::bail!("TrustAnchors::PinnedSha256 requires at least one pin");
+                }
+                rustls::ClientConfig::builder()
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(PinnedServerVerification::new(pins)))
             }
             Self::SkipVerification => {
                 tracing::warn!(
                     "TrustAnchors::SkipVerification — server certificates will NOT be \
                      verified. DEV / TEST ONLY, never use in production."
                 );
-                let config = rustls::ClientConfig::builder()
+                rustls::ClientConfig::builder()
                     .dangerous()
                     .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
-                    .with_no_client_auth();
-                Ok(Arc::new(config))
             }
+        };
+        let config = match client_cert {
+            Some(key) => builder.with_client_cert_resolver(Arc::new(SingleClientCertResolver(key))),
+            None => builder.with_no_client_auth(),
+        };
+        Ok(Arc::new(config))
+    }
+}
+
+/// Client-side cert resolver that always presents the same certificate.
+#[derive(Debug)]
+struct SingleClientCertResolver(Arc<CertifiedKey>);
+
+impl ResolvesClientCert for SingleClientCertResolver {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        _sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        Some(Arc::clone(&self.0))
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+fn root_store(certs: Vec<CertificateDer<'static>>) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in certs {
+        roots
+            .add(cert)
+            .context("failed to add custom CA certificate to root store")?;
+    }
+    Ok(roots)
+}
+
+/// Read every certificate from a PEM file (other PEM items are ignored).
+fn load_pem_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path)
+        .with_context(|| format!("failed to read CA file: {}", path.display()))?;
+    rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("failed to parse CA PEM: {}", path.display()))
+}
+
+/// Read the certificates of every regular file in `dir`, in name order.
+fn load_pem_dir(dir: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let mut paths = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read CA directory: {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("failed to read CA directory: {}", dir.display()))?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    let mut certs = Vec::new();
+    for path in paths {
+        certs.extend(load_pem_certs(&path)?);
+    }
+    Ok(certs)
+}
+
+fn parse_sha256_hex(pin: &str) -> Result<[u8; 32]> {
+    let hex: String = pin.chars().filter(|c| *c != ':').collect();
+    if hex.len() != 64 || !hex.is_ascii() {
+        anyhow::bail!("SHA-256 pin must be 64 hex digits: {pin:?}");
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .with_context(|| format!("invalid hex in SHA-256 pin: {pin:?}"))?;
+    }
+    Ok(out)
+}
+
+/// Certificate verifier for [`TrustAnchors::PinnedSha256`].
+///
+/// Accepts the leaf if its DER or SPKI hash is pinned and it is currently
+/// valid; handshake signatures are verified against the leaf's key.
+#[derive(Debug)]
+struct PinnedServerVerification {
+    pins: Vec<[u8; 32]>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl PinnedServerVerification {
+    fn new(pins: Vec<[u8; 32]>) -> Self {
+        Self {
+            pins,
+            algorithms: rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        }
+    }
+
+    fn is_pinned(&self, data: &[u8]) -> bool {
+        let digest = ring::digest::digest(&ring::digest::SHA256, data);
+        self.pins.iter().any(|pin| pin[..] == *digest.as_ref())
+    }
+}
+
+impl ServerCertVerifier for PinnedServerVerification {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let (_, cert) = x509_parser::parse_x509_certificate(end_entity.as_ref())
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if !self.is_pinned(end_entity.as_ref())
+            && !self.is_pinned(cert.tbs_certificate.subject_pki.raw)
+        {
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ));
         }
+        let now = now.as_secs() as i64;
+        let validity = cert.validity();
+        if now < validity.not_before.timestamp() {
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidYet,
+            ));
+        }
+        if now > validity.not_after.timestamp() {
+            return Err(rustls::Error::InvalidCertificate(CertificateError::Expired));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
     }
 }
 
@@ -141,3 +377,139 @@ impl ServerCertVerifier for SkipServerVerification {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ca_pem() -> String {
+        rcgen::generate_simple_self_signed(vec!["ca.internal".into()])
+            .unwrap()
+            .cert
+            .pem()
+    }
+
+    #[test]
+    fn from_pem_file_and_dir_load_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("bundle.pem");
+        std::fs::write(&bundle, ca_pem() + &ca_pem()).unwrap();
+        assert_eq!(load_pem_certs(&bundle).unwrap().len(), 2);
+        assert!(
+            TrustAnchors::FromPemFile(bundle.clone())
+                .build_client_config()
+                .is_ok()
+        );
+
+        std::fs::write(dir.path().join("other.crt"), ca_pem()).unwrap();
+        std::fs::write(dir.path().join("README"), "not a certificate").unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        assert_eq!(load_pem_dir(dir.path()).unwrap().len(), 3);
+        assert!(
+            TrustAnchors::FromDir(dir.path().to_path_buf())
+                .build_client_config()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn from_pem_file_and_dir_reject_missing_or_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pem");
+        assert!(
+            TrustAnchors::FromPemFile(missing)
+                .build_client_config()
+                .is_err()
+        );
+
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "").unwrap();
+        assert!(
+            TrustAnchors::FromPemFile(empty)
+                .build_client_config()
+                .is_err()
+        );
+        assert!(
+            TrustAnchors::FromDir(dir.path().to_path_buf())
+                .build_client_config()
+                .is_err()
+        );
+    }
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        let digest = ring::digest::digest(&ring::digest::SHA256, data);
+        digest.as_ref().try_into().unwrap()
+    }
+
+    fn verify(
+        verifier: &PinnedServerVerification,
+        cert: &CertificateDer<'_>,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        verifier.verify_server_cert(
+            cert,
+            &[],
+            &ServerName::try_from("localhost").unwrap(),
+            &[],
+            UnixTime::now(),
+        )
+    }
+
+    #[test]
+    fn pinned_verifier_accepts_leaf_or_spki_hash() {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let der = cert.cert.der().clone();
+        let (_, parsed) = x509_parser::parse_x509_certificate(der.as_ref()).unwrap();
+        let spki = parsed.tbs_certificate.subject_pki.raw.to_vec();
+
+        assert!(verify(&PinnedServerVerification::new(vec![sha256(&der)]), &der).is_ok());
+        assert!(verify(&PinnedServerVerification::new(vec![sha256(&spki)]), &der).is_ok());
+
+        let other = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let err = verify(
+            &PinnedServerVerification::new(vec![sha256(&der)]),
+            other.cert.der(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            rustls::Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure)
+        );
+    }
+
+    #[test]
+    fn pinned_verifier_rejects_expired_cert() {
+        let mut params = rcgen::CertificateParams::new(vec!["localhost".into()]).unwrap();
+        params.not_before = rcgen::date_time_ymd(2000, 1, 1);
+        params.not_after = rcgen::date_time_ymd(2001, 1, 1);
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key).unwrap();
+        let der = cert.der().clone();
+
+        let err = verify(&PinnedServerVerification::new(vec![sha256(&der)]), &der).unwrap_err();
+        assert_eq!(
+            err,
+            rustls::Error::InvalidCertificate(CertificateError::Expired)
+        );
+    }
+
+    #[test]
+    fn pinned_sha256_hex_parses_plain_and_colon_forms() {
+        let plain = "00".repeat(31) + "ff";
+        let colon = ["00"; 31].join(":") + ":FF";
+        let TrustAnchors::PinnedSha256(pins) =
+            TrustAnchors::pinned_sha256_hex([plain.as_str(), colon.as_str()]).unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(pins[0], pins[1]);
+        assert_eq!(pins[0][31], 0xff);
+
+        assert!(TrustAnchors::pinned_sha256_hex(["abcd"]).is_err());
+        assert!(TrustAnchors::pinned_sha256_hex(["zz".repeat(32)]).is_err());
+        assert!(
+            TrustAnchors::PinnedSha256(Vec::new())
+                .build_client_config()
+                .is_err()
+        );
+    }
+}
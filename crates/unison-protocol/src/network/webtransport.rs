@@ -27,19 +27,26 @@
 //! サポートしており、 TS SDK 側 (`clients/typescript` の `trust.ts`) が
 //! cert-hash pinning に対応している。 [`WebTransportServer::certificate_hash`]
 //! が pin 用のハッシュ (= SHA-256, hex) を返す。
+//!
+//! [`CertSource::Reloadable`] で bind したサーバーは、 [`CertReloader`](super::cert::CertReloader)
+//! の証明書が差し替わるたびに endpoint の設定を再読み込みし、 ハッシュも更新する
+//! (v1.0 で追加、 確立済みのセッションは切らない)。
 
 use std::net::SocketAddr;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, Weak};
 
 use anyhow::{Context, Result};
-use tracing::{error, info};
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{error, info, warn};
+use wtransport::endpoint::endpoint_side::Server;
 use wtransport::{Endpoint, Identity, ServerConfig};
 
 use super::conn::{BiStream, BoxUnisonRecv, BoxUnisonSend, UnisonConn, UnisonRecv, UnisonSend};
 use super::context::ConnectionContext;
 use super::dispatch::handle_connection;
 use super::server::ProtocolServer;
+use super::transport_options::TransportOptions;
 use super::{NetworkError, cert::CertSource};
 
 // ─────────────────────────────────────────
@@ -60,6 +67,12 @@ impl UnisonSend for wtransport::SendStream {
             Ok(())
         })
     }
+
+    fn reset(&mut self, code: u32) -> Result<(), NetworkError> {
+        // 閉じ済み (= ClosedStream) は finish と同じく正常扱い
+        let _ = wtransport::SendStream::reset(self, wtransport::VarInt::from_u32(code));
+        Ok(())
+    }
 }
 
 /// `wtransport::RecvStream` を [`UnisonRecv`] として扱う。
@@ -143,13 +156,34 @@ impl UnisonConn for wtransport::Connection {
 pub struct WebTransportServer {
     server: Arc<ProtocolServer>,
     cert_source: CertSource,
-    endpoint: Option<Endpoint<wtransport::endpoint::endpoint_side::Server>>,
-    /// leaf 証明書の SHA-256 ハッシュ (= BytesArray 形式)。
-    /// ブラウザの `serverCertificateHashes` pinning 用、 `bind` 後に確定する。
-    certificate_hash: Option<String>,
-    /// leaf 証明書の SHA-256 ハッシュ (= 区切り無しの 64 文字 hex)。
-    /// TS SDK の `trust.ts` (`certHash`) はこの plain hex 形式を期待する。
-    certificate_hash_hex: Option<String>,
+    /// v1.0: 証明書の再読み込みタスクと共有するため `Arc`
+    endpoint: Option<Arc<Endpoint<Server>>>,
+    /// bind 後に確定する leaf 証明書のハッシュ (= 証明書の差し替えで更新、 v1.0)
+    certificate_hashes: Arc<RwLock<Option<CertificateHashes>>>,
+    /// QUIC の transport 設定 (v1.0 で追加)
+    transport: TransportOptions,
+    /// 証明書の差し替えを監視するタスクの停止 guard (= サーバーの drop / 再 bind で止まる、 v1.0)
+    cert_watch: Option<DropGuard>,
+}
+
+/// leaf 証明書の SHA-256 ハッシュ (= 2 形式)
+#[derive(Debug, Clone)]
+struct CertificateHashes {
+    /// BytesArray 形式。 ブラウザの `serverCertificateHashes` pinning 用
+    bytes_array: String,
+    /// 区切り無しの 64 文字 hex。 TS SDK の `trust.ts` (`certHash`) はこの形式を期待する
+    hex: String,
+}
+
+impl CertificateHashes {
+    fn of(identity: &Identity) -> Self {
+        let digest = identity.certificate_chain().as_slice()[0].hash();
+        Self {
+            bytes_array: digest.fmt(wtransport::tls::Sha256DigestFmt::BytesArray),
+            // ブラウザの `serverCertificateHashes` は leaf 証明書 DER 全体の SHA-256 を取る。
+            hex: digest.as_ref().iter().map(|b| format!("{b:02x}")).collect(),
+        }
+    }
 }
 
 impl WebTransportServer {
@@ -162,11 +196,21 @@ impl WebTransportServer {
             server,
             cert_source,
             endpoint: None,
-            certificate_hash: None,
-            certificate_hash_hex: None,
+            certificate_hashes: Arc::new(RwLock::new(None)),
+            transport: TransportOptions::default(),
+            cert_watch: None,
         }
     }
 
+    /// QUIC の transport 設定を指定する（ビルダーパターン、v1.0 で追加）
+    ///
+    /// keep-alive / idle timeout などは raw QUIC 側と同じ [`TransportOptions`] で
+    /// 揃えられる。 単方向 stream は HTTP/3 が使うため制限しない。 `bind` 時に検証する。
+    pub fn with_transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport = options;
+        self
+    }
+
     /// dev quickstart: `localhost` 自己署名証明書で構成する。
     pub fn dev(server: Arc<ProtocolServer>) -> Self {
         Self::new(server, CertSource::dev_localhost())
@@ -175,37 +219,82 @@ impl WebTransportServer {
     /// 指定アドレスに bind する。
     ///
     /// [`CertSource`] を `wtransport::Identity` へ変換し、 HTTP/3 over QUIC の
-    /// エンドポイントを開く。
+    /// エンドポイントを開く。 [`CertSource::Reloadable`] なら証明書の差し替えを
+    /// 監視するタスクも起動する (v1.0 で追加)。
     pub async fn bind(&mut self, addr: SocketAddr) -> Result<()> {
         let identity = cert_source_to_identity(&self.cert_source)?;
-
         // identity は ServerConfig へ move されるため、 先に cert hash を控える。
-        let digest = identity.certificate_chain().as_slice()[0].hash();
-        let cert_hash = digest.fmt(wtransport::tls::Sha256DigestFmt::BytesArray);
-        // TS SDK 向けの区切り無し 64 文字 hex。 ブラウザの
-        // `serverCertificateHashes` は leaf 証明書 DER 全体の SHA-256 を取る。
-        let cert_hash_hex: String = digest.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+        let hashes = CertificateHashes::of(&identity);
 
-        let config = ServerConfig::builder()
-            .with_bind_address(addr)
-            .with_identity(identity)
-            .keep_alive_interval(Some(std::time::Duration::from_secs(10)))
-            .max_idle_timeout(Some(std::time::Duration::from_secs(60)))
-            .context("wtransport max_idle_timeout 設定に失敗")?
-            .build();
-
-        let endpoint = Endpoint::server(config).context("WebTransport endpoint の生成に失敗")?;
+        let endpoint = Endpoint::server(server_config(addr, identity, &self.transport)?)
+            .context("WebTransport endpoint の生成に失敗")?;
+        let endpoint = Arc::new(endpoint);
 
         info!(
             "WebTransport server bound to {} (cert hash: {})",
-            addr, cert_hash
+            addr, hashes.bytes_array
         );
+        *self
+            .certificate_hashes
+            .write()
+            .expect("cert hash lock poisoned") = Some(hashes);
+
+        // 前の bind の監視タスクはここで止まる
+        self.cert_watch = None;
+        if let CertSource::Reloadable(reloader) = &self.cert_source {
+            let mut rotations = reloader.subscribe();
+            rotations.mark_unchanged();
+            let endpoint = Arc::downgrade(&endpoint);
+            let cert_source = self.cert_source.clone();
+            let hashes = Arc::clone(&self.certificate_hashes);
+            let transport = self.transport.clone();
+            let stop = CancellationToken::new();
+            self.cert_watch = Some(stop.clone().drop_guard());
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = stop.cancelled() => break,
+                        changed = rotations.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                            if let Err(e) =
+                                reload_endpoint(&endpoint, addr, &cert_source, &transport, &hashes)
+                            {
+                                warn!("WebTransport certificate reload failed: {:#}", e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
         self.endpoint = Some(endpoint);
-        self.certificate_hash = Some(cert_hash);
-        self.certificate_hash_hex = Some(cert_hash_hex);
         Ok(())
     }
 
+    /// 証明書を `cert_source` から読み直して endpoint に反映する（v1.0 で追加）
+    ///
+    /// 新しいセッションから新しい証明書を使い、 確立済みのセッションはそのまま。
+    /// [`Self::certificate_hash`] / [`Self::certificate_hash_hex`] も更新される。
+    /// [`CertSource::Reloadable`] は差し替えを自動で反映するので、 呼ぶ必要は無い。
+    pub fn reload_certificate(&self) -> Result<()> {
+        let endpoint = self
+            .endpoint
+            .as_ref()
+            .context("WebTransport server not bound")?;
+        let addr = endpoint
+            .local_addr()
+            .context("WebTransport local_addr の取得に失敗")?;
+        reload_endpoint(
+            &Arc::downgrade(endpoint),
+            addr,
+            &self.cert_source,
+            &self.transport,
+            &self.certificate_hashes,
+        )
+    }
+
     /// bind 済みのローカルアドレスを取得する。
     pub fn local_addr(&self) -> Option<SocketAddr> {
         self.endpoint.as_ref().and_then(|ep| ep.local_addr().ok())
@@ -214,9 +303,14 @@ impl WebTransportServer {
     /// leaf 証明書の SHA-256 ハッシュ (= BytesArray 形式) を取得する。
     ///
     /// ブラウザ側 (`WebTransport` の `serverCertificateHashes`) がこの値で
-    /// 自己署名証明書を pin する。 `bind` 前は `None`。
-    pub fn certificate_hash(&self) -> Option<&str> {
-        self.certificate_hash.as_deref()
+    /// 自己署名証明書を pin する。 `bind` 前は `None`。 v1.0 以降は証明書の
+    /// 差し替えで変わるため、 所有した `String` を返す。
+    pub fn certificate_hash(&self) -> Option<String> {
+        self.certificate_hashes
+            .read()
+            .expect("cert hash lock poisoned")
+            .as_ref()
+            .map(|h| h.bytes_array.clone())
     }
 
     /// leaf 証明書の SHA-256 ハッシュを区切り無しの 64 文字 hex で取得する。
@@ -224,8 +318,12 @@ impl WebTransportServer {
     /// [`Self::certificate_hash`] が返す `BytesArray` 形式に対し、 こちらは
     /// TS SDK の `trust.ts` (`{ certHash }`) がそのまま受け取れる plain hex。
     /// `bind` 前は `None`。
-    pub fn certificate_hash_hex(&self) -> Option<&str> {
-        self.certificate_hash_hex.as_deref()
+    pub fn certificate_hash_hex(&self) -> Option<String> {
+        self.certificate_hashes
+            .read()
+            .expect("cert hash lock poisoned")
+            .as_ref()
+            .map(|h| h.hex.clone())
     }
 
     /// 接続の待ち受けを開始する (= 終了までブロック)。
@@ -263,6 +361,15 @@ impl WebTransportServer {
         loop {
             tokio::select! {
                 incoming = endpoint.accept() => {
+                    // v1.0: graceful shutdown 中は新規セッションを拒否
+                    if self.server.is_draining() {
+                        info!(
+                            "Refusing WebTransport session from {} (server draining)",
+                            incoming.remote_address()
+                        );
+                        incoming.refuse();
+                        continue;
+                    }
                     let server = Arc::clone(&self.server);
                     tokio::spawn(async move {
                         if let Err(e) = accept_session(incoming, server).await {
@@ -315,6 +422,48 @@ async fn accept_session(
     handle_connection(conn, server, ctx).await
 }
 
+/// bind アドレス、 identity と transport 設定から endpoint の設定を作る
+fn server_config(
+    addr: SocketAddr,
+    identity: Identity,
+    transport: &TransportOptions,
+) -> Result<ServerConfig> {
+    let transport_config = transport
+        .to_transport_config(true)
+        .map_err(|e| anyhow::anyhow!("invalid transport options: {e}"))?;
+    Ok(ServerConfig::builder()
+        .with_bind_address(addr)
+        .with_custom_transport(identity, transport_config)
+        .build())
+}
+
+/// `cert_source` の証明書で endpoint の設定を差し替え、 ハッシュを更新する (v1.0 で追加)
+///
+/// endpoint が drop 済みなら何もしない。
+fn reload_endpoint(
+    endpoint: &Weak<Endpoint<Server>>,
+    addr: SocketAddr,
+    cert_source: &CertSource,
+    transport: &TransportOptions,
+    hashes: &RwLock<Option<CertificateHashes>>,
+) -> Result<()> {
+    let Some(endpoint) = endpoint.upgrade() else {
+        return Ok(());
+    };
+    let identity = cert_source_to_identity(cert_source)?;
+    let new_hashes = CertificateHashes::of(&identity);
+    // rebind しない (= bind アドレスは無視され、 socket と既存セッションはそのまま)
+    endpoint
+        .reload_config(server_config(addr, identity, transport)?, false)
+        .context("WebTransport endpoint の設定の再読み込みに失敗")?;
+    info!(
+        "WebTransport certificate reloaded (cert hash: {})",
+        new_hashes.bytes_array
+    );
+    *hashes.write().expect("cert hash lock poisoned") = Some(new_hashes);
+    Ok(())
+}
+
 /// [`CertSource`] を `wtransport::Identity` へ変換する。
 ///
 /// raw QUIC 側と TLS マテリアルを共有するためのブリッジ。 `cert.rs` の
@@ -367,6 +516,30 @@ mod tests {
         );
     }
 
+    /// transport 設定は bind 時に検証され、 矛盾していれば bind が失敗すること (v1.0)。
+    #[tokio::test]
+    async fn webtransport_server_validates_transport_options() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let mut wt = WebTransportServer::dev(Arc::new(ProtocolServer::new()))
+            .with_transport_options(
+                TransportOptions::default()
+                    .with_keep_alive_interval(Some(std::time::Duration::from_secs(5)))
+                    .with_max_idle_timeout(Some(std::time::Duration::from_secs(120))),
+            );
+        wt.bind(addr)
+            .await
+            .expect("妥当な transport 設定なら bind は成功するべき");
+
+        let mut invalid = WebTransportServer::dev(Arc::new(ProtocolServer::new()))
+            .with_transport_options(
+                TransportOptions::default().with_max_concurrent_bidi_streams(0),
+            );
+        assert!(
+            invalid.bind(addr).await.is_err(),
+            "矛盾した transport 設定は bind で拒否されるべき"
+        );
+    }
+
     /// WebTransport サーバーが ephemeral port に bind でき、 bind 後に cert hash が
     /// 確定し、 local_addr が取得できること (= 実 ingress の起動確認)。
     #[tokio::test]
@@ -401,4 +574,63 @@ mod tests {
             "plain hex は hex 文字のみであるべき"
         );
     }
+
+    /// [`CertSource::Reloadable`] で bind したサーバーは、 証明書の差し替えで
+    /// cert hash が更新されること。
+    #[tokio::test]
+    async fn webtransport_server_refreshes_cert_hash_on_rotation() {
+        use super::super::cert::CertReloader;
+
+        let reloader = CertReloader::new(CertSource::dev_localhost()).unwrap();
+        let protocol = Arc::new(ProtocolServer::new());
+        let mut wt = WebTransportServer::new(protocol, CertSource::Reloadable(reloader.clone()));
+        wt.bind("127.0.0.1:0".parse().unwrap())
+            .await
+            .expect("WebTransport bind は成功するべき");
+        let before = wt.certificate_hash_hex().expect("bind 後は確定するべき");
+        // QUIC 側と同じ cert (= pin する fingerprint と一致する)
+        assert_eq!(before, reloader.fingerprint());
+
+        // 自己署名 source の reload は新しい cert を生成する
+        let rotated = reloader.reload().unwrap();
+        assert_ne!(rotated, before);
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while wt.certificate_hash_hex().as_deref() == Some(before.as_str()) {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "cert hash は差し替えで更新されるべき"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(wt.certificate_hash_hex().unwrap(), rotated);
+
+        // 明示的な reload は reloader の現在の cert を読み直すだけ (= 再生成しない)
+        wt.reload_certificate().unwrap();
+        assert_eq!(wt.certificate_hash_hex().unwrap(), rotated);
+    }
+
+    /// サーバーを drop すると、 証明書の差し替えを待たずに監視タスクが止まること。
+    #[tokio::test]
+    async fn webtransport_cert_watch_stops_when_server_dropped() {
+        use super::super::cert::CertReloader;
+
+        let reloader = CertReloader::new(CertSource::dev_localhost()).unwrap();
+        let protocol = Arc::new(ProtocolServer::new());
+        let mut wt = WebTransportServer::new(protocol, CertSource::Reloadable(reloader.clone()));
+        wt.bind("127.0.0.1:0".parse().unwrap())
+            .await
+            .expect("WebTransport bind は成功するべき");
+        // 監視タスクは cert hash の格納先を共有している
+        let hashes = Arc::downgrade(&wt.certificate_hashes);
+        drop(wt);
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while hashes.upgrade().is_some() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "監視タスクはサーバーの drop で止まるべき"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::TypeRegistry;
+use crate::network::policy::ChannelPolicy;
 use club_kdl::KdlDeserialize;
 use std::collections::HashMap;
 
@@ -104,6 +105,14 @@ pub struct ChannelMessage {
     #[kdl(argument)]
     pub name: String,
 
+    /// Server-streaming 応答か (v1.0 で追加)
+    ///
+    /// `returns "X" stream=#true` のとき `true`。 サーバーは `X` を 0 個以上
+    /// `send_stream_item` で返し、 `end_stream` で終端する。 `returns` 以外の
+    /// ブロックでは無視される。
+    #[kdl(property, default)]
+    pub stream: bool,
+
     /// フィールド定義
     #[kdl(children, name = "field")]
     pub fields: Vec<Field>,
@@ -124,6 +133,21 @@ pub struct ChannelRequest {
     /// レスポンス型（returns ブロック）
     #[kdl(child)]
     pub returns: Option<ChannelMessage>,
+
+    /// 同じ request を複数回処理しても結果が変わらないか (v1.0 で追加)
+    ///
+    /// `idempotent=#true` を付けた request は replay されても安全とみなす。
+    /// 全ての request が idempotent な channel は 0-RTT で open できる
+    /// ([`Channel::is_replay_safe`])。
+    #[kdl(property, default)]
+    pub idempotent: bool,
+}
+
+impl ChannelRequest {
+    /// `returns ... stream=#true` な Server-streaming request か
+    pub fn is_streaming(&self) -> bool {
+        self.returns.as_ref().is_some_and(|r| r.stream)
+    }
 }
 
 /// チャネル内 Event 定義
@@ -171,6 +195,20 @@ pub struct Channel {
     #[kdl(property)]
     pub channel_id: Option<u64>,
 
+    /// channel open に必要な role (v1.0 で追加)
+    ///
+    /// カンマ区切りで複数指定でき、 いずれか 1 つを持てば open できる (= any-of)。
+    /// 取得は [`Self::policy`] で実行。
+    #[kdl(property, rename = "require-role")]
+    pub require_role: Option<String>,
+
+    /// channel open を許す接続元アドレス (v1.0 で追加)
+    ///
+    /// カンマ区切りの CIDR (= `"10.0.0.0/8,::1"`)。 prefix 省略時は単一アドレス。
+    /// 取得は [`Self::policy`] で実行。
+    #[kdl(property, rename = "allow-from")]
+    pub allow_from: Option<String>,
+
     /// Request/Response 定義（新構文）
     #[kdl(children, name = "request")]
     pub requests: Vec<ChannelRequest>,
@@ -198,13 +236,36 @@ impl Channel {
         self.backend.unwrap_or_default()
     }
 
+    /// `require-role` / `allow-from` から channel のアクセスポリシーを組み立てる
+    ///
+    /// どちらも指定が無ければ `None` (= 制限なし)。
+    pub fn policy(&self) -> Result<Option<ChannelPolicy>, String> {
+        ChannelPolicy::from_attributes(self.require_role.as_deref(), self.allow_from.as_deref())
+            .map_err(|e| format!("channel \"{}\": {}", self.name, e))
+    }
+
+    /// 0-RTT (= replay され得る early data) で open してよい channel か (v1.0 で追加)
+    ///
+    /// stream backend で、 request を 1 つ以上持ち、 その全てが `idempotent=#true`
+    /// のとき true。 event / 旧 channel 構文はサーバー側の副作用を判断できないので
+    /// 持っていれば false。
+    pub fn is_replay_safe(&self) -> bool {
+        self.backend() == ChannelBackend::Stream
+            && !self.requests.is_empty()
+            && self.requests.iter().all(|r| r.idempotent)
+            && self.events.is_empty()
+            && self.send.is_none()
+    }
+
     /// Channel の semantic validation を行う。
     ///
     /// 検証項目:
     /// - `backend="datagram"` の場合は `channel_id` が必須、 0 は予約 (= sentinel)
     /// - `backend="stream"` (= default) の場合は `channel_id` を指定しても無視 (= warning は出さない)
+    /// - `require-role` / `allow-from` が解釈できること
     /// - `backend="datagram"` の channel は `request` ブロックを持てない (= datagram は応答不可)
     pub fn validate(&self) -> Result<(), String> {
+        self.policy()?;
         match self.backend() {
             ChannelBackend::Datagram => {
                 let id = self.channel_id.ok_or_else(|| {
@@ -29,6 +29,7 @@ pub use crate::network::client::{ClientConnectionEvent, ClientConnectionEventRec
 // エラー型
 pub use crate::network::ErrorCategory;
 pub use crate::network::NetworkError as UnisonNetworkError;
+pub use crate::network::ProtocolError;
 pub use crate::parser::ParseError as UnisonParseError;
 
 // メインエントリポイント
@@ -1,4 +1,14 @@
-use unison::network::{MessageType, ProtocolMessage};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use unison::ProtocolServer;
+use unison::network::quic::QuicServer;
+use unison::network::{
+    ConnectionEvent, ConnectionEventReceiver, ConnectionId, MessageType, ProtocolMessage,
+    ServerHandle,
+};
 
 /// テスト用の ProtocolMessage を生成
 #[allow(dead_code)]
@@ -37,3 +47,40 @@ pub fn make_identity(name: &str, channels: &[&str]) -> unison::network::identity
     }
     identity
 }
+
+/// client の接続先 URL (= `[ip]:port`)
+#[allow(dead_code)]
+pub fn url(addr: SocketAddr) -> String {
+    format!("[{}]:{}", addr.ip(), addr.port())
+}
+
+/// `[::1]:0` で listen を始め、 handle と URL を返す
+#[allow(dead_code)]
+pub async fn spawn_server(server: Arc<ProtocolServer>) -> anyhow::Result<(ServerHandle, String)> {
+    let handle = server.spawn_listen_shared("[::1]:0").await?;
+    let url = url(handle.local_addr());
+    Ok((handle, url))
+}
+
+/// builder で組んだ QUIC サーバーを `[::1]:0` で起動し、 bind した address を返す
+#[allow(dead_code)]
+pub async fn spawn_quic_server(mut quic: QuicServer) -> anyhow::Result<SocketAddr> {
+    quic.bind("[::1]:0").await?;
+    let addr = quic.local_addr().expect("bound");
+    tokio::spawn(async move { quic.start().await });
+    Ok(addr)
+}
+
+/// server の Connected event から client の connection_id を取る
+#[allow(dead_code)]
+pub async fn connected_id(events: &mut ConnectionEventReceiver) -> ConnectionId {
+    loop {
+        let event = timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("Connected event timed out")
+            .expect("event channel closed");
+        if let ConnectionEvent::Connected { context, .. } = event {
+            return context.connection_id;
+        }
+    }
+}
@@ -110,3 +110,96 @@ async fn test_integ_connection_context_identity_flow() {
     assert_eq!(retrieved.name, "ctx-server");
     assert_eq!(retrieved.channels.len(), 1);
 }
+
+#[test]
+fn test_integ_channel_update_apply() {
+    let mut identity = common::make_identity("srv", &["events", "query"]);
+
+    identity.apply(&ChannelUpdate::Added(ChannelInfo {
+        name: "alerts".to_string(),
+        direction: ChannelDirection::ServerToClient,
+        lifetime: "transient".to_string(),
+        status: ChannelStatus::Available,
+    }));
+    assert_eq!(identity.channels.len(), 3);
+
+    // 同名の Added は置き換え (= 重複しない)
+    identity.apply(&ChannelUpdate::Added(ChannelInfo {
+        name: "alerts".to_string(),
+        direction: ChannelDirection::ServerToClient,
+        lifetime: "transient".to_string(),
+        status: ChannelStatus::Busy,
+    }));
+    assert_eq!(identity.channels.len(), 3);
+    assert_eq!(identity.channels[2].status, ChannelStatus::Busy);
+
+    identity.apply(&ChannelUpdate::StatusChanged {
+        name: "query".to_string(),
+        status: ChannelStatus::Unavailable,
+    });
+    assert_eq!(identity.channels[1].status, ChannelStatus::Unavailable);
+
+    // 知らないチャネルの StatusChanged は無視
+    identity.apply(&ChannelUpdate::StatusChanged {
+        name: "missing".to_string(),
+        status: ChannelStatus::Busy,
+    });
+    assert_eq!(identity.channels.len(), 3);
+
+    identity.apply(&ChannelUpdate::Removed("events".to_string()));
+    let names: Vec<_> = identity.channels.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["query", "alerts"]);
+}
+
+#[test]
+fn test_integ_channel_update_protocol_message_round_trip() {
+    let update = ChannelUpdate::StatusChanged {
+        name: "busy-ch".to_string(),
+        status: ChannelStatus::Busy,
+    };
+    let msg = update.to_protocol_message();
+    assert_eq!(msg.method, "__channel_update");
+    assert_eq!(msg.msg_type, MessageType::Event);
+
+    match ChannelUpdate::from_protocol_message(&msg).unwrap() {
+        ChannelUpdate::StatusChanged { name, status } => {
+            assert_eq!(name, "busy-ch");
+            assert_eq!(status, ChannelStatus::Busy);
+        }
+        _ => panic!("Expected StatusChanged variant"),
+    }
+}
+
+#[tokio::test]
+async fn test_integ_server_channel_status_in_identity() {
+    use unison::network::ProtocolServer;
+
+    let server = ProtocolServer::with_identity("test-srv", "1.0.0", "ns");
+    server
+        .register_channel("ch1", |_ctx, _stream| async { Ok(()) })
+        .await;
+    assert_eq!(
+        server.channel_status("ch1").await,
+        Some(ChannelStatus::Available)
+    );
+
+    // 未登録のチャネルには状態を設定できない
+    assert!(!server.set_channel_status("nope", ChannelStatus::Busy).await);
+    assert_eq!(server.channel_status("nope").await, None);
+
+    assert!(server.set_channel_status("ch1", ChannelStatus::Busy).await);
+    let identity = server.build_identity().await;
+    assert_eq!(identity.channels[0].status, ChannelStatus::Busy);
+
+    // 解除すると Identity から消え、 状態も残らない
+    assert!(server.unregister_channel("ch1").await);
+    assert!(!server.unregister_channel("ch1").await);
+    assert!(server.build_identity().await.channels.is_empty());
+    server
+        .register_channel("ch1", |_ctx, _stream| async { Ok(()) })
+        .await;
+    assert_eq!(
+        server.channel_status("ch1").await,
+        Some(ChannelStatus::Available)
+    );
+}
@@ -345,3 +345,105 @@ fn test_channel_mixed_stream_and_datagram_channels() {
     assert_eq!(protocol.channels[2].backend(), ChannelBackend::Datagram);
     assert_eq!(protocol.channels[2].channel_id, Some(2));
 }
+
+/// `returns "X" stream=#true` で Server-streaming request を宣言できる (省略時は unary)
+#[test]
+fn test_channel_request_returns_stream() {
+    let schema = r#"
+        protocol "test" version="1.0.0" {
+            channel "memory" from="client" lifetime="persistent" {
+                request "ListAll" {
+                    returns "MemoryItem" stream=#true { field "id" type="string" }
+                }
+                request "Get" {
+                    field "id" type="string"
+                    returns "Memory" { field "content" type="string" }
+                }
+            }
+        }
+    "#;
+    let parser = SchemaParser::new();
+    let protocol = parser.parse(schema).unwrap().protocol.unwrap();
+    let ch = &protocol.channels[0];
+    assert!(ch.requests[0].is_streaming());
+    assert_eq!(ch.requests[0].returns.as_ref().unwrap().name, "MemoryItem");
+    assert!(!ch.requests[1].is_streaming());
+}
+
+// === v1.0: channel のアクセスポリシー (`require-role` / `allow-from`) ===
+
+/// `require-role` / `allow-from` はカンマ区切りで `Channel::policy()` に入る
+#[test]
+fn test_channel_access_policy_attributes() {
+    let schema = r#"
+        protocol "test" version="1.0.0" {
+            channel "admin" from="client" lifetime="persistent" require-role="ops,sre" allow-from="10.0.0.0/8,::1" {
+                request "Drain" { returns "Ack" { field "ok" type="bool" } }
+            }
+            channel "public" from="client" lifetime="transient" {
+                request "Ping" { returns "Pong" { field "ok" type="bool" } }
+            }
+        }
+    "#;
+    let parser = SchemaParser::new();
+    let protocol = parser.parse(schema).unwrap().protocol.unwrap();
+    let admin = protocol.channels[0].policy().unwrap().unwrap();
+    assert_eq!(admin.required_roles, vec!["ops", "sre"]);
+    assert_eq!(admin.allowed_sources.len(), 2);
+    assert!(admin.allowed_sources[0].contains("10.1.2.3".parse().unwrap()));
+    assert!(protocol.channels[1].policy().unwrap().is_none());
+}
+
+/// 解釈できない `allow-from` → validation error
+#[test]
+fn test_channel_invalid_allow_from_fails() {
+    let schema = r#"
+        protocol "test" version="1.0.0" {
+            channel "admin" from="client" lifetime="persistent" allow-from="10.0.0.0/40" {
+                request "Drain" { returns "Ack" { field "ok" type="bool" } }
+            }
+        }
+    "#;
+    let parser = SchemaParser::new();
+    let err = parser
+        .parse(schema)
+        .expect_err("invalid allow-from must fail");
+    let msg = format!("{}", err);
+    assert!(
+        msg.contains("admin"),
+        "error must name the channel: {}",
+        msg
+    );
+}
+
+// === v1.0: `idempotent=#true` と 0-RTT ===
+
+/// 全 request が `idempotent=#true` な stream channel だけが replay-safe
+#[test]
+fn test_channel_idempotent_requests_are_replay_safe() {
+    let schema = r#"
+        protocol "test" version="1.0.0" {
+            channel "catalog" from="client" lifetime="persistent" {
+                request "Get" idempotent=#true { returns "Item" { field "id" type="string" } }
+                request "List" idempotent=#true { returns "Item" stream=#true { field "id" type="string" } }
+            }
+            channel "orders" from="client" lifetime="persistent" {
+                request "Get" idempotent=#true { returns "Order" { field "id" type="string" } }
+                request "Place" { returns "Order" { field "id" type="string" } }
+            }
+            channel "feed" from="server" lifetime="persistent" {
+                event "Update" { field "value" type="string" }
+            }
+        }
+    "#;
+    let parser = SchemaParser::new();
+    let protocol = parser.parse(schema).unwrap().protocol.unwrap();
+    let catalog = &protocol.channels[0];
+    assert!(catalog.requests.iter().all(|r| r.idempotent));
+    assert!(catalog.is_replay_safe());
+    let orders = &protocol.channels[1];
+    assert!(orders.requests[0].idempotent);
+    assert!(!orders.requests[1].idempotent, "省略時は false");
+    assert!(!orders.is_replay_safe());
+    assert!(!protocol.channels[2].is_replay_safe());
+}
@@ -0,0 +1,225 @@
+//! Medium x Integration: 接続認証テスト
+//!
+//! `ProtocolServer::set_authenticator()` を設定したサーバーに対し、 bearer token と
+//! HMAC challenge で `connect()` が認証でき、 principal が channel handler の
+//! `ctx.principal()` に見えること、 不正な資格情報は `NetworkError::Unauthenticated`
+//! になり、 資格情報なしの client の channel open が nack されること、 認証の試行が
+//! 接続ごとに 1 回に限られることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::Level;
+
+use unison::network::frame::{
+    AUTH_METHOD, FRAME_TYPE_PROTOCOL, read_typed_frame, write_typed_frame,
+};
+use unison::network::quic::QuicClient;
+use unison::network::{
+    ChannelRouter, Credentials, HmacAuthenticator, MessageType, NetworkError, Principal,
+    ProtocolError, ProtocolFrame, ProtocolMessage, TokenAuthenticator, TrustAnchors,
+};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// principal の id と roles を返す channel
+fn whoami_router() -> ChannelRouter {
+    ChannelRouter::new().on_request("WhoAmI", |ctx, _req: serde_json::Value| async move {
+        let principal = ctx.connection.principal().cloned();
+        Ok(serde_json::json!({
+            "id": principal.as_ref().map(|p| p.id.clone()),
+            "admin": principal.as_ref().is_some_and(|p| p.has_role("admin")),
+        }))
+    })
+}
+
+async fn spawn_server(server: ProtocolServer) -> Result<(unison::network::ServerHandle, String)> {
+    server
+        .register_channel_router("whoami", whoami_router())
+        .await;
+    common::spawn_server(Arc::new(server)).await
+}
+
+/// bearer token: 正しい token は principal 付きで接続でき、 誤った token は connect が失敗する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_auth_bearer_token() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .set_authenticator(
+            TokenAuthenticator::new()
+                .with_token("good-token", Principal::new("svc-a").with_roles(["admin"])),
+        )
+        .await;
+    let (handle, url) = spawn_server(server).await?;
+
+    let client = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("good-token"));
+    client.connect(&url).await?;
+    assert_eq!(
+        client.context().principal().map(|p| p.id.as_str()),
+        Some("svc-a")
+    );
+
+    let channel = client.open_channel("whoami").await?;
+    let resp: serde_json::Value = channel.request("WhoAmI", &serde_json::json!({})).await?;
+    assert_eq!(resp["id"], "svc-a");
+    assert_eq!(resp["admin"], true);
+    channel.close().await?;
+    client.disconnect().await?;
+
+    let bad = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("bad-token"));
+    match bad.connect(&url).await {
+        Err(NetworkError::Unauthenticated(reason)) => {
+            assert!(reason.contains("invalid token"), "{reason}")
+        }
+        other => panic!("expected Unauthenticated, got {other:?}"),
+    }
+    assert!(!bad.is_connected().await);
+
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// HMAC challenge: 共有鍵で署名できれば認証され、 鍵が違えば connect が失敗する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_auth_hmac_challenge() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .set_authenticator(HmacAuthenticator::new().with_key(
+            "key-1",
+            b"shared-secret".to_vec(),
+            Principal::new("agent-7"),
+        ))
+        .await;
+    let (handle, url) = spawn_server(server).await?;
+
+    let client = ProtocolClient::new_default()?
+        .with_credentials(Credentials::hmac("key-1", b"shared-secret".to_vec()));
+    client.connect(&url).await?;
+    let channel = client.open_channel("whoami").await?;
+    let resp: serde_json::Value = channel.request("WhoAmI", &serde_json::json!({})).await?;
+    assert_eq!(resp["id"], "agent-7");
+    assert_eq!(resp["admin"], false);
+    channel.close().await?;
+    client.disconnect().await?;
+
+    let wrong = ProtocolClient::new_default()?
+        .with_credentials(Credentials::hmac("key-1", b"wrong-secret".to_vec()));
+    match wrong.connect(&url).await {
+        Err(NetworkError::Unauthenticated(reason)) => {
+            assert!(reason.contains("invalid signature"), "{reason}")
+        }
+        other => panic!("expected Unauthenticated, got {other:?}"),
+    }
+
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// 資格情報なしの client は接続できるが channel open は `unauthenticated` で nack される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_auth_required_for_channel_open() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .set_authenticator(TokenAuthenticator::new().with_token("t", Principal::new("x")))
+        .await;
+    let (handle, url) = spawn_server(server).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url).await?;
+    assert!(client.context().principal().is_none());
+    match client.open_channel("whoami").await {
+        Err(NetworkError::Protocol(msg)) => assert!(msg.contains("unauthenticated"), "{msg}"),
+        Err(e) => panic!("expected unauthenticated nack, got {e:?}"),
+        Ok(_) => panic!("expected unauthenticated nack, got Ok"),
+    }
+    client.disconnect().await?;
+
+    // authenticator の無いサーバーに資格情報を出すと拒否される
+    let open = ProtocolServer::new();
+    let (open_handle, open_url) = spawn_server(open).await?;
+    let eager = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("t"));
+    match eager.connect(&open_url).await {
+        Err(NetworkError::Unauthenticated(reason)) => {
+            assert!(reason.contains("does not accept credentials"), "{reason}")
+        }
+        other => panic!("expected Unauthenticated, got {other:?}"),
+    }
+
+    handle.shutdown().await?;
+    open_handle.shutdown().await?;
+    Ok(())
+}
+
+/// 生の `__auth` ストリームで bearer token を出し、 サーバーの応答を返す
+async fn raw_bearer_attempt(
+    connection: &quinn::Connection,
+    token: &str,
+) -> Result<ProtocolMessage> {
+    let (mut send, mut recv) = connection.open_bi().await?;
+    let hello = ProtocolMessage::new_with_json(
+        1,
+        AUTH_METHOD.to_string(),
+        MessageType::Request,
+        serde_json::json!({ "scheme": "bearer", "token": token }),
+    )?;
+    let frame = hello.into_control_frame()?;
+    write_typed_frame(&mut send, FRAME_TYPE_PROTOCOL, &frame.to_bytes()).await?;
+    let (_, bytes) = read_typed_frame(&mut recv).await?;
+    Ok(ProtocolMessage::from_frame(&ProtocolFrame::from_bytes(
+        &bytes,
+    )?)?)
+}
+
+/// 認証の試行は接続ごとに 1 回 — 失敗した接続では正しい token も拒否される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_auth_single_attempt_per_connection() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .set_authenticator(
+            TokenAuthenticator::new().with_token("good-token", Principal::new("svc-a")),
+        )
+        .await;
+    let (handle, url) = spawn_server(server).await?;
+
+    let client = QuicClient::builder()
+        .trust_anchors(TrustAnchors::SkipVerification)
+        .build()?;
+    client.connect(&url).await?;
+    let connection = client.connection().read().await.clone().expect("connected");
+
+    let first = raw_bearer_attempt(&connection, "bad-token").await?;
+    assert_eq!(first.msg_type, MessageType::Error);
+    let err: ProtocolError = serde_json::from_value(first.payload_as_value()?)?;
+    assert_eq!(err.code, ProtocolError::UNAUTHENTICATED);
+    assert!(err.message.contains("invalid token"), "{}", err.message);
+
+    let second = raw_bearer_attempt(&connection, "good-token").await?;
+    assert_eq!(second.msg_type, MessageType::Error);
+    let err: ProtocolError = serde_json::from_value(second.payload_as_value()?)?;
+    assert!(err.message.contains("already attempted"), "{}", err.message);
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
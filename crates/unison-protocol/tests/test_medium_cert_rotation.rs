@@ -0,0 +1,110 @@
+//! Medium x Integration: 証明書の hot rotation テスト
+//!
+//! `CertSource::Reloadable` で bind した QUIC サーバーの証明書ファイルを差し替えて
+//! `CertReloader::reload()` すると、 確立済みの接続は切れずに使い続けられ、
+//! 新しい接続は新しい証明書で handshake することを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::{spawn_quic_server, url};
+use rustls::pki_types::CertificateDer;
+use unison::network::cert::{CertReloader, CertSource};
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::network::{ChannelRouter, UnisonChannel};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `localhost` の自己署名 cert を書き出し、 client の trust anchor 用に DER を返す
+fn write_cert(cert_path: &Path, key_path: &Path) -> Result<CertificateDer<'static>> {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".into(), "::1".into()])?;
+    std::fs::write(cert_path, cert.cert.pem())?;
+    std::fs::write(key_path, cert.signing_key.serialize_pem())?;
+    Ok(cert.cert.der().clone())
+}
+
+fn client_trusting(anchor: &CertificateDer<'static>) -> Result<ProtocolClient> {
+    Ok(ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::Custom(vec![anchor.clone()]))
+            .build()?,
+    ))
+}
+
+async fn ping(channel: &UnisonChannel) -> Result<()> {
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+    Ok(())
+}
+
+/// 差し替え後も既存の接続は生き、 新しい接続は新しい証明書だけを受け入れる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_cert_rotation_keeps_connections() -> Result<()> {
+    init_tracing();
+
+    let dir = tempfile::tempdir()?;
+    let (cert_path, key_path) = (dir.path().join("tls.crt"), dir.path().join("tls.key"));
+    let old_anchor = write_cert(&cert_path, &key_path)?;
+    let reloader = CertReloader::new(CertSource::FromFile {
+        cert_path: cert_path.clone(),
+        key_path: key_path.clone(),
+    })?;
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router(
+            "ping",
+            ChannelRouter::new().on_request("Ping", |_ctx, _req: serde_json::Value| async move {
+                Ok(serde_json::json!({ "ok": true }))
+            }),
+        )
+        .await;
+    let quic = QuicServer::builder(server)
+        .cert_source(CertSource::Reloadable(reloader.clone()))
+        .build();
+    let addr = spawn_quic_server(quic).await?;
+
+    let existing = client_trusting(&old_anchor)?;
+    existing.connect(&url(addr)).await?;
+    let channel = existing.open_channel("ping").await?;
+    ping(&channel).await?;
+
+    let old_fingerprint = reloader.fingerprint();
+    let new_anchor = write_cert(&cert_path, &key_path)?;
+    assert_ne!(reloader.reload()?, old_fingerprint);
+
+    // 確立済みの接続と channel はそのまま
+    ping(&channel).await?;
+    let second = existing.open_channel("ping").await?;
+    ping(&second).await?;
+
+    let fresh = client_trusting(&new_anchor)?;
+    fresh.connect(&url(addr)).await?;
+    let channel = fresh.open_channel("ping").await?;
+    ping(&channel).await?;
+    fresh.disconnect().await?;
+
+    // 古い証明書だけを信頼する新しい client は handshake で失敗する
+    let stale = client_trusting(&old_anchor)?;
+    let result = timeout(Duration::from_secs(5), stale.connect(&url(addr))).await?;
+    assert!(result.is_err(), "stale trust anchor must be rejected");
+
+    existing.disconnect().await?;
+    Ok(())
+}
@@ -0,0 +1,239 @@
+//! Medium x Integration: UnisonChannel 受信キューの backpressure テスト
+//!
+//! event consumer が遅い (= recv() しない) 状態でも、 `OverflowPolicy` が
+//! `Block` 以外 (= デフォルトを含む) なら同じ channel 上の request/response が
+//! 詰まらないこと、 および受信 Request はキュー深さを超えても捨てられないことを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::Level;
+
+use unison::network::channel::UnisonChannel;
+use unison::network::channel_config::DEFAULT_QUEUE_DEPTH;
+use unison::network::{
+    ChannelConfig, ChannelStats, MessageType, NetworkError, OverflowPolicy, ServerHandle,
+};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `{"count": n}` を受けて Event を n 本 push してから Response を返す
+async fn spawn_flood_server() -> Result<(ServerHandle, String)> {
+    let server = ProtocolServer::new();
+    server
+        .register_channel("flood", |_ctx, stream| async move {
+            let channel: UnisonChannel = UnisonChannel::new(stream);
+            while let Ok(msg) = channel.recv().await {
+                if msg.msg_type != MessageType::Request {
+                    continue;
+                }
+                let count = msg
+                    .payload_as_value()?
+                    .get("count")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                for seq in 0..count {
+                    channel
+                        .send_event("Tick", &serde_json::json!({"seq": seq}))
+                        .await?;
+                }
+                channel
+                    .send_response(msg.id, &msg.method, &serde_json::json!({"sent": count}))
+                    .await?;
+            }
+            Ok(())
+        })
+        .await;
+    common::spawn_server(Arc::new(server)).await
+}
+
+/// DropOldest: event を読まなくても Response が届き、 最新 N 件だけが残る
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_drop_oldest_keeps_responses_flowing() -> Result<()> {
+    init_tracing();
+    let (handle, addr) = spawn_flood_server().await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let config = ChannelConfig::default()
+        .with_event_queue_depth(4)
+        .with_overflow(OverflowPolicy::DropOldest)
+        .with_request_timeout(Duration::from_secs(5));
+    let channel = client.open_channel_with_config("flood", config).await?;
+
+    let resp: serde_json::Value = channel
+        .request("Flood", &serde_json::json!({"count": 64}))
+        .await?;
+    assert_eq!(resp["sent"], 64);
+
+    let stats = channel.stats();
+    assert_eq!(stats.events.len, 4);
+    assert_eq!(stats.events.dropped, 60);
+    assert_eq!(stats.raw.overflows, 0);
+
+    // 残っているのは最新 4 件
+    let mut seqs = Vec::new();
+    for _ in 0..4 {
+        let msg = timeout(Duration::from_secs(5), channel.recv()).await??;
+        seqs.push(msg.payload_as_value()?["seq"].as_u64().unwrap());
+    }
+    assert_eq!(seqs, vec![60, 61, 62, 63]);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// CloseWithError: Response は届き、 recv() は溜まった分の後に QueueOverflow を返す
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_close_with_error_reports_overflow() -> Result<()> {
+    init_tracing();
+    let (handle, addr) = spawn_flood_server().await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let config = ChannelConfig::default()
+        .with_event_queue_depth(2)
+        .with_overflow(OverflowPolicy::CloseWithError)
+        .with_request_timeout(Duration::from_secs(5));
+    let channel = client.open_channel_with_config("flood", config).await?;
+
+    let resp: serde_json::Value = channel
+        .request("Flood", &serde_json::json!({"count": 8}))
+        .await?;
+    assert_eq!(resp["sent"], 8);
+
+    for _ in 0..2 {
+        timeout(Duration::from_secs(5), channel.recv()).await??;
+    }
+    match timeout(Duration::from_secs(5), channel.recv()).await? {
+        Err(NetworkError::QueueOverflow { queue, capacity }) => {
+            assert_eq!((queue.as_str(), capacity), ("event", 2));
+        }
+        other => panic!("expected QueueOverflow, got {other:?}"),
+    }
+
+    // キューが閉じても request/response は引き続き使える
+    let resp: serde_json::Value = channel
+        .request("Flood", &serde_json::json!({"count": 0}))
+        .await?;
+    assert_eq!(resp["sent"], 0);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// デフォルト設定: event を読まなくても Response が届く (= recv ループは待たない)
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_default_config_does_not_block_responses() -> Result<()> {
+    init_tracing();
+    let (handle, addr) = spawn_flood_server().await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let config = ChannelConfig::default()
+        .with_event_queue_depth(4)
+        .with_request_timeout(Duration::from_secs(5));
+    assert_eq!(config.overflow, OverflowPolicy::CloseWithError);
+    let channel = client.open_channel_with_config("flood", config).await?;
+
+    let resp: serde_json::Value = channel
+        .request("Flood", &serde_json::json!({"count": 64}))
+        .await?;
+    assert_eq!(resp["sent"], 64);
+    assert_eq!(channel.stats().events.dropped, 60);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// デフォルト設定: キュー深さを超える Request の burst も全て応答される
+/// (= Request は overflow で捨てず、 recv ループが空きを待つ)
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_default_config_serves_request_burst() -> Result<()> {
+    init_tracing();
+
+    let (stats_tx, mut stats_rx) = mpsc::channel::<ChannelStats>(1);
+    let server = ProtocolServer::new();
+    server
+        .register_channel("burst", move |_ctx, stream| {
+            let stats_tx = stats_tx.clone();
+            async move {
+                let channel: UnisonChannel = UnisonChannel::new(stream);
+                // キューが Request で埋まるまで読まない
+                let filled = timeout(Duration::from_secs(5), async {
+                    while channel.stats().events.len < DEFAULT_QUEUE_DEPTH {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                })
+                .await;
+                assert!(filled.is_ok(), "queue should fill up with requests");
+                let _ = stats_tx.send(channel.stats()).await;
+                while let Ok(msg) = channel.recv().await {
+                    channel
+                        .send_response(msg.id, &msg.method, &msg.payload_as_value()?)
+                        .await?;
+                }
+                Ok(())
+            }
+        })
+        .await;
+    let (handle, addr) = common::spawn_server(Arc::new(server)).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let channel = Arc::new(client.open_channel("burst").await?);
+
+    let total = DEFAULT_QUEUE_DEPTH * 2;
+    let calls = (0..total).map(|i| {
+        let channel = Arc::clone(&channel);
+        async move {
+            channel
+                .request::<_, serde_json::Value>("Echo", &serde_json::json!({ "i": i }))
+                .await
+        }
+    });
+    let responses = timeout(
+        Duration::from_secs(20),
+        futures_util::future::join_all(calls),
+    )
+    .await?;
+    for (i, resp) in responses.into_iter().enumerate() {
+        assert_eq!(resp?["i"], i);
+    }
+
+    let stats = timeout(Duration::from_secs(5), stats_rx.recv())
+        .await?
+        .expect("handler should report");
+    assert!(
+        stats.events.overflows > 0,
+        "the burst should fill the queue"
+    );
+    assert_eq!(stats.events.dropped, 0);
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
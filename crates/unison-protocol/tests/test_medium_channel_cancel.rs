@@ -0,0 +1,128 @@
+//! Medium x Integration: Request cancellation テスト
+//!
+//! `request()` の timeout / future drop が `__cancel` として server に届き、
+//! handler 側の `CancellationToken` が cancel されることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::channel::UnisonChannel;
+use unison::network::{MessageType, NetworkError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// 応答せず token の cancel を待つ "slow" handler。 cancel された request id を報告する。
+async fn register_slow_handler(server: &ProtocolServer, cancelled_tx: mpsc::Sender<u64>) {
+    server
+        .register_channel("slow", move |_ctx, stream| {
+            let cancelled_tx = cancelled_tx.clone();
+            async move {
+                let channel: UnisonChannel = UnisonChannel::new(stream);
+                while let Ok(msg) = channel.recv().await {
+                    if msg.msg_type != MessageType::Request {
+                        continue;
+                    }
+                    let token = channel
+                        .cancellation_token(msg.id)
+                        .await
+                        .expect("in-flight request should have a token");
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            let _ = cancelled_tx.send(msg.id).await;
+                        }
+                        _ = tokio::time::sleep(Duration::from_secs(30)) => {
+                            channel
+                                .send_response(msg.id, &msg.method, &serde_json::json!({}))
+                                .await?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+        })
+        .await;
+}
+
+/// request() の timeout → server の CancellationToken が cancel される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_request_timeout_cancels_handler() -> Result<()> {
+    init_tracing();
+
+    let (cancelled_tx, mut cancelled_rx) = mpsc::channel(4);
+    let server = ProtocolServer::new();
+    register_slow_handler(&server, cancelled_tx).await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client
+        .open_channel("slow")
+        .await?
+        .with_request_timeout(Duration::from_millis(200));
+
+    let result = channel
+        .request::<_, serde_json::Value>("Work", &serde_json::json!({}))
+        .await;
+    assert!(matches!(result, Err(NetworkError::Timeout)), "{result:?}");
+
+    let id = timeout(Duration::from_secs(5), cancelled_rx.recv())
+        .await?
+        .expect("handler should observe the cancel");
+    assert_eq!(id, 1);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// request() の future を drop → server の CancellationToken が cancel される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_request_drop_cancels_handler() -> Result<()> {
+    init_tracing();
+
+    let (cancelled_tx, mut cancelled_rx) = mpsc::channel(4);
+    let server = ProtocolServer::new();
+    register_slow_handler(&server, cancelled_tx).await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client.open_channel("slow").await?;
+
+    // 送信後に future を捨てる (= select! で負けた側と同じ)
+    let abandoned = timeout(
+        Duration::from_millis(200),
+        channel.request::<_, serde_json::Value>("Work", &serde_json::json!({})),
+    )
+    .await;
+    assert!(abandoned.is_err(), "request should still be pending");
+
+    let id = timeout(Duration::from_secs(5), cancelled_rx.recv())
+        .await?
+        .expect("handler should observe the cancel");
+    assert_eq!(id, 1);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
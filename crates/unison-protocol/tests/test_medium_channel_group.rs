@@ -0,0 +1,183 @@
+//! Medium x Integration: ChannelGroup テスト
+//!
+//! `ChannelGroup::publish_event` が全購読者に届くこと、 channel close での自動離脱、
+//! `OverflowPolicy::CloseWithError` による slow consumer の切り離しを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::{ChannelConfig, ChannelGroup, OverflowPolicy};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// group のメンバー数が `n` になるまで待つ
+async fn wait_for_members(group: &ChannelGroup, n: usize) {
+    timeout(Duration::from_secs(5), async {
+        while group.len() != n {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("group never reached {n} members (now {})", group.len()));
+}
+
+/// 3 client に publish が届き、 close した client は group から外れる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_group_publish_and_auto_leave() -> Result<()> {
+    init_tracing();
+
+    let group = ChannelGroup::new("feed");
+    let server = ProtocolServer::new();
+    server.register_channel_group(group.clone()).await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+    let url = url(addr);
+
+    let mut clients = Vec::new();
+    let mut channels = Vec::new();
+    for _ in 0..3 {
+        let client = ProtocolClient::new_default()?;
+        client.connect(&url).await?;
+        channels.push(client.open_channel("feed").await?);
+        clients.push(client);
+    }
+    wait_for_members(&group, 3).await;
+
+    let sent = group
+        .publish_event("Posted", &serde_json::json!({"seq": 1}))
+        .await?;
+    assert_eq!(sent, 3);
+    for channel in &channels {
+        let msg = timeout(Duration::from_secs(5), channel.recv()).await??;
+        assert_eq!(msg.method, "Posted");
+        assert_eq!(msg.payload_as_value()?["seq"], 1);
+    }
+
+    // 購読専用 channel への Request は METHOD_NOT_FOUND
+    assert!(
+        channels[0]
+            .request::<_, serde_json::Value>("Nope", &serde_json::json!({}))
+            .await
+            .is_err()
+    );
+
+    let closing = channels.pop().expect("3 channels");
+    closing.close().await?;
+    wait_for_members(&group, 2).await;
+
+    let sent = group
+        .publish_event("Posted", &serde_json::json!({"seq": 2}))
+        .await?;
+    assert_eq!(sent, 2);
+    for channel in &channels {
+        let msg = timeout(Duration::from_secs(5), channel.recv()).await??;
+        assert_eq!(msg.payload_as_value()?["seq"], 2);
+    }
+
+    for client in &clients {
+        client.disconnect().await?;
+    }
+    wait_for_members(&group, 0).await;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// 読まない client は CloseWithError で切り離され、 他の client には全件届く
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_group_evicts_slow_consumer() -> Result<()> {
+    init_tracing();
+
+    let group = ChannelGroup::new("feed")
+        .with_queue_depth(4)
+        .with_overflow(OverflowPolicy::CloseWithError);
+    let server = ProtocolServer::new();
+    server.register_channel_group(group.clone()).await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+    let url = url(addr);
+
+    // slow: 受信キュー 1 + Block で recv ループごと止め、 flow control を詰まらせる
+    let slow = ProtocolClient::new_default()?;
+    slow.connect(&url).await?;
+    let _slow_channel = slow
+        .open_channel_with_config(
+            "feed",
+            ChannelConfig::default()
+                .with_event_queue_depth(1)
+                .with_overflow(OverflowPolicy::Block),
+        )
+        .await?;
+
+    let fast = ProtocolClient::new_default()?;
+    fast.connect(&url).await?;
+    let fast_channel = Arc::new(fast.open_channel("feed").await?);
+    wait_for_members(&group, 2).await;
+    // join 順に MemberId が振られる (= fast が後)
+    let fast_id = group
+        .members()
+        .iter()
+        .map(|(id, _)| *id)
+        .max()
+        .expect("2 members");
+
+    const TOTAL: usize = 200;
+    let received = Arc::new(AtomicUsize::new(0));
+    let drain = {
+        let fast_channel = Arc::clone(&fast_channel);
+        let received = Arc::clone(&received);
+        tokio::spawn(async move {
+            while received.load(Ordering::SeqCst) < TOTAL {
+                if fast_channel.recv().await.is_err() {
+                    break;
+                }
+                received.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    };
+
+    // 圧縮で縮まないよう疑似乱数の payload (= 1 件 ~64KB)
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let blob: String = (0..64 * 1024)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            char::from(b'a' + (seed % 26) as u8)
+        })
+        .collect();
+    for seq in 0..TOTAL {
+        group
+            .publish_event("Blob", &serde_json::json!({"seq": seq, "blob": blob}))
+            .await?;
+        // fast 側のキューは溢れさせない (= 溢れるのは読まない slow だけ)
+        while group.member_stats(fast_id).is_some_and(|s| s.len >= 2) {
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+    }
+
+    wait_for_members(&group, 1).await;
+    timeout(Duration::from_secs(10), drain).await??;
+    assert_eq!(received.load(Ordering::SeqCst), TOTAL);
+
+    fast.disconnect().await?;
+    slow.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Medium x Integration: channel のアクセスポリシーテスト
+//!
+//! KDL schema の `require-role` / `allow-from` を `ProtocolServer::load_channel_policies()`
+//! で読み込んだサーバーに対し、 principal の role を持つ client だけが channel を
+//! open でき、 role の無い client や許可されないアドレスからの open が `forbidden`
+//! (= `policy` / `detail` 付き) で nack されることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::Level;
+
+use unison::network::{
+    ChannelPolicy, ChannelRouter, Credentials, NetworkError, Principal, TokenAuthenticator,
+};
+use unison::parser::SchemaParser;
+use unison::{ProtocolClient, ProtocolServer};
+
+const SCHEMA: &str = r#"
+    protocol "ops" version="1.0.0" {
+        channel "admin" from="client" lifetime="persistent" require-role="ops,sre" {
+            request "Ping" { returns "Pong" { field "ok" type="bool" } }
+        }
+        channel "internal" from="client" lifetime="persistent" allow-from="10.0.0.0/8" {
+            request "Ping" { returns "Pong" { field "ok" type="bool" } }
+        }
+        channel "loopback" from="client" lifetime="persistent" allow-from="::1,127.0.0.1" {
+            request "Ping" { returns "Pong" { field "ok" type="bool" } }
+        }
+    }
+"#;
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+fn ping_router() -> ChannelRouter {
+    ChannelRouter::new().on_request("Ping", |_ctx, _req: serde_json::Value| async move {
+        Ok(serde_json::json!({ "ok": true }))
+    })
+}
+
+async fn spawn_server(server: ProtocolServer) -> Result<(unison::network::ServerHandle, String)> {
+    for name in ["admin", "internal", "loopback"] {
+        server.register_channel_router(name, ping_router()).await;
+    }
+    common::spawn_server(Arc::new(server)).await
+}
+
+fn assert_forbidden(result: Result<unison::network::UnisonChannel, NetworkError>, policy: &str) {
+    match result {
+        Err(NetworkError::Protocol(msg)) => {
+            assert!(msg.contains("forbidden"), "{msg}");
+            assert!(msg.contains(policy), "{msg}");
+        }
+        Err(e) => panic!("expected forbidden nack, got {e:?}"),
+        Ok(_) => panic!("expected forbidden nack, got Ok"),
+    }
+}
+
+/// `require-role`: principal の role で open でき、 role が無ければ forbidden
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_policy_require_role() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .set_authenticator(
+            TokenAuthenticator::new()
+                .with_token("ops-token", Principal::new("alice").with_roles(["sre"]))
+                .with_token("dev-token", Principal::new("bob").with_roles(["dev"])),
+        )
+        .await;
+    let schema = SchemaParser::new().parse(SCHEMA)?;
+    assert_eq!(server.load_channel_policies(&schema).await?, 3);
+    let (handle, url) = spawn_server(server).await?;
+
+    let ops = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("ops-token"));
+    ops.connect(&url).await?;
+    let channel = ops.open_channel("admin").await?;
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+    channel.close().await?;
+    ops.disconnect().await?;
+
+    let dev = ProtocolClient::new_default()?.with_credentials(Credentials::bearer("dev-token"));
+    dev.connect(&url).await?;
+    assert_forbidden(dev.open_channel("admin").await, "require-role");
+    // 拒否された後も接続は生きていて、 ポリシーを満たす channel は開ける
+    let channel = dev.open_channel("loopback").await?;
+    channel.close().await?;
+    dev.disconnect().await?;
+
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// `allow-from`: 接続元アドレスが許可ネットワークに無ければ forbidden
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_policy_allow_from() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    let schema = SchemaParser::new().parse(SCHEMA)?;
+    server.load_channel_policies(&schema).await?;
+    // コードからの設定は schema の値を上書きできる
+    server
+        .set_channel_policy("admin", ChannelPolicy::new())
+        .await;
+    let (handle, url) = spawn_server(server).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url).await?;
+    assert_forbidden(client.open_channel("internal").await, "allow-from");
+    let channel = client.open_channel("loopback").await?;
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+    channel.close().await?;
+    // 空のポリシーで外した channel は誰でも開ける
+    let channel = client.open_channel("admin").await?;
+    channel.close().await?;
+    client.disconnect().await?;
+
+    handle.shutdown().await?;
+    Ok(())
+}
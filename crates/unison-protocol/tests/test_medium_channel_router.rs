@@ -0,0 +1,212 @@
+//! Medium x Integration: ChannelRouter テスト
+//!
+//! `ChannelRouter` の型付き dispatch / 未登録 method の HandlerNotFound /
+//! decode 失敗 / 同時実行数制限 / Event handler を実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::Level;
+
+use unison::network::channel_config::DEFAULT_QUEUE_DEPTH;
+use unison::network::router::DEFAULT_MAX_CONCURRENCY;
+use unison::network::{ChannelRouter, NetworkError, ProtocolError, ServerHandle};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PingReq {
+    text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PingResp {
+    echo: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Note {
+    body: String,
+}
+
+async fn spawn_server(router: ChannelRouter) -> Result<(ServerHandle, String)> {
+    let server = ProtocolServer::new();
+    server.register_channel_router("svc", router).await;
+    common::spawn_server(Arc::new(server)).await
+}
+
+/// 型付き request / 未登録 method / decode 失敗 / event
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_router_dispatch() -> Result<()> {
+    init_tracing();
+
+    let (note_tx, mut note_rx) = mpsc::channel::<String>(4);
+    let router = ChannelRouter::new()
+        .on_request("Ping", |_ctx, req: PingReq| async move {
+            Ok(PingResp { echo: req.text })
+        })
+        .on_request("Fail", |_ctx, _req: serde_json::Value| async move {
+            Err::<(), _>(ProtocolError::new(409, "conflict"))
+        })
+        .on_event("Note", move |_ctx, note: Note| {
+            let note_tx = note_tx.clone();
+            async move {
+                let _ = note_tx.send(note.body).await;
+            }
+        });
+    let (handle, addr) = spawn_server(router).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let channel = client.open_channel("svc").await?;
+
+    let resp: PingResp = channel
+        .request("Ping", &PingReq { text: "hi".into() })
+        .await?;
+    assert_eq!(resp.echo, "hi");
+
+    match channel
+        .request::<_, serde_json::Value>("Nope", &serde_json::json!({}))
+        .await
+    {
+        Err(NetworkError::HandlerNotFound { method }) => assert_eq!(method, "Nope"),
+        other => panic!("expected HandlerNotFound, got {other:?}"),
+    }
+
+    match channel
+        .request::<_, serde_json::Value>("Ping", &serde_json::json!({"wrong": 1}))
+        .await
+    {
+        Err(NetworkError::Remote(err)) => assert_eq!(err.code, ProtocolError::INVALID_PAYLOAD),
+        other => panic!("expected INVALID_PAYLOAD, got {other:?}"),
+    }
+
+    match channel
+        .request::<_, serde_json::Value>("Fail", &serde_json::json!({}))
+        .await
+    {
+        Err(NetworkError::Remote(err)) => assert_eq!(err, ProtocolError::new(409, "conflict")),
+        other => panic!("expected Remote, got {other:?}"),
+    }
+
+    channel
+        .send_event(
+            "Note",
+            &Note {
+                body: "memo".into(),
+            },
+        )
+        .await?;
+    let body = timeout(Duration::from_secs(5), note_rx.recv()).await?;
+    assert_eq!(body.as_deref(), Some("memo"));
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// 同時実行数 + キュー深さを超えて pipeline された Request も全て応答される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_router_serves_pipelined_burst() -> Result<()> {
+    init_tracing();
+
+    let router =
+        ChannelRouter::new().on_request("Echo", |_ctx, req: serde_json::Value| async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            Ok(req)
+        });
+    let (handle, addr) = spawn_server(router).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let channel = client.open_channel("svc").await?;
+
+    let total = (DEFAULT_MAX_CONCURRENCY + DEFAULT_QUEUE_DEPTH) * 2;
+    let calls = (0..total).map(|i| {
+        let channel = &channel;
+        async move {
+            channel
+                .request::<_, serde_json::Value>("Echo", &serde_json::json!({ "i": i }))
+                .await
+        }
+    });
+    let results = timeout(
+        Duration::from_secs(20),
+        futures_util::future::join_all(calls),
+    )
+    .await?;
+    for (i, result) in results.into_iter().enumerate() {
+        assert_eq!(result?["i"], i);
+    }
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// Request は並行に処理され、 同時実行数は with_max_concurrency で頭打ちになる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_router_concurrency_limit() -> Result<()> {
+    init_tracing();
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let router = {
+        let running = Arc::clone(&running);
+        let peak = Arc::clone(&peak);
+        ChannelRouter::new()
+            .on_request("Slow", move |_ctx, _req: serde_json::Value| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(serde_json::json!({"ok": true}))
+                }
+            })
+            .with_max_concurrency(2)
+    };
+    let (handle, addr) = spawn_server(router).await?;
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&addr).await?;
+    let channel = client.open_channel("svc").await?;
+
+    let req = serde_json::json!({});
+    let calls = (0..6).map(|_| channel.request::<_, serde_json::Value>("Slow", &req));
+    let results = timeout(
+        Duration::from_secs(5),
+        futures_util::future::join_all(calls),
+    )
+    .await?;
+    for result in results {
+        assert_eq!(result?["ok"], true);
+    }
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
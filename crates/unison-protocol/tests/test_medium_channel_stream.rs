@@ -0,0 +1,325 @@
+//! Medium x Integration: Server-streaming request テスト
+//!
+//! `UnisonChannel::request_stream` / `send_stream_item` / `end_stream` と、
+//! client drop 時 / item キュー溢れ時の `__cancel` 伝播を実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::channel::UnisonChannel;
+use unison::network::{ChannelConfig, MessageType, NetworkError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `{"count": n}` を受けて `{"seq": 0..n}` を流し、 end_stream する
+async fn register_counter_handler(server: &ProtocolServer) {
+    server
+        .register_channel("counter", |_ctx, stream| async move {
+            let channel: UnisonChannel = UnisonChannel::new(stream);
+            while let Ok(msg) = channel.recv().await {
+                if msg.msg_type != MessageType::Request {
+                    continue;
+                }
+                let count = msg
+                    .payload_as_value()?
+                    .get("count")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                for seq in 0..count {
+                    channel
+                        .send_stream_item(msg.id, &msg.method, &serde_json::json!({"seq": seq}))
+                        .await?;
+                }
+                channel.end_stream(msg.id).await?;
+            }
+            Ok(())
+        })
+        .await;
+}
+
+/// request_stream で N 件受信 → end_stream で None に到達
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_stream_items_then_end() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    register_counter_handler(&server).await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client.open_channel("counter").await?;
+
+    let mut items = channel
+        .request_stream::<_, serde_json::Value>("Count", &serde_json::json!({"count": 5}))
+        .await?;
+    let mut seqs = Vec::new();
+    while let Some(item) = timeout(Duration::from_secs(5), items.next()).await? {
+        seqs.push(item?["seq"].as_u64().unwrap());
+    }
+    assert_eq!(seqs, vec![0, 1, 2, 3, 4]);
+
+    // 同じ channel で unary request と混在できる (= 空 stream も即終端)
+    let mut empty = channel
+        .request_stream::<_, serde_json::Value>("Count", &serde_json::json!({"count": 0}))
+        .await?;
+    assert!(
+        timeout(Duration::from_secs(5), empty.next())
+            .await?
+            .is_none()
+    );
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// client が stream を途中で drop → server の send_stream_item が Cancelled を返す
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_stream_drop_propagates_cancel() -> Result<()> {
+    init_tracing();
+
+    let (result_tx, mut result_rx) = mpsc::channel::<Result<u64, NetworkError>>(1);
+
+    let server = ProtocolServer::new();
+    server
+        .register_channel("tail", move |_ctx, stream| {
+            let result_tx = result_tx.clone();
+            async move {
+                let channel: UnisonChannel = UnisonChannel::new(stream);
+                let msg = channel.recv().await?;
+                // client が drop するまで無限に流し続ける
+                let mut sent = 0u64;
+                let outcome = loop {
+                    if let Err(e) = channel
+                        .send_stream_item(msg.id, &msg.method, &serde_json::json!({"line": sent}))
+                        .await
+                    {
+                        break Err(e);
+                    }
+                    sent += 1;
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                };
+                let _ = result_tx.send(outcome).await;
+                Ok(())
+            }
+        })
+        .await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client.open_channel("tail").await?;
+
+    let mut lines = channel
+        .request_stream::<_, serde_json::Value>("Tail", &serde_json::json!({}))
+        .await?;
+    let request_id = lines.request_id();
+    for _ in 0..3 {
+        timeout(Duration::from_secs(5), lines.next())
+            .await?
+            .expect("stream should yield")?;
+    }
+    drop(lines);
+
+    let outcome = timeout(Duration::from_secs(5), result_rx.recv())
+        .await?
+        .expect("handler should report");
+    match outcome {
+        Err(NetworkError::Cancelled { request_id: id }) => assert_eq!(id, request_id),
+        other => panic!("expected Cancelled, got {other:?}"),
+    }
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// cancel 後の send_stream_item は何度呼んでも Cancelled、 end_stream は何も送らない
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_stream_stays_cancelled_until_end() -> Result<()> {
+    init_tracing();
+
+    let (result_tx, mut result_rx) = mpsc::channel::<Vec<Result<(), NetworkError>>>(1);
+
+    let server = ProtocolServer::new();
+    server
+        .register_channel("tail", move |_ctx, stream| {
+            let result_tx = result_tx.clone();
+            async move {
+                let channel: UnisonChannel = UnisonChannel::new(stream);
+                let msg = channel.recv().await?;
+                let item = serde_json::json!({"line": 0});
+                channel.send_stream_item(msg.id, &msg.method, &item).await?;
+                let token = channel
+                    .cancellation_token(msg.id)
+                    .await
+                    .expect("request is in flight");
+                token.cancelled().await;
+                let outcomes = vec![
+                    channel.send_stream_item(msg.id, &msg.method, &item).await,
+                    channel.send_stream_item(msg.id, &msg.method, &item).await,
+                    channel.end_stream(msg.id).await,
+                ];
+                let _ = result_tx.send(outcomes).await;
+                Ok(())
+            }
+        })
+        .await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client.open_channel("tail").await?;
+
+    let mut lines = channel
+        .request_stream::<_, serde_json::Value>("Tail", &serde_json::json!({}))
+        .await?;
+    let request_id = lines.request_id();
+    timeout(Duration::from_secs(5), lines.next())
+        .await?
+        .expect("stream should yield")?;
+    drop(lines);
+
+    let outcomes = timeout(Duration::from_secs(5), result_rx.recv())
+        .await?
+        .expect("handler should report");
+    for outcome in &outcomes[..2] {
+        match outcome {
+            Err(NetworkError::Cancelled { request_id: id }) => assert_eq!(*id, request_id),
+            other => panic!("expected Cancelled, got {other:?}"),
+        }
+    }
+    assert!(outcomes[2].is_ok(), "end_stream after cancel is a no-op");
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// 読まれない stream が溢れても同じ channel の request は応答され、
+/// その stream だけ QueueOverflow で終わってサーバーに cancel が届く
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_stream_overflow_does_not_block_channel() -> Result<()> {
+    init_tracing();
+
+    let (result_tx, mut result_rx) = mpsc::channel::<Result<u64, NetworkError>>(1);
+
+    let server = ProtocolServer::new();
+    server
+        .register_channel("flood", move |_ctx, stream| {
+            let result_tx = result_tx.clone();
+            async move {
+                let channel: Arc<UnisonChannel> = Arc::new(UnisonChannel::new(stream));
+                while let Ok(msg) = channel.recv().await {
+                    if msg.method == "Ping" {
+                        channel
+                            .send_response(msg.id, &msg.method, &serde_json::json!({"pong": true}))
+                            .await?;
+                        continue;
+                    }
+                    // cancel されるまで流し続ける
+                    let channel = Arc::clone(&channel);
+                    let result_tx = result_tx.clone();
+                    tokio::spawn(async move {
+                        let mut sent = 0u64;
+                        let outcome = loop {
+                            if let Err(e) = channel
+                                .send_stream_item(
+                                    msg.id,
+                                    &msg.method,
+                                    &serde_json::json!({"n": sent}),
+                                )
+                                .await
+                            {
+                                break Err(e);
+                            }
+                            sent += 1;
+                            tokio::time::sleep(Duration::from_millis(1)).await;
+                        };
+                        let _ = result_tx.send(outcome).await;
+                    });
+                }
+                Ok(())
+            }
+        })
+        .await;
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client
+        .open_channel_with_config("flood", ChannelConfig::default().with_stream_queue_depth(4))
+        .await?;
+
+    let mut items = channel
+        .request_stream::<_, serde_json::Value>("Flood", &serde_json::json!({}))
+        .await?;
+    let request_id = items.request_id();
+
+    // stream を読まないまま unary request が通る
+    let pong: serde_json::Value = timeout(
+        Duration::from_secs(5),
+        channel.request("Ping", &serde_json::json!({})),
+    )
+    .await??;
+    assert_eq!(pong["pong"], true);
+
+    let outcome = timeout(Duration::from_secs(5), result_rx.recv())
+        .await?
+        .expect("handler should report");
+    match outcome {
+        Err(NetworkError::Cancelled { request_id: id }) => assert_eq!(id, request_id),
+        other => panic!("expected Cancelled, got {other:?}"),
+    }
+
+    // 溜まった分を返した後に QueueOverflow で終わる
+    let mut received = 0;
+    let overflow = loop {
+        match timeout(Duration::from_secs(5), items.next()).await? {
+            Some(Ok(_)) => received += 1,
+            Some(Err(e)) => break e,
+            None => panic!("stream ended without an overflow error"),
+        }
+    };
+    assert_eq!(received, 4);
+    match overflow {
+        NetworkError::QueueOverflow { queue, capacity } => {
+            assert_eq!((queue.as_str(), capacity), ("stream", 4));
+        }
+        other => panic!("expected QueueOverflow, got {other:?}"),
+    }
+    assert!(items.next().await.is_none());
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
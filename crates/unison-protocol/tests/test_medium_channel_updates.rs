@@ -0,0 +1,131 @@
+//! Medium x Integration: チャネル更新 push テスト
+//!
+//! 接続中に `ProtocolServer::register_channel` / `set_channel_status` /
+//! `unregister_channel` を呼ぶと、 クライアントの `subscribe_channel_updates()` に
+//! `ChannelUpdate` が届き、 `server_identity()` に反映されることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::identity::{ChannelStatus, ChannelUpdate};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+async fn next_update(updates: &mut broadcast::Receiver<ChannelUpdate>) -> ChannelUpdate {
+    timeout(Duration::from_secs(5), updates.recv())
+        .await
+        .expect("channel update timed out")
+        .expect("channel update receiver closed")
+}
+
+/// 登録 / 状態変更 / 解除が接続中のクライアントへ順に届く
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_updates_pushed_to_client() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::with_identity("updates", "1.0.0", "test"));
+    server
+        .register_channel("events", |_ctx, _stream| async { Ok(()) })
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    let mut updates = client.subscribe_channel_updates();
+    client.connect(&url(addr)).await?;
+    let identity = client.server_identity().await.expect("identity received");
+    assert_eq!(identity.channels.len(), 1);
+
+    server
+        .register_channel("alerts", |_ctx, _stream| async { Ok(()) })
+        .await;
+    match next_update(&mut updates).await {
+        ChannelUpdate::Added(info) => assert_eq!(info.name, "alerts"),
+        other => panic!("Expected Added, got {:?}", other),
+    }
+
+    assert!(
+        server
+            .set_channel_status("events", ChannelStatus::Busy)
+            .await
+    );
+    match next_update(&mut updates).await {
+        ChannelUpdate::StatusChanged { name, status } => {
+            assert_eq!(name, "events");
+            assert_eq!(status, ChannelStatus::Busy);
+        }
+        other => panic!("Expected StatusChanged, got {:?}", other),
+    }
+
+    assert!(server.unregister_channel("alerts").await);
+    match next_update(&mut updates).await {
+        ChannelUpdate::Removed(name) => assert_eq!(name, "alerts"),
+        other => panic!("Expected Removed, got {:?}", other),
+    }
+
+    // 受信時点で Identity に反映済み
+    let identity = client.server_identity().await.expect("identity received");
+    assert_eq!(identity.channels.len(), 1);
+    assert_eq!(identity.channels[0].name, "events");
+    assert_eq!(identity.channels[0].status, ChannelStatus::Busy);
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// 状態が変わらない `set_channel_status` は push しない
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_unchanged_status_is_not_pushed() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel("events", |_ctx, _stream| async { Ok(()) })
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    let mut updates = client.subscribe_channel_updates();
+    client.connect(&url(addr)).await?;
+
+    assert!(
+        server
+            .set_channel_status("events", ChannelStatus::Available)
+            .await
+    );
+    assert!(
+        server
+            .set_channel_status("events", ChannelStatus::Unavailable)
+            .await
+    );
+    match next_update(&mut updates).await {
+        ChannelUpdate::StatusChanged { status, .. } => {
+            assert_eq!(status, ChannelStatus::Unavailable)
+        }
+        other => panic!("Expected StatusChanged, got {:?}", other),
+    }
+    assert!(updates.try_recv().is_err());
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! Medium x Integration: ClientPool テスト
+//!
+//! 2 台のサーバーに張った `ClientPool` が `open_channel` を両方へ振り分けること、
+//! 片方を止めると失敗した接続を外して残りのサーバーで channel を開き (= failover)、
+//! 止めたサーバーを eject することを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use unison::network::{
+    ChannelRouter, ClientPool, LoadBalance, PoolConfig, ServerHandle, UnisonChannel,
+};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `WhoAmI` に自分の名前を返すサーバー
+async fn spawn_server(name: &'static str) -> Result<(ServerHandle, String)> {
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router(
+            "whoami",
+            ChannelRouter::new().on_request(
+                "WhoAmI",
+                move |_ctx, _req: serde_json::Value| async move {
+                    Ok(serde_json::json!({ "name": name }))
+                },
+            ),
+        )
+        .await;
+    common::spawn_server(server).await
+}
+
+async fn whoami(channel: &UnisonChannel) -> Result<String> {
+    let resp: serde_json::Value = channel.request("WhoAmI", &serde_json::json!({})).await?;
+    Ok(resp["name"].as_str().unwrap_or_default().to_string())
+}
+
+fn pool(config: PoolConfig) -> ClientPool {
+    ClientPool::new(ProtocolClient::new_default).with_config(config)
+}
+
+/// round-robin で 2 台に均等に振り分け、 LeastLoaded は開いている channel の少ない方へ
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_client_pool_spreads_channels() -> Result<()> {
+    init_tracing();
+
+    let (handle_a, url_a) = spawn_server("a").await?;
+    let (handle_b, url_b) = spawn_server("b").await?;
+
+    let pool = pool(PoolConfig::default().with_connections(2));
+    pool.connect([&url_a, &url_b]).await?;
+    assert_eq!(pool.connections(), 2);
+    assert!(pool.endpoints().iter().all(|e| e.connections == 1));
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for _ in 0..4 {
+        let channel = pool.open_channel("whoami").await?;
+        *counts.entry(whoami(&channel).await?).or_default() += 1;
+    }
+    assert_eq!(counts.get("a"), Some(&2), "{counts:?}");
+    assert_eq!(counts.get("b"), Some(&2), "{counts:?}");
+
+    let least = self::pool(
+        PoolConfig::default()
+            .with_connections(2)
+            .with_load_balance(LoadBalance::LeastLoaded),
+    );
+    least.connect([&url_a, &url_b]).await?;
+    let first = least.open_channel("whoami").await?;
+    let second = least.open_channel("whoami").await?;
+    let first_name = whoami(&first).await?;
+    assert_ne!(first_name, whoami(&second).await?);
+    // 閉じた channel の接続が空くので、 次はそちらへ
+    first.close().await?;
+    let third = least.open_channel("whoami").await?;
+    assert_eq!(whoami(&third).await?, first_name);
+
+    pool.close().await;
+    least.close().await;
+    handle_a.shutdown().await?;
+    handle_b.shutdown().await?;
+    Ok(())
+}
+
+/// 片方のサーバーが落ちても open_channel は残りで成功し、 落ちた方は eject される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_client_pool_fails_over_and_ejects() -> Result<()> {
+    init_tracing();
+
+    let (handle_a, url_a) = spawn_server("a").await?;
+    let (handle_b, url_b) = spawn_server("b").await?;
+
+    let pool = pool(
+        PoolConfig::default()
+            .with_connections(2)
+            .with_max_failures(1)
+            .with_ejection_time(Duration::from_secs(60))
+            .with_health_check_interval(Duration::from_millis(100))
+            .with_connect_timeout(Duration::from_millis(500)),
+    );
+    pool.connect([&url_a, &url_b]).await?;
+    handle_a.shutdown().await?;
+
+    // health check を待たずに開いても、 切れた接続を飛ばして b に繋がる
+    for _ in 0..4 {
+        let channel = pool.open_channel("whoami").await?;
+        assert_eq!(whoami(&channel).await?, "b");
+    }
+
+    timeout(Duration::from_secs(5), async {
+        loop {
+            let endpoints = pool.endpoints();
+            let a = endpoints
+                .iter()
+                .find(|e| e.url == url_a)
+                .expect("endpoint a");
+            if a.ejected && pool.connections() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await?;
+    // eject 中は a へ張り直さず、 2 本とも b に張る
+    let endpoints = pool.endpoints();
+    let b = endpoints
+        .iter()
+        .find(|e| e.url == url_b)
+        .expect("endpoint b");
+    assert_eq!(b.connections, 2, "{endpoints:?}");
+    assert!(!b.ejected);
+
+    pool.close().await;
+    assert_eq!(pool.connections(), 0);
+    handle_b.shutdown().await?;
+    Ok(())
+}
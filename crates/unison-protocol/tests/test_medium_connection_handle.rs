@@ -0,0 +1,157 @@
+//! Medium x Integration: ConnectionHandle テスト
+//!
+//! `ProtocolServer::connection(id)` / `connections()` / `broadcast_filtered` と
+//! `ConnectionHandle::send_datagram` / `close` が接続単位で動くことを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。 datagram は unreliable
+//! なので受信側は複数 attempt する。
+
+mod common;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::{connected_id, url};
+use unison::codec::JsonCodec;
+use unison::network::{ClientConnectionEvent, ConnectionHandle, ConnectionId, DatagramChannel};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Notice {
+    text: String,
+}
+
+/// `send` を繰り返しつつ datagram channel の受信を待つ (= drop tolerance)
+async fn recv_with_retry<F, Fut>(chan: &DatagramChannel, mut send: F) -> Option<Notice>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    for _ in 0..10 {
+        send().await;
+        if let Ok(Ok(notice)) =
+            timeout(Duration::from_millis(100), chan.recv_event::<Notice>()).await
+        {
+            return Some(notice);
+        }
+    }
+    None
+}
+
+/// 2 client のうち片方だけに datagram を届け、 handle から peer info / close を扱う
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_connection_handle_targets_one_client() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    // server 側 handler は broadcast の channel_id 解決用 (= 受信は使わない)
+    server
+        .register_channel_datagram("notice", 7, |chan| async move {
+            while chan.recv_event::<Notice>().await.is_ok() {}
+        })
+        .await;
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+    let url = url(addr);
+
+    let client_a = ProtocolClient::new_default()?;
+    client_a.connect(&url).await?;
+    let id_a = connected_id(&mut events).await;
+    let client_b = ProtocolClient::new_default()?;
+    client_b.connect(&url).await?;
+    let id_b = connected_id(&mut events).await;
+    assert_ne!(id_a, id_b);
+
+    let mut ids: Vec<ConnectionId> = server
+        .connections()
+        .await
+        .iter()
+        .map(ConnectionHandle::id)
+        .collect();
+    ids.sort();
+    let mut expected = vec![id_a, id_b];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let conn_a = server.connection(id_a).await.expect("client A handle");
+    let info = conn_a.peer_info();
+    assert_eq!(info.connection_id, id_a);
+    assert_eq!(info.remote_addr, conn_a.remote_address());
+    assert_eq!(conn_a.context().connection_id, id_a);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let chan_a = client_a.open_datagram_channel("notice", 7).await?;
+    let chan_b = client_b.open_datagram_channel("notice", 7).await?;
+
+    // broadcast_filtered: B だけに届く
+    let only_b = Notice {
+        text: "only-b".into(),
+    };
+    let received = recv_with_retry(&chan_b, || async {
+        let sent = server
+            .broadcast_filtered::<_, JsonCodec, _>("notice", &only_b, |c| c.id() == id_b)
+            .await
+            .expect("broadcast_filtered");
+        assert_eq!(sent, 1);
+    })
+    .await;
+    assert_eq!(received.as_ref(), Some(&only_b));
+    assert!(
+        timeout(Duration::from_millis(200), chan_a.recv_event::<Notice>())
+            .await
+            .is_err(),
+        "client A must not receive B's datagram"
+    );
+
+    // ConnectionHandle::send_datagram: A だけに届く
+    let to_a = Notice {
+        text: "to-a".into(),
+    };
+    let received = recv_with_retry(&chan_a, || async {
+        conn_a
+            .send_datagram::<_, JsonCodec>("notice", &to_a)
+            .await
+            .expect("send_datagram");
+    })
+    .await;
+    assert_eq!(received.as_ref(), Some(&to_a));
+
+    // close(reason): client 側に reason が届き、 server の map からも消える
+    let mut client_events = client_a.subscribe_connection_events();
+    conn_a.close("bye");
+    let reason = loop {
+        let event = timeout(Duration::from_secs(5), client_events.recv())
+            .await
+            .expect("Disconnected event timed out")
+            .expect("event channel closed");
+        if let ClientConnectionEvent::Disconnected { reason } = event {
+            break reason;
+        }
+    };
+    assert!(reason.contains("bye"), "{reason}");
+
+    timeout(Duration::from_secs(5), async {
+        while server.connection(id_a).await.is_some() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await?;
+    assert!(server.connection(id_b).await.is_some());
+
+    client_b.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,216 @@
+//! Medium x Integration: graceful shutdown テスト
+//!
+//! `ServerHandle::shutdown_graceful(deadline)` が drain 通知 (`__goaway`)・新規 channel
+//! の拒否・実行中 handler の完了待ち・deadline 超過時の force close を行うことを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::quic::QuicServer;
+use unison::network::{ChannelRouter, ClientConnectionEvent, ConnectionEvent, NetworkError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `Sleep { ms }` で指定時間眠ってから応答する router
+fn sleepy_router() -> ChannelRouter {
+    ChannelRouter::new().on_request("Sleep", |_ctx, req: serde_json::Value| async move {
+        let ms = req["ms"].as_u64().unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        Ok(serde_json::json!({"slept": ms}))
+    })
+}
+
+/// drain 中: in-flight request は完了し、 client には Draining、 新規 open は拒否される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_graceful_shutdown_drains_inflight() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router("work", sleepy_router())
+        .await;
+    let mut server_events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    let mut client_events = client.subscribe_connection_events();
+    client.connect(&url(addr)).await?;
+    let channel = Arc::new(client.open_channel("work").await?);
+
+    // 300ms かかる request を飛ばしてから drain を開始
+    let inflight = {
+        let channel = Arc::clone(&channel);
+        tokio::spawn(async move {
+            channel
+                .request::<_, serde_json::Value>("Sleep", &serde_json::json!({"ms": 300}))
+                .await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(server.active_handler_count(), 1);
+
+    let shutdown = tokio::spawn(handle.shutdown_graceful(Duration::from_secs(5)));
+
+    // client は Draining を受け取る
+    let deadline = loop {
+        let event = timeout(Duration::from_secs(5), client_events.recv()).await??;
+        if let ClientConnectionEvent::Draining { deadline } = event {
+            break deadline;
+        }
+    };
+    assert_eq!(deadline, Duration::from_secs(5));
+    assert!(server.is_draining());
+
+    // drain 中の新規 channel は server-draining で拒否
+    match client.open_channel("work").await {
+        Err(NetworkError::Protocol(msg)) => assert!(msg.contains("server-draining"), "{msg}"),
+        Err(e) => panic!("expected server-draining nack, got {e:?}"),
+        Ok(_) => panic!("expected server-draining nack, got Ok"),
+    }
+
+    // in-flight request は完了する
+    let resp = timeout(Duration::from_secs(5), inflight).await???;
+    assert_eq!(resp["slept"], 300);
+
+    // handler が終わった channel を client が閉じると drain 完了
+    channel.close().await?;
+    timeout(Duration::from_secs(5), shutdown).await???;
+
+    // server 側には接続ごとの Disconnected が出ている
+    let mut disconnected = false;
+    while let Ok(Ok(event)) = timeout(Duration::from_millis(100), server_events.recv()).await {
+        disconnected |= matches!(event, ConnectionEvent::Disconnected { .. });
+    }
+    assert!(disconnected, "Disconnected event expected");
+    assert!(!server.is_draining());
+    Ok(())
+}
+
+/// deadline を過ぎても終わらない handler があれば force close する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_graceful_shutdown_force_closes_after_deadline() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router("work", sleepy_router())
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    let mut client_events = client.subscribe_connection_events();
+    client.connect(&url(addr)).await?;
+    // channel を開いたままにする (= handler は deadline まで終わらない)
+    let _channel = client.open_channel("work").await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let started = Instant::now();
+    timeout(
+        Duration::from_secs(5),
+        handle.shutdown_graceful(Duration::from_millis(300)),
+    )
+    .await??;
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(300), "{elapsed:?}");
+    assert!(server.connections().await.is_empty());
+
+    let reason = loop {
+        let event = timeout(Duration::from_secs(5), client_events.recv()).await??;
+        if let ClientConnectionEvent::Disconnected { reason } = event {
+            break reason;
+        }
+    };
+    assert!(reason.contains("drain deadline exceeded"), "{reason}");
+    Ok(())
+}
+
+/// drain 中の新規接続は拒否される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_graceful_shutdown_refuses_new_connections() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router("work", sleepy_router())
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+    let url = url(addr);
+
+    let first = ProtocolClient::new_default()?;
+    first.connect(&url).await?;
+    let _channel = first.open_channel("work").await?;
+
+    let shutdown = tokio::spawn(handle.shutdown_graceful(Duration::from_millis(500)));
+    timeout(Duration::from_secs(5), async {
+        while !server.is_draining() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await?;
+
+    let late = ProtocolClient::new_default()?;
+    assert!(late.connect(&url).await.is_err());
+
+    timeout(Duration::from_secs(5), shutdown).await???;
+    Ok(())
+}
+
+/// drain はサーバー全体に効く — 同じサーバーの `start()` ingress も新規接続を拒否する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_graceful_shutdown_refuses_on_shared_ingress() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router("work", sleepy_router())
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+
+    // `listen()` と同じ経路 (= QuicServer::start) の 2 本目の ingress
+    let mut ingress = QuicServer::new(Arc::clone(&server));
+    ingress.bind("[::1]:0").await?;
+    let addr = ingress.local_addr().expect("bound");
+    let url = url(addr);
+    let ingress_task = tokio::spawn(async move { ingress.start().await });
+
+    let first = ProtocolClient::new_default()?;
+    first.connect(&url).await?;
+    let _channel = first.open_channel("work").await?;
+
+    let shutdown = tokio::spawn(handle.shutdown_graceful(Duration::from_millis(500)));
+    timeout(Duration::from_secs(5), async {
+        while !server.is_draining() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await?;
+
+    let late = ProtocolClient::new_default()?;
+    assert!(late.connect(&url).await.is_err());
+
+    timeout(Duration::from_secs(5), shutdown).await???;
+    ingress_task.abort();
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Medium x Integration: handler panic の分離テスト
+//!
+//! channel handler の panic が `__channel_error` + stream reset として client に届き、
+//! `ConnectionEvent::HandlerFailed` と `handler_panic_count()` に記録されること、
+//! router の request handler の panic がその request だけの INTERNAL エラーになることを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::channel::UnisonChannel;
+use unison::network::{ChannelRouter, ConnectionEvent, MessageType, NetworkError, ProtocolError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// 生 handler の panic: client の request は INTERNAL で失敗し、 接続は生き続ける
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_channel_handler_panic_is_reported() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel("boom", |_ctx, stream| async move {
+            let channel: UnisonChannel = UnisonChannel::new(stream);
+            let msg = channel.recv().await?;
+            assert_eq!(msg.msg_type, MessageType::Request);
+            panic!("boom handler exploded");
+        })
+        .await;
+    server
+        .register_channel_router(
+            "echo",
+            ChannelRouter::new().on_request("Echo", |_ctx, req: serde_json::Value| async move {
+                Ok(req)
+            }),
+        )
+        .await;
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+
+    let channel = client.open_channel("boom").await?;
+    let result = timeout(
+        Duration::from_secs(5),
+        channel.request::<_, serde_json::Value>("Explode", &serde_json::json!({})),
+    )
+    .await?;
+    match result {
+        Err(NetworkError::Remote(err)) => {
+            assert_eq!(err.code, ProtocolError::INTERNAL);
+            let details = err.details.expect("details");
+            assert_eq!(details["channel"], "boom");
+            assert_eq!(details["reason"], "handler-panicked");
+        }
+        other => panic!("expected INTERNAL remote error, got {other:?}"),
+    }
+    // reset 後の channel は閉じる
+    timeout(Duration::from_secs(5), channel.closed()).await?;
+
+    let (channel_name, error) = loop {
+        let event = timeout(Duration::from_secs(5), events.recv()).await??;
+        if let ConnectionEvent::HandlerFailed {
+            channel,
+            error,
+            panicked,
+            ..
+        } = event
+        {
+            assert!(panicked);
+            break (channel, error);
+        }
+    };
+    assert_eq!(channel_name, "boom");
+    assert!(error.contains("boom handler exploded"), "{error}");
+    assert_eq!(server.handler_panic_count(), 1);
+    assert_eq!(server.handler_failure_count(), 0);
+    assert_eq!(server.active_handler_count(), 0);
+
+    // 同じ接続の別 channel は影響を受けない
+    let echo = client.open_channel("echo").await?;
+    let resp: serde_json::Value = echo
+        .request("Echo", &serde_json::json!({"still": "alive"}))
+        .await?;
+    assert_eq!(resp["still"], "alive");
+
+    echo.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// router の request handler の panic はその request だけが INTERNAL になる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_router_request_panic_keeps_channel() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router(
+            "work",
+            ChannelRouter::new().on_request("Run", |_ctx, req: serde_json::Value| async move {
+                if req["explode"].as_bool().unwrap_or(false) {
+                    panic!("run exploded");
+                }
+                Ok(serde_json::json!({"ok": true}))
+            }),
+        )
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    let channel = client.open_channel("work").await?;
+
+    match timeout(
+        Duration::from_secs(5),
+        channel.request::<_, serde_json::Value>("Run", &serde_json::json!({"explode": true})),
+    )
+    .await?
+    {
+        Err(NetworkError::Remote(err)) => assert_eq!(err.code, ProtocolError::INTERNAL),
+        other => panic!("expected INTERNAL remote error, got {other:?}"),
+    }
+
+    let resp: serde_json::Value = channel
+        .request("Run", &serde_json::json!({"explode": false}))
+        .await?;
+    assert_eq!(resp["ok"], true);
+    // channel handler 自体は panic していない
+    assert_eq!(server.handler_panic_count(), 0);
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
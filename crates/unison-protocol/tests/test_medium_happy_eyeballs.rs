@@ -0,0 +1,134 @@
+//! Medium x Integration: happy-eyeballs connect テスト
+//!
+//! 応答しない address (= 受けるだけで何も返さない UDP socket) が先頭の候補でも、
+//! `connection_attempt_delay` の後に並行に始めた次の候補で接続できること、 全ての候補が
+//! 応答しなければ `connect_timeout` で `NetworkError::Timeout` になること、
+//! `Connected` event が確立した address を返すことを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+use anyhow::Result;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::Level;
+
+use unison::network::cert::CertSource;
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::network::{ClientConnectionEvent, NetworkError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// packet を受けるだけで応答しない address (= 壊れた IPv6 経路の代わり)
+fn blackhole() -> Result<(UdpSocket, SocketAddr)> {
+    let socket = UdpSocket::bind("[::1]:0")?;
+    let addr = socket.local_addr()?;
+    Ok((socket, addr))
+}
+
+/// 先頭の候補が応答しなくても、 次の候補で接続する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_happy_eyeballs_falls_back_to_next_family() -> Result<()> {
+    init_tracing();
+
+    let server_config = QuicServer::configure_server_with(CertSource::dev_localhost()).await?;
+    let endpoint = quinn::Endpoint::server(server_config, "127.0.0.1:0".parse()?)?;
+    let live = endpoint.local_addr()?;
+    tokio::spawn(async move {
+        while let Some(incoming) = endpoint.accept().await {
+            tokio::spawn(async move {
+                if let Ok(connection) = incoming.await {
+                    connection.closed().await;
+                }
+            });
+        }
+    });
+    let (_socket, dead) = blackhole()?;
+
+    let client = QuicClient::builder()
+        .trust_anchors(TrustAnchors::SkipVerification)
+        .connection_attempt_delay(Duration::from_millis(100))
+        .build()?;
+    let started = Instant::now();
+    client.connect_addrs(&[dead, live]).await?;
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "{:?}",
+        started.elapsed()
+    );
+
+    let remote = client
+        .connection()
+        .read()
+        .await
+        .as_ref()
+        .map(|c| c.remote_address());
+    assert_eq!(remote, Some(live));
+    client.disconnect().await?;
+    Ok(())
+}
+
+/// 全ての候補が応答しなければ connect_timeout で Timeout
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_happy_eyeballs_connect_timeout() -> Result<()> {
+    init_tracing();
+
+    let (_socket, dead) = blackhole()?;
+    let client = ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::SkipVerification)
+            .connect_timeout(Duration::from_millis(300))
+            .build()?,
+    );
+
+    let started = Instant::now();
+    let result = client.connect(&dead.to_string()).await;
+    assert!(
+        matches!(result, Err(NetworkError::Timeout)),
+        "expected Timeout, got {result:?}"
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "{:?}",
+        started.elapsed()
+    );
+    assert!(!client.is_connected().await);
+    Ok(())
+}
+
+/// `Connected` は確立した address を返す
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_happy_eyeballs_reports_chosen_address() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    let handle = Arc::clone(&server)
+        .spawn_listen_shared("127.0.0.1:0")
+        .await?;
+    let port = handle.local_addr().port();
+
+    let client = ProtocolClient::new_default()?;
+    let mut events = client.subscribe_connection_events();
+    client.connect(&format!("localhost:{port}")).await?;
+
+    match events.recv_skip_lagged().await? {
+        ClientConnectionEvent::Connected { remote_addr } => {
+            assert_eq!(remote_addr, SocketAddr::from(([127, 0, 0, 1], port)));
+        }
+        other => panic!("expected Connected, got {other:?}"),
+    }
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
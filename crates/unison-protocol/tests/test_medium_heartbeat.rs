@@ -0,0 +1,156 @@
+//! Medium x Integration: application-level heartbeat テスト
+//!
+//! heartbeat を設定した client / server が `__heartbeat` で RTT を測れること、 pong を
+//! 返さない peer (= stream を accept しない素の quinn endpoint) を `max_missed` 回の
+//! miss で dead とみなし、 `Disconnected { reason: "heartbeat timeout" }` を出して接続を
+//! 閉じることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::cert::CertSource;
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::network::{ClientConnectionEvent, ConnectionEvent, HeartbeatConfig};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+fn fast_heartbeat() -> HeartbeatConfig {
+    HeartbeatConfig::default()
+        .with_interval(Duration::from_millis(100))
+        .with_max_missed(3)
+}
+
+/// 両側で heartbeat を有効にすると、 それぞれが RTT を測る
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_heartbeat_measures_rtt() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server.set_heartbeat(fast_heartbeat()).await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let url = url(handle.local_addr());
+
+    let client = ProtocolClient::new_default()?.with_heartbeat(fast_heartbeat());
+    assert_eq!(client.rtt(), None);
+    client.connect(&url).await?;
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let rtt = client.rtt().expect("client must have measured RTT");
+    assert!(rtt < Duration::from_millis(100), "{rtt:?}");
+    let stats = client.heartbeat_stats();
+    assert!(stats.acked >= 2, "{stats:?}");
+    assert_eq!(stats.missed, 0);
+    assert!(stats.smoothed_rtt.is_some());
+
+    let connections = server.connections().await;
+    assert_eq!(connections.len(), 1);
+    let stats = connections[0].heartbeat_stats();
+    assert!(stats.acked >= 2, "{stats:?}");
+    assert_eq!(stats.missed, 0);
+    assert!(connections[0].rtt().is_some());
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// pong を返さない client をサーバーが dead とみなして閉じる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_heartbeat_server_detects_dead_peer() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server.set_heartbeat(fast_heartbeat()).await;
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+
+    // 素の quinn client (= 接続は保つが stream を accept しないので pong を返さない)
+    let client_config = QuicClient::configure_client_with(TrustAnchors::SkipVerification).await?;
+    let mut endpoint = quinn::Endpoint::client("[::]:0".parse()?)?;
+    endpoint.set_default_client_config(client_config);
+    let connection = endpoint.connect(handle.local_addr(), "localhost")?.await?;
+
+    let reason = timeout(Duration::from_secs(5), async {
+        loop {
+            if let ConnectionEvent::Disconnected { reason, .. } = events.recv_skip_lagged().await? {
+                return anyhow::Ok(reason);
+            }
+        }
+    })
+    .await??;
+    assert_eq!(reason, "heartbeat timeout");
+
+    let closed = timeout(Duration::from_secs(2), connection.closed()).await?;
+    assert!(
+        matches!(closed, quinn::ConnectionError::ApplicationClosed(ref close)
+            if close.reason.as_ref() == b"heartbeat timeout"),
+        "{closed:?}"
+    );
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// pong を返さないサーバーを client が dead とみなして閉じる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_heartbeat_client_detects_dead_server() -> Result<()> {
+    init_tracing();
+
+    // 素の quinn server (= 接続は受けるが stream を accept しない)
+    let server_config = QuicServer::configure_server_with(CertSource::dev_localhost()).await?;
+    let endpoint = quinn::Endpoint::server(server_config, "[::1]:0".parse()?)?;
+    let addr = endpoint.local_addr()?;
+    let accepted = tokio::spawn(async move {
+        let incoming = endpoint.accept().await.expect("incoming connection");
+        let connection = incoming.await.expect("handshake");
+        connection.closed().await
+    });
+
+    let client = Arc::new(ProtocolClient::new_default()?.with_heartbeat(fast_heartbeat()));
+    let mut events = client.subscribe_connection_events();
+    // Identity を待つ間に heartbeat が先に判定する (= connect の結果は問わない)
+    let connecting = {
+        let client = Arc::clone(&client);
+        tokio::spawn(async move { client.connect(&url(addr)).await })
+    };
+
+    let reason = timeout(Duration::from_secs(5), async {
+        loop {
+            if let ClientConnectionEvent::Disconnected { reason } =
+                events.recv_skip_lagged().await?
+            {
+                return anyhow::Ok(reason);
+            }
+        }
+    })
+    .await??;
+    assert_eq!(reason, "heartbeat timeout");
+    assert!(!client.is_connected().await);
+    assert!(client.heartbeat_stats().total_missed >= 3);
+
+    let closed = timeout(Duration::from_secs(2), accepted).await??;
+    assert!(
+        matches!(closed, quinn::ConnectionError::ApplicationClosed(ref close)
+            if close.reason.as_ref() == b"heartbeat timeout"),
+        "{closed:?}"
+    );
+    let _ = connecting.await;
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Medium x Integration: Interceptor テスト
+//!
+//! `ProtocolServer::add_interceptor()` で登録した interceptor が channel open を nack でき、
+//! 受信 Request を handler の手前で拒否でき、 `TimingInterceptor` が Request ごとの
+//! 処理時間を計れること、 サーバー発信の channel でも on_close が呼ばれることを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::{connected_id, url};
+use unison::network::{
+    ChannelRouter, InterceptContext, Interceptor, NetworkError, ProtocolError, ProtocolMessage,
+    RequestTiming, TimingInterceptor, TracingInterceptor,
+};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `admin` channel の open と `Blocked` Request を拒否し、 close を数える interceptor
+#[derive(Default)]
+struct Guard {
+    closed: AtomicUsize,
+}
+
+impl Interceptor for Guard {
+    fn on_open<'a>(
+        &'a self,
+        ctx: &'a InterceptContext,
+    ) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+        Box::pin(async move {
+            if ctx.channel == "admin" {
+                Err("forbidden".to_string())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    fn on_inbound(
+        &self,
+        _ctx: &InterceptContext,
+        msg: &ProtocolMessage,
+    ) -> Result<(), ProtocolError> {
+        if msg.method == "Blocked" {
+            Err(ProtocolError::new(429, "rate limited"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn on_close(&self, _ctx: &InterceptContext) {
+        self.closed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn echo_router(calls: Arc<AtomicUsize>) -> ChannelRouter {
+    let blocked_calls = Arc::clone(&calls);
+    ChannelRouter::new()
+        .on_request("Echo", move |_ctx, req: serde_json::Value| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(req)
+            }
+        })
+        .on_request("Blocked", move |_ctx, req: serde_json::Value| {
+            let calls = Arc::clone(&blocked_calls);
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(req)
+            }
+        })
+}
+
+/// open の nack・Request の拒否・timing の計測
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_interceptor_rejects_and_times() -> Result<()> {
+    init_tracing();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let timings: Arc<Mutex<Vec<RequestTiming>>> = Arc::default();
+    let guard = Arc::new(Guard::default());
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router("echo", echo_router(Arc::clone(&calls)))
+        .await;
+    server
+        .register_channel_router("admin", echo_router(Arc::clone(&calls)))
+        .await;
+    server.add_interceptor(TracingInterceptor::new()).await;
+    server.add_interceptor(Arc::clone(&guard)).await;
+    let sink = Arc::clone(&timings);
+    server
+        .add_interceptor(
+            TimingInterceptor::new().on_complete(move |t| sink.lock().unwrap().push(t.clone())),
+        )
+        .await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+
+    // on_open の Err は nack の reason になる
+    match client.open_channel("admin").await {
+        Err(NetworkError::Protocol(msg)) => assert!(msg.contains("forbidden"), "{msg}"),
+        Err(e) => panic!("expected forbidden nack, got {e:?}"),
+        Ok(_) => panic!("expected forbidden nack, got Ok"),
+    }
+
+    let channel = client.open_channel("echo").await?;
+
+    // on_inbound の Err は handler に届かず Error として返る
+    match channel
+        .request::<_, serde_json::Value>("Blocked", &serde_json::json!({}))
+        .await
+    {
+        Err(NetworkError::Remote(err)) => {
+            assert_eq!(err.code, 429);
+            assert_eq!(err.message, "rate limited");
+        }
+        other => panic!("expected rate limited error, got {other:?}"),
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let resp: serde_json::Value = channel
+        .request("Echo", &serde_json::json!({"n": 1}))
+        .await?;
+    assert_eq!(resp["n"], 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // 拒否された Request は Guard より後の TimingInterceptor に届かない
+    {
+        let timings = timings.lock().unwrap();
+        assert_eq!(timings.len(), 1, "{timings:?}");
+        assert_eq!(timings[0].channel, "echo");
+        assert_eq!(timings[0].method, "Echo");
+        assert!(!timings[0].is_error);
+    }
+
+    // handler の終了で on_close (= nack した admin は数えない)
+    channel.close().await?;
+    timeout(Duration::from_secs(5), async {
+        while guard.closed.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    assert_eq!(guard.closed.load(Ordering::SeqCst), 1);
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// サーバー発信の channel も閉じたら on_close が呼ばれる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_interceptor_closes_server_initiated_channel() -> Result<()> {
+    init_tracing();
+
+    let guard = Arc::new(Guard::default());
+    let server = Arc::new(ProtocolServer::new());
+    let mut events = server.subscribe_connection_events();
+    server.add_interceptor(Arc::clone(&guard)).await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client
+        .register_channel_router("jobs", echo_router(Arc::new(AtomicUsize::new(0))))
+        .await;
+    client.connect(&url(addr)).await?;
+
+    let client_id = connected_id(&mut events).await;
+    let channel = server.open_channel(client_id, "jobs").await?;
+    let resp: serde_json::Value = channel
+        .request("Echo", &serde_json::json!({"n": 1}))
+        .await?;
+    assert_eq!(resp["n"], 1);
+    assert_eq!(guard.closed.load(Ordering::SeqCst), 0);
+
+    channel.close().await?;
+    timeout(Duration::from_secs(5), async {
+        while guard.closed.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    assert_eq!(guard.closed.load(Ordering::SeqCst), 1);
+
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Medium x Integration: client の path migration テスト
+//!
+//! 接続中の client が別の UDP socket へ移っても (= NAT rebinding 相当)、 サーバーが同じ
+//! `connection_id` の接続として扱い続け、 `ConnectionEvent::PathChanged` を出して
+//! `ConnectionHandle::remote_address()` / `ConnectionContext::remote_addr()` を新しい
+//! アドレスに更新することを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+use anyhow::Result;
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use unison::ProtocolServer;
+use unison::network::ConnectionEvent;
+use unison::network::quic::QuicClient;
+use unison::network::trust::TrustAnchors;
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// rebind した client の接続は同じ ID のまま新しいアドレスに移る
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_path_migration_updates_remote_address() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+
+    // 素の quinn client (= socket を差し替えられる)
+    let client_config = QuicClient::configure_client_with(TrustAnchors::SkipVerification).await?;
+    let mut endpoint = quinn::Endpoint::client("[::1]:0".parse()?)?;
+    endpoint.set_default_client_config(client_config);
+    let old = endpoint.local_addr()?;
+    let connection = endpoint.connect(handle.local_addr(), "localhost")?.await?;
+
+    let connection_id = timeout(Duration::from_secs(2), async {
+        loop {
+            if let ConnectionEvent::Connected {
+                remote_addr,
+                context,
+            } = events.recv_skip_lagged().await?
+            {
+                assert_eq!(remote_addr, old);
+                return anyhow::Ok(context.connection_id);
+            }
+        }
+    })
+    .await??;
+
+    // 別の port へ移り、 新しい path で packet を送る
+    endpoint.rebind(UdpSocket::bind("[::1]:0")?)?;
+    let new = endpoint.local_addr()?;
+    assert_ne!(old, new);
+    connection.send_datagram(bytes::Bytes::from_static(b"ping"))?;
+
+    let (id, from, to) = timeout(Duration::from_secs(5), async {
+        loop {
+            if let ConnectionEvent::PathChanged {
+                connection_id,
+                old,
+                new,
+            } = events.recv_skip_lagged().await?
+            {
+                return anyhow::Ok((connection_id, old, new));
+            }
+        }
+    })
+    .await??;
+    assert_eq!((id, from, to), (connection_id, old, new));
+
+    let current = server
+        .connection(connection_id)
+        .await
+        .expect("connection must stay registered under the same id");
+    assert_eq!(current.remote_address(), new);
+    assert_eq!(current.context().remote_addr(), Some(new));
+    assert_eq!(server.active_connection_count().await, 1);
+
+    connection.close(0u32.into(), b"done");
+    let remote_addr = timeout(Duration::from_secs(2), async {
+        loop {
+            if let ConnectionEvent::Disconnected { remote_addr, .. } =
+                events.recv_skip_lagged().await?
+            {
+                return anyhow::Ok(remote_addr);
+            }
+        }
+    })
+    .await??;
+    assert_eq!(remote_addr, new);
+    handle.shutdown().await?;
+    Ok(())
+}
@@ -10,9 +10,9 @@ use std::time::Duration;
 use tokio::time::timeout;
 use tracing::{Level, info};
 
-use unison::network::MessageType;
 use unison::network::channel::UnisonChannel;
-use unison::{ProtocolClient, ProtocolServer};
+use unison::network::{MessageType, ProtocolError};
+use unison::{ErrorCategory, NetworkError, ProtocolClient, ProtocolServer};
 
 /// テスト用のトレーシング初期化（複数テストで呼ばれても安全）
 fn init_tracing() {
@@ -286,3 +286,57 @@ async fn test_medium_quic_server_shutdown_disconnects_client() -> Result<()> {
     client.disconnect().await?;
     Ok(())
 }
+
+// ─────────────────────────────────────────────────
+// Test 7: handler の構造化エラー
+// ─────────────────────────────────────────────────
+
+/// send_error → client 側で NetworkError::Remote(ProtocolError) として受け取れる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_quic_handler_structured_error() -> Result<()> {
+    init_tracing();
+
+    let server = ProtocolServer::new();
+    server
+        .register_channel("memory", |_ctx, stream| async move {
+            let channel: UnisonChannel = UnisonChannel::new(stream);
+            while let Ok(msg) = channel.recv().await {
+                let err = ProtocolError::new(404, "memory not found")
+                    .with_details(serde_json::json!({"id": "m-1"}));
+                channel.send_error(msg.id, err).await?;
+            }
+            Ok(())
+        })
+        .await;
+
+    let handle = server.spawn_listen("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client
+        .connect(&format!("[{}]:{}", addr.ip(), addr.port()))
+        .await?;
+    let channel = client.open_channel("memory").await?;
+
+    let result = timeout(
+        Duration::from_secs(5),
+        channel.request::<_, serde_json::Value>("Get", &serde_json::json!({"id": "m-1"})),
+    )
+    .await?;
+    let err = result.expect_err("handler returned an error");
+    assert_eq!(err.category(), ErrorCategory::Application);
+    match err {
+        NetworkError::Remote(remote) => {
+            assert_eq!(remote.code, 404);
+            assert_eq!(remote.message, "memory not found");
+            assert_eq!(remote.details, Some(serde_json::json!({"id": "m-1"})));
+        }
+        other => panic!("expected NetworkError::Remote, got {other:?}"),
+    }
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
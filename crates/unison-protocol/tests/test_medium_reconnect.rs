@@ -0,0 +1,185 @@
+//! Medium x Integration: 自動再接続テスト
+//!
+//! `ReconnectPolicy` を設定した client が、 サーバー側から接続を切られると同じ URL へ
+//! 再接続し、 `__auth` をやり直して、 開いていた channel を開き直すこと
+//! (= 手元の `UnisonChannel` をそのまま使い続けられること)、 接続断の時点で応答待ち
+//! だった request が `NetworkError::Reconnecting` で失敗することを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use unison::network::{
+    ChannelRouter, ClientConnectionEvent, ClientConnectionEventReceiver, Credentials, NetworkError,
+    Principal, ReconnectPolicy, TokenAuthenticator,
+};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+fn router() -> ChannelRouter {
+    ChannelRouter::new()
+        .on_request("Ping", |_ctx, _req: serde_json::Value| async move {
+            Ok(serde_json::json!({ "ok": true }))
+        })
+        .on_request("Slow", |_ctx, _req: serde_json::Value| async move {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(serde_json::json!({ "ok": true }))
+        })
+}
+
+fn fast_policy() -> ReconnectPolicy {
+    ReconnectPolicy::default()
+        .with_initial_backoff(Duration::from_millis(50))
+        .with_jitter(0.0)
+        .with_attempt_timeout(Duration::from_millis(500))
+}
+
+/// 条件に合う event まで読み飛ばす
+async fn wait_for(
+    events: &mut ClientConnectionEventReceiver,
+    pred: impl Fn(&ClientConnectionEvent) -> bool,
+) -> Result<ClientConnectionEvent> {
+    timeout(Duration::from_secs(10), async {
+        loop {
+            let ev = events.recv_skip_lagged().await?;
+            if pred(&ev) {
+                return Ok(ev);
+            }
+        }
+    })
+    .await?
+}
+
+/// サーバーが接続を切ると、 認証と channel を引き継いで再接続する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_reconnect_reopens_channels() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .set_authenticator(TokenAuthenticator::new().with_token("token", Principal::new("alice")))
+        .await;
+    server.register_channel_router("ping", router()).await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let url = url(handle.local_addr());
+
+    let client = Arc::new(
+        ProtocolClient::new_default()?
+            .with_credentials(Credentials::bearer("token"))
+            .with_reconnect(fast_policy()),
+    );
+    let mut events = client.subscribe_connection_events();
+    client.connect(&url).await?;
+    let channel = Arc::new(client.open_channel("ping").await?);
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+
+    // 応答待ちの request を残したまま、 サーバー側から接続を切る
+    let slow = {
+        let channel = Arc::clone(&channel);
+        tokio::spawn(async move {
+            channel
+                .request::<_, serde_json::Value>("Slow", &serde_json::json!({}))
+                .await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    for conn in server.connections().await {
+        conn.close("kicked by test");
+    }
+
+    let result = timeout(Duration::from_secs(5), slow).await??;
+    assert!(
+        matches!(result, Err(NetworkError::Reconnecting)),
+        "{result:?}"
+    );
+
+    let ev = wait_for(&mut events, |ev| {
+        matches!(ev, ClientConnectionEvent::Reconnecting { .. })
+    })
+    .await?;
+    let ClientConnectionEvent::Reconnecting { attempt, .. } = ev else {
+        unreachable!()
+    };
+    assert_eq!(attempt, 1);
+    let ev = wait_for(&mut events, |ev| {
+        matches!(ev, ClientConnectionEvent::Reconnected { .. })
+    })
+    .await?;
+    let ClientConnectionEvent::Reconnected { channels, .. } = ev else {
+        unreachable!()
+    };
+    assert_eq!(channels, vec!["ping".to_string()]);
+
+    // 同じ UnisonChannel がそのまま使える (= 認証も通り直している)
+    assert!(!channel.is_closed());
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+    assert_eq!(
+        client.context().principal().map(|p| p.id.as_str()),
+        Some("alice")
+    );
+
+    // 明示 disconnect では再接続せず、 channel も閉じる
+    client.disconnect().await?;
+    assert!(channel.is_closed());
+    let reconnecting = timeout(
+        Duration::from_millis(300),
+        wait_for(&mut events, |ev| {
+            matches!(ev, ClientConnectionEvent::Reconnecting { .. })
+        }),
+    )
+    .await;
+    assert!(
+        reconnecting.is_err(),
+        "explicit disconnect must not reconnect"
+    );
+
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// サーバーが戻らなければ max_attempts で諦め、 channel を閉じる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_reconnect_gives_up() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    server.register_channel_router("ping", router()).await;
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let url = url(handle.local_addr());
+
+    let client = ProtocolClient::new_default()?.with_reconnect(fast_policy().with_max_attempts(2));
+    let mut events = client.subscribe_connection_events();
+    client.connect(&url).await?;
+    let channel = client.open_channel("ping").await?;
+
+    handle.shutdown().await?;
+
+    let ev = wait_for(&mut events, |ev| {
+        matches!(ev, ClientConnectionEvent::ReconnectFailed { .. })
+    })
+    .await?;
+    let ClientConnectionEvent::ReconnectFailed { attempts, .. } = ev else {
+        unreachable!()
+    };
+    assert_eq!(attempts, 2);
+    timeout(Duration::from_secs(1), channel.closed()).await?;
+    assert!(!client.is_connected().await);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Medium x Integration: サーバー発信 channel テスト
+//!
+//! `ProtocolServer::open_channel(connection_id, name)` でサーバーからクライアントへ
+//! channel を開き、 クライアント側の `register_channel` / `register_channel_router`
+//! handler が応答することを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::{connected_id, url};
+use unison::network::channel::UnisonChannel;
+use unison::network::{ChannelRouter, ConnectionId, MessageType, NetworkError};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// server → client に channel を開き、 client の router handler に request を送る
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_server_opens_channel_to_client() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client
+        .register_channel_router(
+            "jobs",
+            ChannelRouter::new().on_request("Run", |_ctx, req: serde_json::Value| async move {
+                Ok(serde_json::json!({"done": req["job"]}))
+            }),
+        )
+        .await;
+    client.connect(&url(addr)).await?;
+
+    let client_id = connected_id(&mut events).await;
+    let channel = server.open_channel(client_id, "jobs").await?;
+    let resp: serde_json::Value = channel
+        .request("Run", &serde_json::json!({"job": "build"}))
+        .await?;
+    assert_eq!(resp["done"], "build");
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
+
+/// client 側の生 handler でも server 発信 channel を受けられ、 未登録 name は reject
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_server_initiated_channel_raw_handler_and_nack() -> Result<()> {
+    init_tracing();
+
+    let server = Arc::new(ProtocolServer::new());
+    let mut events = server.subscribe_connection_events();
+    let handle = Arc::clone(&server).spawn_listen_shared("[::1]:0").await?;
+    let addr = handle.local_addr();
+
+    let client = ProtocolClient::new_default()?;
+    client.connect(&url(addr)).await?;
+    // connect 後の登録でも有効
+    client
+        .register_channel("notify", |_ctx, stream| async move {
+            let channel: UnisonChannel = UnisonChannel::new(stream);
+            let msg = channel.recv().await?;
+            assert_eq!(msg.msg_type, MessageType::Event);
+            channel
+                .send_event("Ack", &serde_json::json!({"seen": msg.method}))
+                .await?;
+            Ok(())
+        })
+        .await;
+
+    let client_id = connected_id(&mut events).await;
+
+    match server.open_channel(client_id, "missing").await {
+        Err(NetworkError::Protocol(msg)) => assert!(msg.contains("open rejected"), "{msg}"),
+        Err(e) => panic!("expected open rejected, got {e:?}"),
+        Ok(_) => panic!("expected open rejected, got Ok"),
+    }
+
+    let channel = server.open_channel(client_id, "notify").await?;
+    channel.send_event("Hello", &serde_json::json!({})).await?;
+    let ack = timeout(Duration::from_secs(5), channel.recv()).await??;
+    assert_eq!(ack.method, "Ack");
+    assert_eq!(ack.payload_as_value()?["seen"], "Hello");
+
+    // 接続の無い ID は NotConnected
+    let unknown = ConnectionId::nil();
+    assert!(matches!(
+        server.open_channel(unknown, "notify").await,
+        Err(NetworkError::NotConnected)
+    ));
+
+    channel.close().await?;
+    client.disconnect().await?;
+    handle.shutdown().await?;
+    Ok(())
+}
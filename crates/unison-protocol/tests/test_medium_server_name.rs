@@ -0,0 +1,151 @@
+//! Medium x Integration: TLS server name テスト
+//!
+//! CA が発行した証明書で bind した QUIC サーバーに対し、 client が接続先の hostname
+//! (= `connect(url)` / `ClientPool` の endpoint) または
+//! `connect_with_server_name()` で指定した名前で証明書を検証することを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::url;
+use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+use rustls::pki_types::CertificateDer;
+use unison::network::cert::CertSource;
+use unison::network::pool::ClientPool;
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::network::{ChannelRouter, UnisonChannel};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// CA を作って `names` の leaf cert を発行し、 書き出した cert/key と CA の DER を返す
+fn issue_cert(dir: &Path, names: &[&str]) -> Result<(CertSource, CertificateDer<'static>)> {
+    let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate()?)?;
+
+    let leaf_key = KeyPair::generate()?;
+    let leaf_names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let leaf = CertificateParams::new(leaf_names)?.signed_by(&leaf_key, &ca)?;
+
+    let (cert_path, key_path) = (dir.join("tls.crt"), dir.join("tls.key"));
+    std::fs::write(&cert_path, leaf.pem())?;
+    std::fs::write(&key_path, leaf_key.serialize_pem())?;
+    Ok((
+        CertSource::FromFile {
+            cert_path,
+            key_path,
+        },
+        ca.der().clone(),
+    ))
+}
+
+async fn spawn_server(cert: CertSource, bind: &str) -> Result<SocketAddr> {
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router(
+            "ping",
+            ChannelRouter::new().on_request("Ping", |_ctx, _req: serde_json::Value| async move {
+                Ok(serde_json::json!({ "ok": true }))
+            }),
+        )
+        .await;
+    let mut quic = QuicServer::builder(server).cert_source(cert).build();
+    quic.bind(bind).await?;
+    let addr = quic.local_addr().expect("bound");
+    tokio::spawn(async move { quic.start().await });
+    Ok(addr)
+}
+
+fn client_trusting(ca: &CertificateDer<'static>) -> Result<ProtocolClient> {
+    Ok(ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::Custom(vec![ca.clone()]))
+            .build()?,
+    ))
+}
+
+async fn ping(channel: &UnisonChannel) -> Result<()> {
+    let resp: serde_json::Value = channel.request("Ping", &serde_json::json!({})).await?;
+    assert_eq!(resp["ok"], true);
+    Ok(())
+}
+
+/// IP で dial しても、 指定した server name で CA 発行の証明書を検証する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_server_name_verifies_ca_issued_cert() -> Result<()> {
+    init_tracing();
+
+    let dir = tempfile::tempdir()?;
+    let (cert, ca) = issue_cert(dir.path(), &["broker.unison.test"])?;
+    let addr = spawn_server(cert, "[::1]:0").await?;
+    let url = url(addr);
+
+    let client = client_trusting(&ca)?;
+    client
+        .connect_with_server_name(&url, "broker.unison.test")
+        .await?;
+    let channel = client.open_channel("ping").await?;
+    ping(&channel).await?;
+    channel.close().await?;
+    client.disconnect().await?;
+
+    // server name が無ければ IP address で検証する (= 証明書に無いので拒否)
+    let result = timeout(Duration::from_secs(5), client_trusting(&ca)?.connect(&url)).await?;
+    assert!(result.is_err(), "cert without an IP SAN must be rejected");
+
+    // 証明書に無い名前も拒否する
+    let result = timeout(
+        Duration::from_secs(5),
+        client_trusting(&ca)?.connect_with_server_name(&url, "other.unison.test"),
+    )
+    .await?;
+    assert!(result.is_err(), "cert for another name must be rejected");
+    Ok(())
+}
+
+/// `connect(url)` と `ClientPool` は解決前の hostname で証明書を検証する
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_hostname_is_the_server_name() -> Result<()> {
+    init_tracing();
+
+    let dir = tempfile::tempdir()?;
+    let (cert, ca) = issue_cert(dir.path(), &["localhost"])?;
+    // localhost が IPv4 / IPv6 のどちらに解決されても届くように両方で待ち受ける
+    let addr = spawn_server(cert, "[::]:0").await?;
+    let url = format!("localhost:{}", addr.port());
+
+    let client = client_trusting(&ca)?;
+    client.connect(&url).await?;
+    let channel = client.open_channel("ping").await?;
+    ping(&channel).await?;
+    channel.close().await?;
+    client.disconnect().await?;
+
+    // pool は address ごとに dial するが、 検証は hostname で行う
+    let pool = ClientPool::new(move || client_trusting(&ca));
+    pool.connect([url.as_str()]).await?;
+    let channel = pool.open_channel("ping").await?;
+    ping(&channel).await?;
+    channel.close().await?;
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Medium x Integration: TransportOptions テスト
+//!
+//! idle timeout を短くしたサーバーに対し、 keep-alive を idle timeout より短く設定した
+//! client の接続は無通信でも切れず、 keep-alive を止めた client の接続は idle timeout で
+//! 閉じることを実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Level;
+
+use common::{spawn_quic_server, url};
+use unison::network::TransportOptions;
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+fn client(options: TransportOptions) -> Result<ProtocolClient> {
+    Ok(ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::SkipVerification)
+            .transport_options(options)
+            .build()?,
+    ))
+}
+
+/// keep-alive があれば idle timeout を過ぎても接続が残り、 無ければ閉じる
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_transport_keep_alive_outlives_idle_timeout() -> Result<()> {
+    init_tracing();
+
+    // サーバー側は keep-alive を送らず、 1 秒で idle timeout
+    let quic = QuicServer::builder(Arc::new(ProtocolServer::new()))
+        .transport_options(
+            TransportOptions::default()
+                .with_keep_alive_interval(None)
+                .with_max_idle_timeout(Some(Duration::from_secs(1))),
+        )
+        .build();
+    let url = url(spawn_quic_server(quic).await?);
+
+    let kept = client(
+        TransportOptions::default()
+            .with_keep_alive_interval(Some(Duration::from_millis(200)))
+            .with_max_idle_timeout(Some(Duration::from_secs(30))),
+    )?;
+    kept.connect(&url).await?;
+
+    let idle = client(TransportOptions::default().with_keep_alive_interval(None))?;
+    idle.connect(&url).await?;
+
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert!(
+        kept.is_connected().await,
+        "keep-alive must keep the connection"
+    );
+    assert!(
+        !idle.is_connected().await,
+        "connection without keep-alive must hit the negotiated idle timeout"
+    );
+
+    kept.disconnect().await?;
+    Ok(())
+}
+
+/// 矛盾した設定は build 時に拒否される
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_transport_invalid_options_rejected() -> Result<()> {
+    init_tracing();
+
+    let invalid = TransportOptions::default()
+        .with_keep_alive_interval(Some(Duration::from_secs(30)))
+        .with_max_idle_timeout(Some(Duration::from_secs(10)));
+
+    let result = QuicClient::builder()
+        .transport_options(invalid.clone())
+        .build();
+    assert!(result.is_err(), "client build must validate");
+
+    let mut quic = QuicServer::builder(Arc::new(ProtocolServer::new()))
+        .transport_options(invalid)
+        .build();
+    assert!(quic.bind("[::1]:0").await.is_err(), "bind must validate");
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! Medium x Integration: TLS session resumption / 0-RTT テスト
+//!
+//! `enable_0rtt` の client が同じ `QuicClient` で再接続すると、 保持していた session で
+//! resumption して 0-RTT で接続し、 replay-safe な channel を handshake の完了前に開いて
+//! 使えること、 サーバーが replay-safe と挙げていない channel の 0-RTT の open は
+//! nack されること、 サーバーが 0-RTT を有効にしていなければ通常の handshake に戻ることを
+//! 実 QUIC 上で検証する。
+//!
+//! すべて `#[ignore]` 付き — `cargo test -- --ignored` で実行。
+
+mod common;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::Level;
+
+use common::{spawn_quic_server, url};
+use unison::network::quic::{QuicClient, QuicServer};
+use unison::network::trust::TrustAnchors;
+use unison::network::{ChannelRouter, EarlyDataState, NetworkError, UnisonChannel};
+use unison::{ProtocolClient, ProtocolServer};
+
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_test_writer()
+        .try_init();
+}
+
+/// `Get` に固定の値を返す replay-safe な `catalog` channel と、 `Place` の呼び出しを
+/// `orders` に数える `orders` channel を持つサーバーを起動し、 URL を返す
+async fn spawn_server_counting(zero_rtt: bool, orders: Arc<AtomicUsize>) -> Result<String> {
+    let server = Arc::new(ProtocolServer::new());
+    server
+        .register_channel_router(
+            "catalog",
+            ChannelRouter::new().on_request("Get", |_ctx, _req: serde_json::Value| async move {
+                Ok(serde_json::json!({ "item": "widget" }))
+            }),
+        )
+        .await;
+    server
+        .register_channel_router(
+            "orders",
+            ChannelRouter::new().on_request("Place", move |_ctx, _req: serde_json::Value| {
+                let orders = Arc::clone(&orders);
+                async move {
+                    orders.fetch_add(1, Ordering::SeqCst);
+                    Ok(serde_json::json!({ "placed": true }))
+                }
+            }),
+        )
+        .await;
+    server.set_zero_rtt_channels(["catalog"]).await;
+    let quic = QuicServer::builder(server).enable_0rtt(zero_rtt).build();
+    let addr = spawn_quic_server(quic).await?;
+    Ok(url(addr))
+}
+
+async fn spawn_server(zero_rtt: bool) -> Result<String> {
+    spawn_server_counting(zero_rtt, Arc::default()).await
+}
+
+fn client() -> Result<ProtocolClient> {
+    Ok(ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::SkipVerification)
+            .enable_0rtt(true)
+            .build()?,
+    )
+    .with_zero_rtt_channels(["catalog"]))
+}
+
+async fn get(channel: &UnisonChannel) -> Result<String> {
+    let resp: serde_json::Value = channel.request("Get", &serde_json::json!({})).await?;
+    Ok(resp["item"].as_str().unwrap_or_default().to_string())
+}
+
+/// 2 回目の接続は 0-RTT になり、 replay-safe な channel を handshake 前に開ける
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_zero_rtt_reconnect_uses_early_data() -> Result<()> {
+    init_tracing();
+
+    let url = spawn_server(true).await?;
+    let client = client()?;
+
+    // 初回は resumption できる session が無い
+    client.connect(&url).await?;
+    assert_eq!(client.early_data(), EarlyDataState::NotUsed);
+    let channel = client.open_channel("catalog").await?;
+    assert_eq!(get(&channel).await?, "widget");
+    client.disconnect().await?;
+
+    client.connect(&url).await?;
+    assert_ne!(client.early_data(), EarlyDataState::NotUsed);
+    let channel = client.open_channel("catalog").await?;
+    assert_eq!(get(&channel).await?, "widget");
+    // handshake が終わればサーバーが early data を受け入れたことが分かる
+    timeout(Duration::from_secs(2), async {
+        while client.early_data() == EarlyDataState::Pending {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    assert_eq!(client.early_data(), EarlyDataState::Accepted);
+    client.disconnect().await?;
+    Ok(())
+}
+
+/// replay-safe でない channel を 0-RTT で open しても、 サーバーは handshake の完了前には
+/// 受け付けない
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_zero_rtt_rejects_non_idempotent_channel() -> Result<()> {
+    init_tracing();
+
+    let orders = Arc::new(AtomicUsize::new(0));
+    let url = spawn_server_counting(true, Arc::clone(&orders)).await?;
+    // client が replay-safe でない `orders` も 0-RTT で開く設定を誤って持っている
+    let client = ProtocolClient::new(
+        QuicClient::builder()
+            .trust_anchors(TrustAnchors::SkipVerification)
+            .enable_0rtt(true)
+            .build()?,
+    )
+    .with_zero_rtt_channels(["catalog", "orders"]);
+
+    // 初回の接続で resumption 用の session を受け取る
+    client.connect(&url).await?;
+    let channel = client.open_channel("catalog").await?;
+    assert_eq!(get(&channel).await?, "widget");
+    client.disconnect().await?;
+
+    client.connect(&url).await?;
+    assert_eq!(client.early_data(), EarlyDataState::Pending);
+    match client.open_channel("orders").await {
+        Err(NetworkError::Protocol(msg)) => assert!(msg.contains("early-data"), "{msg}"),
+        Err(e) => panic!("expected early-data nack, got {e:?}"),
+        Ok(_) => panic!("expected early-data nack, got Ok"),
+    }
+    assert_eq!(orders.load(Ordering::SeqCst), 0);
+
+    // handshake の完了後は同じ接続で開ける
+    timeout(Duration::from_secs(2), async {
+        while client.early_data() == EarlyDataState::Pending {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    let channel = client.open_channel("orders").await?;
+    let resp: serde_json::Value = channel.request("Place", &serde_json::json!({})).await?;
+    assert_eq!(resp["placed"], true);
+    assert_eq!(orders.load(Ordering::SeqCst), 1);
+
+    client.disconnect().await?;
+    Ok(())
+}
+
+/// サーバーが 0-RTT を有効にしていなければ、 再接続は通常の handshake で行う
+#[tokio::test]
+#[ignore = "Medium: requires QUIC runtime"]
+async fn test_medium_zero_rtt_server_opt_out() -> Result<()> {
+    init_tracing();
+
+    let url = spawn_server(false).await?;
+    let client = client()?;
+
+    for _ in 0..2 {
+        client.connect(&url).await?;
+        assert_eq!(client.early_data(), EarlyDataState::NotUsed);
+        let channel = client.open_channel("catalog").await?;
+        assert_eq!(get(&channel).await?, "widget");
+        client.disconnect().await?;
+    }
+    Ok(())
+}